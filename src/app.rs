@@ -1,17 +1,40 @@
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use clap::ValueEnum;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
 use ratatui::prelude::*;
+use std::cell::Cell;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Child;
 use std::sync::mpsc::{self, Receiver};
-use std::time::Duration;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use crate::audio::extractor::AudioExtractor;
-use crate::subtitle::burner::SubtitleBurner;
-use crate::subtitle::generator::SubtitleGenerator;
+use crate::audio::silence;
+use crate::cli::args::{SubtitlePosition, WhisperModel};
+use crate::config::Config;
+use crate::subtitle::ass::{AssStyle, DEFAULT_FRAME_HEIGHT};
+use crate::subtitle::burner::{self, OverlayBand, SubtitleBurner};
+use crate::subtitle::diff::{self, DiffSummary};
+use crate::subtitle::embedded::{self, EmbeddedTrack};
+use crate::subtitle::fix;
+use crate::subtitle::format::{self, SubtitleFormat};
+use crate::subtitle::generator::{DetectedLanguage, SubtitleGenerator};
+use crate::subtitle::lint::{self, LintIssue, LintRules};
+use crate::subtitle::resegment;
 use crate::subtitle::srt::Subtitle;
+use crate::subtitle::timing;
 use crate::ui;
+use crate::ui::keymap::{Action, KeyMap};
+use crate::utils::autosave;
+use crate::utils::cancel::CancelHandle;
+use crate::utils::eta::StageTimer;
+use crate::utils::mpv::{self, MpvIpc};
+use crate::utils::overwrite::{unique_path, OverwritePolicy};
+use crate::utils::tail::OutputTail;
+use crate::utils::temp::TempWorkspace;
+use crate::utils::template::{self, TemplateValues};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppState {
@@ -23,17 +46,141 @@ pub enum AppState {
     BurningSubtitles,
     ExtractingOverlay,
     PreviewingOverlay,
+    TestBurning,
     Done,
 }
 
+/// Length of the excerpt rendered by a test burn, centered on the selected cue
+const TEST_BURN_WINDOW_MS: u64 = 10_000;
+
+/// Modal prompt shown on top of the editor
+#[derive(Debug, Clone, PartialEq)]
+pub enum Prompt {
+    /// Ask for the model and language to regenerate with ("<model> [language]")
+    RegenerateSettings,
+    /// Confirm regeneration, which replaces the current subtitles
+    ConfirmRegenerate {
+        model: WhisperModel,
+        language: String,
+    },
+    /// Regeneration finished: keep the new subtitles or restore the old ones
+    ReviewRegenerate { summary: DiffSummary },
+    /// The chosen video has no audio; offer to caption it by hand
+    NoAudioStream,
+    /// The chosen video carries subtitle tracks; offer one instead of transcribing
+    EmbeddedSubtitles { tracks: Vec<EmbeddedTrack> },
+    /// Some cues run past the end of the video: trim them, burn anyway or cancel
+    SubtitlesPastEnd { count: usize, media_end_ms: u64 },
+    /// The burn output already exists: overwrite, rename or cancel
+    OverwriteOutput { path: PathBuf },
+    /// Type exact start and end timecodes for the selected cue
+    CueTiming {
+        /// Why the last entry was rejected, shown under the input
+        error: Option<String>,
+    },
+    /// Type a timecode to jump to the cue showing at that time
+    GoToTime {
+        /// Why the last entry was rejected, shown under the input
+        error: Option<String>,
+    },
+    /// The opened SRT has newer unsaved edits from an earlier session
+    RestoreAutosave { path: PathBuf },
+    /// Leaving the editor with unsaved edits: save, discard or stay
+    UnsavedChanges { then: LeaveAction },
+    /// Type the path of another subtitle file to open in a new tab
+    OpenDocument,
+    /// Type the text to replace across every cue
+    FindText {
+        mode: FindMode,
+        error: Option<String>,
+    },
+    /// Type what `find` is replaced with
+    ReplaceWith { find: String, mode: FindMode },
+    /// Confirm re-cutting cues `first` to `last` at pauses in the audio,
+    /// which rewrites their timing and text
+    ConfirmResegment { first: usize, last: usize },
+    /// Show how much a replace changes before applying it
+    ConfirmReplace {
+        find: String,
+        to: String,
+        mode: FindMode,
+        occurrences: usize,
+        cues: usize,
+    },
+}
+
+/// How find-and-replace matches its text (Tab cycles while typing it)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FindMode {
+    IgnoreCase,
+    MatchCase,
+    Regex,
+}
+
+impl FindMode {
+    pub fn next(self) -> Self {
+        match self {
+            FindMode::IgnoreCase => FindMode::MatchCase,
+            FindMode::MatchCase => FindMode::Regex,
+            FindMode::Regex => FindMode::IgnoreCase,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FindMode::IgnoreCase => "ignore case",
+            FindMode::MatchCase => "match case",
+            FindMode::Regex => "regex",
+        }
+    }
+
+    fn replacement(self, find: &str, to: &str) -> Result<fix::Replacement> {
+        match self {
+            FindMode::IgnoreCase => fix::Replacement::literal(find, to, false),
+            FindMode::MatchCase => fix::Replacement::literal(find, to, true),
+            FindMode::Regex => fix::Replacement::regex(find, to, true),
+        }
+    }
+}
+
+/// Where the editor was headed when it asked about unsaved changes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeaveAction {
+    Quit,
+    Home,
+}
+
 #[derive(Debug, Clone)]
 pub enum ProgressMessage {
     Progress(f32, String),
+    /// A subtitle produced while transcription is still running
+    Segment(Subtitle),
+    /// The final transcribed cues, cleaned up as saved and with per-word
+    /// confidence; sent once, before `Complete`
+    Transcript(Vec<Subtitle>),
+    /// The language Whisper detected, when it was asked to (`auto`)
+    Language(DetectedLanguage),
     Complete,
     Error(String),
 }
 
+/// A subtitle file open in an editor tab other than the active one. The
+/// active tab's state lives in the matching `App` fields.
+pub struct Document {
+    pub srt_path: Option<PathBuf>,
+    pub subtitles: Vec<Subtitle>,
+    pub selected_index: usize,
+    pub text_scroll: u16,
+    pub selection_anchor: Option<usize>,
+    pub selection: Option<(usize, usize)>,
+    pub dirty: bool,
+    last_save: Instant,
+    unsaved_edits: u32,
+    subtitle_format: Option<SubtitleFormat>,
+}
+
 pub struct App {
+    pub config: Config,
     pub state: AppState,
     pub should_quit: bool,
     pub video_path: Option<PathBuf>,
@@ -42,24 +189,92 @@ pub struct App {
     pub output_path: Option<PathBuf>,
     pub subtitles: Vec<Subtitle>,
     pub selected_index: usize,
+    pub lint_rules: LintRules,
     pub editing_subtitle: bool,
     pub edit_buffer: String,
+    /// Edit cue text in its list row, spreadsheet style, instead of the panel
+    pub inline_edit: bool,
+    /// Lines scrolled in the edit panel's cue text; reset when the selection changes
+    pub text_scroll: u16,
+    /// How far the cue text can scroll, as measured by the last draw
+    pub text_overflow: Cell<u16>,
+    /// Subtitles have edits that aren't saved to the SRT yet
+    pub dirty: bool,
+    /// When edits were last written out, by a save or an autosave
+    pub last_save: Instant,
+    /// Edits made since `last_save`
+    unsaved_edits: u32,
     pub progress: f32,
     pub progress_message: String,
+    /// Step count and rate estimate for the running operation
+    pub stage: Option<StageTimer>,
     pub file_browser: FileBrowser,
     pub error_message: Option<String>,
     progress_rx: Option<Receiver<ProgressMessage>>,
+    /// Stops the running background job (FFmpeg or Whisper)
+    job: CancelHandle,
+    /// Holds the extracted audio; removed when the app exits or starts over
+    workspace: Option<TempWorkspace>,
+    /// Holds the latest test burn clip until the next one replaces it
+    test_burn: Option<(TempWorkspace, PathBuf)>,
+    /// Model download started alongside audio extraction; joined before transcribing
+    model_download: Option<JoinHandle<Result<()>>>,
+    /// Cue where a `V` range selection started, while it is being extended
+    pub selection_anchor: Option<usize>,
+    /// Finished range selection (first and last cue); `b` burns only its span
+    pub selection: Option<(usize, usize)>,
+    /// Whisper is still producing segments in the background
+    pub transcribing: bool,
+    /// Cues as Whisper streamed them in, to tell apart edits made meanwhile
+    stream_draft: Vec<Subtitle>,
+    /// Edits made while transcribing were kept over the final cues
+    kept_stream_edits: bool,
+    /// Highlight words Whisper was unsure of in the edit panel
+    pub show_confidence: bool,
+    /// Timing edits to a cue move the cues after it by the same amount
+    pub ripple: bool,
+    /// Editor tabs in order, with `None` in the active tab's slot; empty while
+    /// only one file is open
+    pub documents: Vec<Option<Document>>,
+    /// Editor key bindings, from the config's `[keys]`
+    pub keymap: KeyMap,
+    /// The last transcription found no speech at all
+    pub no_speech: bool,
+    /// What the last `auto` transcription detected the language as
+    pub detected_language: Option<DetectedLanguage>,
+    /// Subtitles from before a regenerate, kept until the new ones are reviewed
+    regenerate_backup: Option<Vec<Subtitle>>,
+    /// What the last replace or re-segment was, with the subtitles from before
+    /// it and right after it. Undo only applies while the cues still match the
+    /// latter.
+    undo: Option<(&'static str, Vec<Subtitle>, Vec<Subtitle>)>,
+    // Transcription settings
+    pub whisper_model: WhisperModel,
+    pub whisper_language: String,
+    // Modal prompt state
+    pub prompt: Option<Prompt>,
+    pub prompt_buffer: String,
+    /// Whether burning may replace an existing output
+    pub overwrite: OverwritePolicy,
+    /// Format of the subtitle file when it was given explicitly (`edit --input-format`)
+    subtitle_format: Option<SubtitleFormat>,
     // Overlay settings for burning
     pub overlay_height: u32,
     pub overlay_width: Option<u32>,
     pub overlay_x_offset: i32,
     pub overlay_y_offset: i32,
+    /// Width and height of the loaded video, when ffprobe could read them
+    pub video_size: Option<(u32, u32)>,
+    /// Length of the loaded video in milliseconds, when ffprobe could read it
+    pub video_duration_ms: Option<u64>,
     // Preview state
     pub preview_active: bool,
     preview_process: Option<Child>,
+    /// Last lines the preview player wrote to stderr, for error reports
+    preview_stderr: Option<OutputTail>,
     preview_socket_path: Option<PathBuf>,
-    preview_video_width: u32,
-    preview_video_height: u32,
+    /// Where to pause the preview while auditioning a single cue
+    audition_end_ms: Option<u64>,
 }
 
 pub struct FileBrowser {
@@ -158,6 +373,7 @@ fn is_video_file(path: &PathBuf) -> bool {
 impl App {
     pub fn new() -> Self {
         Self {
+            config: Config::default(),
             state: AppState::Home,
             should_quit: false,
             video_path: None,
@@ -166,32 +382,247 @@ impl App {
             output_path: None,
             subtitles: Vec::new(),
             selected_index: 0,
+            lint_rules: LintRules::default(),
             editing_subtitle: false,
             edit_buffer: String::new(),
+            inline_edit: false,
+            text_scroll: 0,
+            text_overflow: Cell::new(0),
+            dirty: false,
+            last_save: Instant::now(),
+            unsaved_edits: 0,
             progress: 0.0,
             progress_message: String::new(),
+            stage: None,
             file_browser: FileBrowser::new(),
             error_message: None,
             progress_rx: None,
+            job: CancelHandle::new(),
+            workspace: None,
+            test_burn: None,
+            model_download: None,
+            selection_anchor: None,
+            subtitle_format: None,
+            selection: None,
+            transcribing: false,
+            stream_draft: Vec::new(),
+            kept_stream_edits: false,
+            show_confidence: false,
+            ripple: false,
+            documents: Vec::new(),
+            keymap: KeyMap::default(),
+            no_speech: false,
+            detected_language: None,
+            regenerate_backup: None,
+            undo: None,
+            whisper_model: WhisperModel::Base,
+            whisper_language: "en".to_string(),
+            prompt: None,
+            prompt_buffer: String::new(),
+            overwrite: OverwritePolicy::Ask,
             overlay_height: 200,
             overlay_width: None,
             overlay_x_offset: 0,
             overlay_y_offset: 0,
+            video_size: None,
+            video_duration_ms: None,
             preview_active: false,
             preview_process: None,
+            preview_stderr: None,
             preview_socket_path: None,
-            preview_video_width: 0,
-            preview_video_height: 0,
+            audition_end_ms: None,
         }
     }
 
-    pub fn load_srt_file(&mut self, path: &PathBuf) -> Result<()> {
-        use crate::subtitle::srt;
+    /// Use settings loaded from the config file
+    pub fn with_config(mut self, config: Config) -> Self {
+        // Bad bindings were already reported when the config was loaded
+        self.keymap = config.keymap().unwrap_or_default();
+        self.config = config;
+        self
+    }
+
+    /// Read and save the subtitle file as `format` instead of going by its extension
+    pub fn with_subtitle_format(mut self, format: Option<SubtitleFormat>) -> Self {
+        self.subtitle_format = format;
+        self
+    }
+
+    /// What to do when a burn would replace an existing file (`--yes` / `--no-overwrite`)
+    pub fn with_overwrite_policy(mut self, policy: OverwritePolicy) -> Self {
+        self.overwrite = policy;
+        self
+    }
+
+    /// Timing and readability problems in the current subtitles
+    pub fn lint_issues(&self) -> Vec<LintIssue> {
+        // Check line widths against the overlay as it would currently be burned;
+        // its default width is the video's, which is only known once previewed
+        let mut rules = self.lint_rules.clone();
+        rules.overlay_width = self
+            .overlay_width
+            .or(self.video_size.map(|(width, _)| width));
+        rules.font_size = SubtitleBurner::new().font_size_for_height(self.overlay_height);
+        rules.char_width_factor = self.config.char_width_factor();
+        rules.media_end_ms = self.video_duration_ms;
+        lint::lint(&self.subtitles, &rules)
+    }
+
+    /// Set the video being captioned and probe its size, which bounds the
+    /// overlay, and its length, which cues shouldn't run past
+    pub fn set_video(&mut self, path: Option<PathBuf>) {
+        let burner = SubtitleBurner::new();
+        self.video_size = path
+            .as_deref()
+            .and_then(|p| burner.get_video_dimensions(p).ok());
+        self.video_duration_ms = path
+            .as_deref()
+            .and_then(|p| burner.get_video_duration_ms(p).ok());
+        self.video_path = path;
+        self.clamp_overlay();
+    }
+
+    /// Where the overlay band will land in the video, once its size is known
+    pub fn overlay_band(&self) -> Option<OverlayBand> {
+        self.video_size.map(|video| {
+            OverlayBand::new(
+                video,
+                self.overlay_width.unwrap_or(video.0),
+                self.overlay_height,
+                self.overlay_x_offset,
+                self.overlay_y_offset,
+            )
+        })
+    }
+
+    /// X and Y offset ranges that keep the overlay band inside the video
+    pub fn overlay_offset_limits(&self) -> Option<((i32, i32), (i32, i32))> {
+        self.video_size.map(|video| {
+            OverlayBand::offset_limits(
+                video,
+                self.overlay_width.unwrap_or(video.0),
+                self.overlay_height,
+            )
+        })
+    }
+
+    /// Pull the overlay size and offsets back inside the video
+    fn clamp_overlay(&mut self) {
+        let Some((video_width, video_height)) = self.video_size else {
+            return;
+        };
+        self.overlay_height = self.overlay_height.min(video_height);
+        self.overlay_width = self.overlay_width.map(|w| w.min(video_width));
+        if let Some(((x_min, x_max), (y_min, y_max))) = self.overlay_offset_limits() {
+            self.overlay_x_offset = self.overlay_x_offset.clamp(x_min, x_max);
+            self.overlay_y_offset = self.overlay_y_offset.clamp(y_min, y_max);
+        }
+    }
+
+    /// Apply one of the h/H/w/W/x/X/y/Y overlay adjustments, noting when it
+    /// ran into the edge of the video
+    fn adjust_overlay(&mut self, key: char) {
+        let default_width = self.video_size.map_or(1920, |(width, _)| width);
+        match key {
+            'h' => self.overlay_height = self.overlay_height.saturating_sub(10),
+            'H' => self.overlay_height = self.overlay_height.saturating_add(10),
+            'w' => {
+                let current = self.overlay_width.unwrap_or(default_width);
+                self.overlay_width = Some(current.saturating_sub(10));
+            }
+            // From auto, the first press switches to an explicit full width
+            'W' => {
+                self.overlay_width = Some(
+                    self.overlay_width
+                        .map_or(default_width, |w| w.saturating_add(10)),
+                )
+            }
+            'x' => self.overlay_x_offset = self.overlay_x_offset.saturating_sub(10),
+            'X' => self.overlay_x_offset = self.overlay_x_offset.saturating_add(10),
+            'y' => self.overlay_y_offset = self.overlay_y_offset.saturating_sub(10),
+            'Y' => self.overlay_y_offset = self.overlay_y_offset.saturating_add(10),
+            _ => return,
+        }
+
+        let wanted = self.overlay_value(key);
+        self.clamp_overlay();
+        let (label, value) = match key {
+            'h' | 'H' => ("Overlay height", format!("{}px", self.overlay_height)),
+            'w' | 'W' => (
+                "Overlay width",
+                format!("{}px", self.overlay_width.unwrap_or(default_width)),
+            ),
+            'x' | 'X' => ("Overlay X offset", format!("{}px", self.overlay_x_offset)),
+            _ => ("Overlay Y offset", format!("{}px", self.overlay_y_offset)),
+        };
+        self.progress_message = if self.overlay_value(key) != wanted {
+            let limit = if key.is_lowercase() { "min" } else { "max" };
+            format!(
+                "{}: {} ({} {})",
+                label,
+                value,
+                limit,
+                self.overlay_value(key)
+            )
+        } else {
+            format!("{}: {}", label, value)
+        };
+
+        if self.preview_active {
+            self.update_preview_overlay();
+        }
+    }
+
+    /// The overlay setting an adjustment key changes
+    fn overlay_value(&self, key: char) -> i64 {
+        match key.to_ascii_lowercase() {
+            'h' => self.overlay_height as i64,
+            'w' => self.overlay_width.unwrap_or(0) as i64,
+            'x' => self.overlay_x_offset as i64,
+            _ => self.overlay_y_offset as i64,
+        }
+    }
+
+    /// First and last cue of the range selection, including one still being extended
+    pub fn selected_range(&self) -> Option<(usize, usize)> {
+        let (a, b) = match self.selection_anchor {
+            Some(anchor) => (anchor, self.selected_index),
+            None => self.selection?,
+        };
+        // Cues may have been deleted since the range was marked
+        let last = self.subtitles.len().checked_sub(1)?;
+        Some((a.min(b).min(last), a.max(b).min(last)))
+    }
+
+    /// Span of the finished range selection as (start, duration) in milliseconds
+    fn selection_window(&self) -> Option<(u64, u64)> {
+        if self.selection_anchor.is_some() {
+            return None;
+        }
+        let (first, last) = self.selected_range()?;
+        let cues = &self.subtitles[first..=last];
+        let start = cues.iter().map(|sub| sub.start_time).min()?;
+        let end = cues.iter().map(|sub| sub.end_time).max()?;
+        Some((start, end.saturating_sub(start)))
+    }
+
+    /// Whether a background job (extraction, transcription, burning) is running
+    pub fn is_busy(&self) -> bool {
+        self.progress_rx.is_some()
+    }
 
-        self.subtitles = srt::parse_srt(path)?;
+    pub fn load_srt_file(&mut self, path: &PathBuf) -> Result<()> {
+        self.subtitles = format::load_subtitles(path, self.subtitle_format)?;
         self.srt_path = Some(path.clone());
         self.state = AppState::Editing;
         self.selected_index = 0;
+        self.dirty = false;
+
+        if let Some(autosave) =
+            autosave::newer_autosave(path, self.config.paths.temp_dir.as_deref())
+        {
+            self.prompt = Some(Prompt::RestoreAutosave { path: autosave });
+        }
 
         Ok(())
     }
@@ -203,6 +634,7 @@ impl App {
         loop {
             // Check for progress updates
             self.check_progress();
+            self.autosave_if_due();
 
             // Draw UI
             terminal.draw(|frame| self.draw(frame))?;
@@ -211,7 +643,7 @@ impl App {
             if event::poll(Duration::from_millis(100))? {
                 if let Event::Key(key) = event::read()? {
                     if key.kind == KeyEventKind::Press {
-                        self.handle_key(key.code).await?;
+                        self.handle_key(key).await?;
                     }
                 }
             }
@@ -220,6 +652,13 @@ impl App {
                 break;
             }
         }
+
+        // Leave unsaved edits behind for the next session; a clean exit needs no autosave
+        if self.dirty {
+            self.write_autosave();
+        } else {
+            self.discard_autosave();
+        }
         Ok(())
     }
 
@@ -232,28 +671,38 @@ impl App {
                         // Preview closed
                         self.preview_active = false;
                         self.preview_process = None;
+                        let stderr = self.preview_stderr.take().map(|tail| tail.summary());
                         if status.success() {
                             self.progress_message = "Preview closed".to_string();
                         } else {
-                            self.error_message = Some(format!(
-                                "Preview exited with error (code: {:?}). Check if MPV is installed.",
-                                status.code()
-                            ));
+                            let reason = match status.code() {
+                                Some(code) => format!("exit code {}", code),
+                                None => "killed by a signal".to_string(),
+                            };
+                            self.error_message = Some(match stderr {
+                                Some(stderr) if !stderr.is_empty() => {
+                                    format!("mpv preview exited ({}): {}", reason, stderr)
+                                }
+                                _ => format!("mpv preview exited ({})", reason),
+                            });
                         }
                     }
                     Ok(None) => {
                         // Still running
                     }
-                    Err(_) => {
+                    Err(e) => {
                         // Error checking status, assume dead
                         self.preview_active = false;
                         self.preview_process = None;
-                        self.error_message = Some("Preview process error".to_string());
+                        self.preview_stderr = None;
+                        self.error_message = Some(format!("Lost track of mpv preview: {}", e));
                     }
                 }
             }
         }
 
+        self.check_audition();
+
         // Take the receiver out to avoid borrow issues
         let rx = match self.progress_rx.take() {
             Some(rx) => rx,
@@ -275,24 +724,73 @@ impl App {
                 ProgressMessage::Progress(progress, message) => {
                     self.progress = progress;
                     self.progress_message = message;
+                    if let Some(stage) = &mut self.stage {
+                        stage.update(progress);
+                    }
+                }
+                ProgressMessage::Segment(mut subtitle) => {
+                    // Show the editor as soon as the first cue arrives
+                    if self.state == AppState::GeneratingSubtitles {
+                        self.state = AppState::Editing;
+                    }
+                    subtitle.index = self.subtitles.len() + 1;
+                    self.stream_draft.push(subtitle.clone());
+                    self.subtitles.push(subtitle);
+                }
+                ProgressMessage::Transcript(transcript) => {
+                    // The streamed cues were a draft; the editor shows what was
+                    // saved, except where the user edited the draft meanwhile
+                    let draft = std::mem::take(&mut self.stream_draft);
+                    if self.subtitles == draft {
+                        self.subtitles = transcript;
+                    } else {
+                        self.subtitles =
+                            diff::carry_over_edits(&draft, &self.subtitles, transcript);
+                        // The saved file doesn't have them (and replaced any save made meanwhile)
+                        self.kept_stream_edits = true;
+                        self.dirty = true;
+                        self.unsaved_edits = self.unsaved_edits.max(1);
+                    }
+                }
+                ProgressMessage::Language(language) => {
+                    self.detected_language = Some(language);
                 }
                 ProgressMessage::Complete => {
+                    if self.transcribing {
+                        self.selected_index = self
+                            .selected_index
+                            .min(self.subtitles.len().saturating_sub(1));
+                        self.transcribing = false;
+                        self.no_speech = self.subtitles.is_empty();
+                        self.state = AppState::Editing;
+                        if let Some(old) = &self.regenerate_backup {
+                            let summary = diff::diff_subtitles(old, &self.subtitles);
+                            self.prompt = Some(Prompt::ReviewRegenerate { summary });
+                        }
+                        // Otherwise keep the generator's summary, which counts
+                        // the repeated cues it removed
+                        if self.no_speech {
+                            self.progress_message =
+                                "No speech detected - press a to caption manually".to_string();
+                        }
+                        if std::mem::take(&mut self.kept_stream_edits) {
+                            self.progress_message.push_str(
+                                " Kept your edits made while transcribing - press s to save them",
+                            );
+                        }
+                        if let Some(language) = &self.detected_language {
+                            self.progress_message
+                                .push_str(&format!(" - Detected: {}", language));
+                        }
+                        should_drop_rx = true;
+                        continue;
+                    }
                     match self.state {
                         AppState::ExtractingAudio => {
                             self.state = AppState::GeneratingSubtitles;
                             self.progress = 0.0;
                             should_start_generation = true;
                         }
-                        AppState::GeneratingSubtitles => {
-                            // Load the generated subtitles from file
-                            if let Some(srt_path) = &self.srt_path {
-                                if let Ok(subs) = crate::subtitle::srt::parse_srt(srt_path) {
-                                    self.subtitles = subs;
-                                }
-                            }
-                            self.state = AppState::Editing;
-                            should_drop_rx = true;
-                        }
                         AppState::BurningSubtitles => {
                             self.state = AppState::Done;
                             should_drop_rx = true;
@@ -302,12 +800,39 @@ impl App {
                             self.state = AppState::Editing;
                             should_drop_rx = true;
                         }
+                        AppState::TestBurning => {
+                            self.state = AppState::Editing;
+                            should_drop_rx = true;
+                            if let Some((_, clip)) = &self.test_burn {
+                                match SubtitleBurner::launch_player(clip) {
+                                    Ok(_) => {
+                                        self.progress_message =
+                                            "Playing test burn (close the player when done)"
+                                                .to_string()
+                                    }
+                                    Err(e) => self.error_message = Some(e.to_string()),
+                                }
+                            }
+                        }
                         _ => {}
                     }
                 }
                 ProgressMessage::Error(err) => {
                     self.error_message = Some(err);
+                    // A failed regenerate leaves the previous subtitles in place
+                    if self.transcribing {
+                        if let Some(old) = self.regenerate_backup.take() {
+                            self.subtitles = old;
+                            self.selected_index = 0;
+                            self.state = AppState::Editing;
+                        }
+                    }
+                    self.transcribing = false;
                     should_drop_rx = true;
+                    // A failed test burn shouldn't cost the editing session
+                    if self.state == AppState::TestBurning {
+                        self.state = AppState::Editing;
+                    }
                 }
             }
         }
@@ -319,11 +844,13 @@ impl App {
 
         // Start subtitle generation after we've released the borrow
         if should_start_generation {
+            self.stage = Some(StageTimer::new(2, 3));
             self.start_subtitle_generation();
         }
     }
 
-    async fn handle_key(&mut self, key: KeyCode) -> Result<()> {
+    async fn handle_key(&mut self, event: KeyEvent) -> Result<()> {
+        let key = event.code;
         // Clear error on any key press
         if self.error_message.is_some() && key != KeyCode::Esc {
             self.error_message = None;
@@ -336,8 +863,9 @@ impl App {
             | AppState::GeneratingSubtitles
             | AppState::BurningSubtitles
             | AppState::ExtractingOverlay
-            | AppState::PreviewingOverlay => self.handle_progress_keys(key),
-            AppState::Editing => self.handle_editor_keys(key),
+            | AppState::PreviewingOverlay
+            | AppState::TestBurning => self.handle_progress_keys(key),
+            AppState::Editing => self.handle_editor_keys(event),
             AppState::Done => self.handle_done_keys(key),
         }
         Ok(())
@@ -366,8 +894,8 @@ impl App {
             KeyCode::Down | KeyCode::Char('j') => self.file_browser.down(),
             KeyCode::Enter => {
                 if let Some(path) = self.file_browser.enter() {
-                    self.video_path = Some(path.clone());
-                    self.start_audio_extraction();
+                    self.set_video(Some(path));
+                    self.offer_embedded_subtitles();
                 }
             }
             KeyCode::Char('.') => {
@@ -380,15 +908,83 @@ impl App {
 
     fn handle_progress_keys(&mut self, key: KeyCode) {
         match key {
-            KeyCode::Char('q') | KeyCode::Esc => {
-                // TODO: Cancel current operation
+            KeyCode::Esc => self.cancel_job(),
+            KeyCode::Char('q') => {
+                self.cancel_job();
                 self.should_quit = true;
             }
             _ => {}
         }
     }
 
-    fn handle_editor_keys(&mut self, key: KeyCode) {
+    /// Fresh cancel handle for a background job about to start
+    fn new_job(&mut self) -> CancelHandle {
+        self.job = CancelHandle::new();
+        self.job.clone()
+    }
+
+    /// Kill the running job and go back to where it was started from
+    fn cancel_job(&mut self) {
+        self.job.cancel();
+        // The worker's "cancelled" error isn't worth reporting
+        self.progress_rx = None;
+        self.stage = None;
+        self.transcribing = false;
+        if self.state == AppState::ExtractingAudio {
+            // A partial WAV mustn't be reused as up to date next time
+            if let Some(audio) = self.audio_path.take() {
+                let _ = std::fs::remove_file(audio);
+            }
+        }
+        self.state = match self.state {
+            AppState::ExtractingAudio | AppState::GeneratingSubtitles => {
+                match self.regenerate_backup.take() {
+                    Some(old) => {
+                        self.subtitles = old;
+                        self.selected_index = 0;
+                        AppState::Editing
+                    }
+                    None => AppState::Home,
+                }
+            }
+            AppState::TestBurning => {
+                self.test_burn = None;
+                AppState::Editing
+            }
+            AppState::BurningSubtitles | AppState::ExtractingOverlay => {
+                // FFmpeg was killed mid-write, so the file is unplayable
+                if let Some(output) = self.output_path.take() {
+                    let _ = std::fs::remove_file(output);
+                }
+                AppState::Editing
+            }
+            _ => AppState::Editing,
+        };
+        self.progress_message = "Cancelled".to_string();
+    }
+
+    fn handle_editor_keys(&mut self, event: KeyEvent) {
+        let selected = self.selected_index;
+        let before = self.subtitles.clone();
+        let last_save = self.last_save;
+        self.handle_editor_key(event);
+        if self.selected_index != selected {
+            self.text_scroll = 0;
+        }
+        // Any key that changed the cues counts as an edit, unless it also saved them
+        if self.subtitles != before && self.last_save == last_save {
+            self.dirty = true;
+            self.unsaved_edits += 1;
+        }
+    }
+
+    fn handle_editor_key(&mut self, event: KeyEvent) {
+        let key = event.code;
+        if self.prompt.is_some() {
+            self.handle_prompt_keys(key);
+            return;
+        }
+
         if self.editing_subtitle {
             match key {
                 KeyCode::Esc => {
@@ -396,12 +992,22 @@ impl App {
                     self.edit_buffer.clear();
                 }
                 KeyCode::Enter => {
-                    if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
-                        sub.text = self.edit_buffer.clone();
-                    }
+                    self.commit_edit();
                     self.editing_subtitle = false;
                     self.edit_buffer.clear();
                 }
+                // Inline, the arrows move to the neighbouring cue and keep editing
+                KeyCode::Up | KeyCode::Down if self.inline_edit => {
+                    self.commit_edit();
+                    if key == KeyCode::Up {
+                        self.selected_index = self.selected_index.saturating_sub(1);
+                    } else if self.selected_index + 1 < self.subtitles.len() {
+                        self.selected_index += 1;
+                    }
+                    if let Some(sub) = self.subtitles.get(self.selected_index) {
+                        self.edit_buffer = sub.text.clone();
+                    }
+                }
                 KeyCode::Char(c) => {
                     self.edit_buffer.push(c);
                 }
@@ -410,227 +1016,818 @@ impl App {
                 }
                 _ => {}
             }
-        } else {
-            match key {
-                KeyCode::Char('q') => {
-                    // Stop preview before quitting
-                    if self.preview_active {
-                        self.stop_preview();
-                    }
-                    self.should_quit = true;
-                }
-                KeyCode::Esc => self.state = AppState::Home,
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if self.selected_index > 0 {
-                        self.selected_index -= 1;
-                    }
+        } else if let Some((action, large)) = self.keymap.action_for(event) {
+            self.run_action(action, large);
+        } else if let KeyCode::Char(c @ '1'..='9') = key {
+            // Number keys switch tabs
+            let tab = c as usize - '1' as usize;
+            if tab < self.documents.len() {
+                if self.transcribing {
+                    self.error_message =
+                        Some("Wait for transcription to finish before switching tabs".to_string());
+                } else {
+                    self.switch_document(tab);
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if self.selected_index < self.subtitles.len().saturating_sub(1) {
-                        self.selected_index += 1;
-                    }
+            }
+        }
+    }
+
+    /// Write the edit buffer to the selected cue
+    fn commit_edit(&mut self) {
+        if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
+            if sub.text != self.edit_buffer {
+                sub.text = self.edit_buffer.clone();
+                sub.confidence = None;
+            }
+        }
+    }
+
+    /// Carry out an editor command from the cue list
+    fn run_action(&mut self, action: Action, large: bool) {
+        // How far the nudge and move actions go: further with Shift held
+        let step = self.config.nudge_steps().step(large);
+        match action {
+            Action::Quit => self.leave_editor(LeaveAction::Quit),
+            Action::Back => {
+                // Drop a range selection before leaving the editor
+                if self.selection_anchor.is_some() || self.selection.is_some() {
+                    self.selection_anchor = None;
+                    self.selection = None;
+                    self.progress_message = "Selection cleared".to_string();
+                } else {
+                    self.leave_editor(LeaveAction::Home);
                 }
-                KeyCode::Enter | KeyCode::Char('e') => {
-                    if let Some(sub) = self.subtitles.get(self.selected_index) {
-                        self.edit_buffer = sub.text.clone();
-                        self.editing_subtitle = true;
-                    }
+            }
+            Action::Up => {
+                if self.selected_index > 0 {
+                    self.selected_index -= 1;
                 }
-                KeyCode::Char('a') => {
-                    // Add new subtitle
-                    let new_sub = if let Some(last) = self.subtitles.last() {
-                        Subtitle {
-                            index: self.subtitles.len() + 1,
-                            start_time: last.end_time,
-                            end_time: last.end_time + 2000, // 2 seconds
-                            text: String::from("New subtitle"),
-                        }
-                    } else {
-                        Subtitle {
-                            index: 1,
-                            start_time: 0,
-                            end_time: 2000,
-                            text: String::from("New subtitle"),
-                        }
-                    };
-                    self.subtitles.push(new_sub);
-                    self.selected_index = self.subtitles.len() - 1;
+            }
+            Action::Down => {
+                if self.selected_index < self.subtitles.len().saturating_sub(1) {
+                    self.selected_index += 1;
                 }
-                KeyCode::Char('d') => {
-                    if !self.subtitles.is_empty() {
-                        self.subtitles.remove(self.selected_index);
-                        if self.selected_index >= self.subtitles.len() && self.selected_index > 0 {
-                            self.selected_index -= 1;
-                        }
-                        // Re-index subtitles
-                        for (i, sub) in self.subtitles.iter_mut().enumerate() {
-                            sub.index = i + 1;
-                        }
-                    }
+            }
+            Action::Edit => {
+                if let Some(sub) = self.subtitles.get(self.selected_index) {
+                    self.edit_buffer = sub.text.clone();
+                    self.editing_subtitle = true;
                 }
-                KeyCode::Char('[') => {
-                    // Decrease start time by 100ms
-                    if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
-                        sub.start_time = sub.start_time.saturating_sub(100);
+            }
+            Action::AddCue => {
+                // Add new subtitle
+                let new_sub = if let Some(last) = self.subtitles.last() {
+                    let start_time = last.end_time + self.config.min_gap_ms();
+                    Subtitle {
+                        index: self.subtitles.len() + 1,
+                        start_time,
+                        end_time: start_time + 2000, // 2 seconds
+                        text: String::from("New subtitle"),
+                        position: None,
+                        confidence: None,
+                        locked: false,
                     }
-                }
-                KeyCode::Char(']') => {
-                    // Increase start time by 100ms
-                    if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
-                        sub.start_time += 100;
-                        if sub.start_time >= sub.end_time {
-                            sub.start_time = sub.end_time - 100;
-                        }
+                } else {
+                    Subtitle {
+                        index: 1,
+                        start_time: 0,
+                        end_time: 2000,
+                        text: String::from("New subtitle"),
+                        position: None,
+                        confidence: None,
+                        locked: false,
                     }
-                }
-                KeyCode::Char('{') => {
-                    // Decrease end time by 100ms
-                    if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
-                        if sub.end_time > sub.start_time + 100 {
-                            sub.end_time -= 100;
+                };
+                self.subtitles.push(new_sub);
+                self.selected_index = self.subtitles.len() - 1;
+            }
+            Action::Delete => {
+                if !self.subtitles.is_empty() {
+                    let removed = self.subtitles.remove(self.selected_index);
+                    // Ripple delete: the next cue takes the removed one's start
+                    if self.ripple {
+                        if let Some(next) = self.subtitles.get(self.selected_index) {
+                            let delta = removed.start_time as i64 - next.start_time as i64;
+                            timing::ripple_from(
+                                &mut self.subtitles,
+                                self.selected_index,
+                                delta,
+                                self.config.min_gap_ms(),
+                            );
                         }
                     }
-                }
-                KeyCode::Char('}') => {
-                    // Increase end time by 100ms
-                    if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
-                        sub.end_time += 100;
+                    if self.selected_index >= self.subtitles.len() && self.selected_index > 0 {
+                        self.selected_index -= 1;
                     }
+                    fix::reindex(&mut self.subtitles);
                 }
-                KeyCode::Char('s') => {
-                    // Save SRT file
-                    self.save_subtitles();
+            }
+            Action::StartEarlier => {
+                // Decrease start time by a step, stopping short of the previous cue
+                let floor = match self.selected_index.checked_sub(1) {
+                    Some(prev) => self.subtitles[prev].end_time + self.config.min_gap_ms(),
+                    None => 0,
+                };
+                if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
+                    sub.start_time = sub
+                        .start_time
+                        .saturating_sub(step)
+                        .max(floor.min(sub.start_time));
                 }
-                KeyCode::Char('b') => {
-                    // Burn subtitles
-                    self.save_subtitles();
-                    self.start_burning();
+            }
+            Action::StartLater => {
+                // Increase start time by a step, keeping the cue at least 100ms long
+                if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
+                    sub.start_time += step;
+                    if sub.start_time >= sub.end_time {
+                        sub.start_time = sub.end_time - 100;
+                    }
                 }
-                KeyCode::Char('o') => {
-                    // Extract overlay only
-                    self.save_subtitles();
-                    self.start_overlay_extraction();
+            }
+            Action::EndEarlier => {
+                // Decrease end time by a step, keeping the cue at least 100ms long
+                if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
+                    let old_end = sub.end_time;
+                    if sub.end_time > sub.start_time + 100 {
+                        sub.end_time = sub.end_time.saturating_sub(step).max(sub.start_time + 100);
+                    }
+                    self.ripple_end_change(old_end);
                 }
-                KeyCode::Char('p') => {
-                    // Toggle preview overlay position
-                    self.save_subtitles();
-                    self.toggle_preview();
+            }
+            Action::EndLater => {
+                // Increase end time by a step, stopping short of the next cue
+                // unless ripple moves it along
+                let ceiling = self
+                    .subtitles
+                    .get(self.selected_index + 1)
+                    .filter(|next| !self.ripple || next.locked)
+                    .map(|next| next.start_time.saturating_sub(self.config.min_gap_ms()));
+                if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
+                    let old_end = sub.end_time;
+                    let end_time = sub.end_time + step;
+                    sub.end_time = match ceiling {
+                        Some(ceiling) => end_time.min(ceiling.max(sub.end_time)),
+                        None => end_time,
+                    };
+                    self.ripple_end_change(old_end);
                 }
-                KeyCode::Char('h') => {
-                    // Decrease overlay height
-                    self.overlay_height = self.overlay_height.saturating_sub(10);
-                    if self.preview_active {
-                        self.progress_message =
-                            format!("Updating preview... Height: {}px", self.overlay_height);
-                        self.update_preview_overlay();
+            }
+            Action::MoveEarlier | Action::MoveLater => {
+                // Shift the whole cue by a step, keeping its duration
+                let delta = if action == Action::MoveLater {
+                    step as i64
+                } else {
+                    -(step as i64)
+                };
+                let moved = timing::move_cue(
+                    &mut self.subtitles,
+                    self.selected_index,
+                    delta,
+                    self.config.min_gap_ms(),
+                );
+                if moved == 0 && self.selected_index < self.subtitles.len() {
+                    self.progress_message = if delta > 0 {
+                        "The cue can't move later without overlapping the next one"
                     } else {
-                        self.progress_message =
-                            format!("Overlay height: {}px", self.overlay_height);
+                        "The cue can't move earlier"
                     }
+                    .to_string();
                 }
-                KeyCode::Char('H') => {
-                    // Increase overlay height
-                    self.overlay_height = self.overlay_height.saturating_add(10);
-                    if self.preview_active {
-                        self.progress_message =
-                            format!("Updating preview... Height: {}px", self.overlay_height);
-                        self.update_preview_overlay();
-                    } else {
-                        self.progress_message =
-                            format!("Overlay height: {}px", self.overlay_height);
-                    }
+            }
+            Action::Ripple => {
+                self.ripple = !self.ripple;
+                self.progress_message = if self.ripple {
+                    "Ripple on: changing a cue's end moves the cues after it (locked cues stay)"
+                        .to_string()
+                } else {
+                    "Ripple off".to_string()
+                };
+            }
+            Action::Lock => {
+                // Lock the cue so ripple edits leave it (and the cues after it) in place
+                if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
+                    sub.locked = !sub.locked;
+                    self.progress_message = format!(
+                        "Cue {} {}",
+                        sub.index,
+                        if sub.locked { "locked" } else { "unlocked" }
+                    );
                 }
-                KeyCode::Char('w') => {
-                    // Decrease overlay width
-                    let current = self.overlay_width.unwrap_or(1920);
-                    self.overlay_width = Some(current.saturating_sub(10));
-                    self.progress_message =
-                        format!("Overlay width: {}px", self.overlay_width.unwrap());
-                    if self.preview_active {
-                        self.update_preview_overlay();
-                    }
+            }
+            Action::Save => {
+                // Save SRT file
+                self.save_subtitles();
+            }
+            Action::SetTimes => {
+                // Type exact timecodes for the selected cue
+                if let Some(sub) = self.subtitles.get(self.selected_index) {
+                    self.prompt_buffer = format!(
+                        "{} --> {}",
+                        Subtitle::format_time(sub.start_time),
+                        Subtitle::format_time(sub.end_time)
+                    );
+                    self.prompt = Some(Prompt::CueTiming { error: None });
                 }
-                KeyCode::Char('W') => {
-                    // Increase overlay width (or set to None for auto)
-                    if let Some(current) = self.overlay_width {
-                        self.overlay_width = Some(current.saturating_add(10));
-                        self.progress_message = format!("Overlay width: {}px", current + 10);
-                    } else {
-                        self.overlay_width = Some(1920);
-                        self.progress_message = "Overlay width: 1920px".to_string();
-                    }
-                    if self.preview_active {
-                        self.update_preview_overlay();
-                    }
+            }
+            Action::GoToTime => {
+                // Jump to the cue at a typed timecode
+                self.prompt_buffer.clear();
+                self.prompt = Some(Prompt::GoToTime { error: None });
+            }
+            Action::Confidence => {
+                self.show_confidence = !self.show_confidence;
+                self.progress_message = if self.show_confidence {
+                    "Highlighting low-confidence words (transcribed cues only)".to_string()
+                } else {
+                    "Confidence highlighting off".to_string()
+                };
+            }
+            Action::Case => {
+                // Cycle lowercase -> Sentence case -> UPPERCASE
+                if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
+                    sub.text = fix::cycle_case(&sub.text);
                 }
-                KeyCode::Char('x') => {
-                    // Decrease X offset (move left)
-                    self.overlay_x_offset = self.overlay_x_offset.saturating_sub(10);
-                    self.progress_message =
-                        format!("Overlay X offset: {}px", self.overlay_x_offset);
-                    if self.preview_active {
-                        self.update_preview_overlay();
+            }
+            Action::SelectRange => {
+                // Start a range selection, finish it, or clear the finished one
+                if let Some(anchor) = self.selection_anchor.take() {
+                    self.selection = Some((anchor, self.selected_index));
+                    if let Some((first, last)) = self.selected_range() {
+                        self.progress_message = format!(
+                            "Selected cues {}-{} - b burns only this range, V clears",
+                            first + 1,
+                            last + 1
+                        );
                     }
+                } else if self.selection.take().is_some() {
+                    self.progress_message = "Selection cleared".to_string();
+                } else if !self.subtitles.is_empty() {
+                    self.selection_anchor = Some(self.selected_index);
+                    self.progress_message = format!(
+                        "Selecting from cue {} - move and press V again",
+                        self.selected_index + 1
+                    );
                 }
-                KeyCode::Char('X') => {
-                    // Increase X offset (move right)
-                    self.overlay_x_offset = self.overlay_x_offset.saturating_add(10);
-                    self.progress_message =
-                        format!("Overlay X offset: {}px", self.overlay_x_offset);
-                    if self.preview_active {
-                        self.update_preview_overlay();
-                    }
+            }
+            Action::FixSpacing => {
+                // Tidy spacing in every cue, French-style for French subtitles
+                let french = self.whisper_language == "fr";
+                let changed = fix::normalize_whitespace_all(&mut self.subtitles, french);
+                self.progress_message = format!("Fixed spacing in {} cues", changed);
+            }
+            Action::TogglePosition => {
+                // Move the selected cue to the top (e.g. off on-screen text) or back
+                if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
+                    sub.position = match sub.position {
+                        Some(SubtitlePosition::Top) => None,
+                        _ => Some(SubtitlePosition::Top),
+                    };
+                    self.progress_message = format!(
+                        "Cue {} position: {}",
+                        sub.index,
+                        sub.position.unwrap_or(SubtitlePosition::Bottom).as_str()
+                    );
                 }
-                KeyCode::Char('y') => {
-                    // Decrease Y offset (move up)
-                    self.overlay_y_offset = self.overlay_y_offset.saturating_sub(10);
-                    self.progress_message =
-                        format!("Overlay Y offset: {}px", self.overlay_y_offset);
-                    if self.preview_active {
-                        self.update_preview_overlay();
+            }
+            Action::ScrollDown => {
+                // Scroll long cue text in the edit panel
+                self.text_scroll = (self.text_scroll + 1).min(self.text_overflow.get());
+            }
+            Action::ScrollUp => {
+                self.text_scroll = self.text_scroll.saturating_sub(1);
+            }
+            Action::NextIssue => self.jump_to_issue(true),
+            Action::PrevIssue => self.jump_to_issue(false),
+            Action::MarkStart => {
+                // Set cue start from the preview's playback position
+                self.mark_from_preview(true);
+            }
+            Action::PlayPause => {
+                self.audition_end_ms = None;
+                self.control_preview(|mpv| mpv.toggle_pause());
+            }
+            Action::SeekToCue => {
+                // Play from the selected cue's start
+                self.audition_end_ms = None;
+                if let Some(start) = self
+                    .subtitles
+                    .get(self.selected_index)
+                    .map(|s| s.start_time)
+                {
+                    if self.control_preview(|mpv| mpv.seek_ms(start)) {
+                        self.progress_message =
+                            format!("Playing from {}", Subtitle::format_time(start));
                     }
                 }
-                KeyCode::Char('Y') => {
-                    // Increase Y offset (move down)
-                    self.overlay_y_offset = self.overlay_y_offset.saturating_add(10);
-                    self.progress_message =
-                        format!("Overlay Y offset: {}px", self.overlay_y_offset);
-                    if self.preview_active {
-                        self.update_preview_overlay();
-                    }
+            }
+            Action::MarkEnd => {
+                // Set cue end from the preview's playback position
+                self.mark_from_preview(false);
+            }
+            Action::PlayCue => self.play_cue(),
+            Action::Burn | Action::ExportOverlay | Action::Preview | Action::TestBurn
+                if self.transcribing =>
+            {
+                self.error_message = Some("Wait for transcription to finish first".to_string());
+            }
+            Action::Burn => {
+                // Burn subtitles
+                self.save_subtitles();
+                self.start_burning();
+            }
+            Action::TestBurn => {
+                // Burn a short clip around the selected cue
+                self.save_subtitles();
+                self.start_test_burn();
+            }
+            Action::ExportOverlay => {
+                // Extract overlay only
+                self.save_subtitles();
+                self.start_overlay_extraction();
+            }
+            Action::Preview => {
+                // Toggle preview overlay position
+                self.save_subtitles();
+                self.toggle_preview();
+            }
+            Action::OverlayShorter => self.adjust_overlay('h'),
+            Action::OverlayTaller => self.adjust_overlay('H'),
+            Action::OverlayNarrower => self.adjust_overlay('w'),
+            Action::OverlayWider => self.adjust_overlay('W'),
+            Action::OverlayLeft => self.adjust_overlay('x'),
+            Action::OverlayRight => self.adjust_overlay('X'),
+            Action::OverlayUp => self.adjust_overlay('y'),
+            Action::OverlayDown => self.adjust_overlay('Y'),
+            Action::Regenerate => {
+                // Regenerate subtitles from the already-extracted audio
+                if self.transcribing {
+                    self.error_message = Some("Transcription is already running".to_string());
+                } else if self.audio_path.as_ref().is_some_and(|p| p.exists()) {
+                    self.prompt_buffer =
+                        format!("{} {}", self.whisper_model.as_str(), self.whisper_language);
+                    self.prompt = Some(Prompt::RegenerateSettings);
+                } else {
+                    self.error_message =
+                        Some("No extracted audio available to regenerate from".to_string());
                 }
-                KeyCode::Char('0') => {
-                    // Reset overlay settings to defaults
-                    self.overlay_height = 200;
-                    self.overlay_width = None;
-                    self.overlay_x_offset = 0;
-                    self.overlay_y_offset = 0;
-                    self.progress_message = "Overlay settings reset to defaults".to_string();
-                    if self.preview_active {
-                        self.update_preview_overlay();
-                    }
+            }
+            Action::Resegment => {
+                // Re-cut the selected range (or every cue) at pauses in the audio
+                if self.transcribing {
+                    self.error_message = Some("Wait for transcription to finish first".to_string());
+                } else if !self.audio_path.as_ref().is_some_and(|p| p.exists()) {
+                    self.error_message = Some(
+                        "Re-segmenting needs the audio: open the video, or pass --audio"
+                            .to_string(),
+                    );
+                } else if let Some(last) = self.subtitles.len().checked_sub(1) {
+                    let (first, last) = self.selected_range().unwrap_or((0, last));
+                    self.prompt = Some(Prompt::ConfirmResegment { first, last });
                 }
-                _ => {}
             }
-        }
-    }
-
-    fn handle_done_keys(&mut self, key: KeyCode) {
-        match key {
-            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => {
-                self.should_quit = true;
+            Action::OpenTab => {
+                // Open another subtitle file in a new tab
+                self.prompt_buffer = self
+                    .srt_path
+                    .as_ref()
+                    .and_then(|p| p.parent())
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .map(|dir| format!("{}/", dir.display()))
+                    .unwrap_or_default();
+                self.prompt = Some(Prompt::OpenDocument);
             }
-            KeyCode::Char('r') => {
-                // Reset and start over
-                *self = App::new();
+            Action::InlineEdit => {
+                // Switch between editing in the list rows and in the side panel
+                self.inline_edit = !self.inline_edit;
+                self.progress_message = if self.inline_edit {
+                    format!(
+                        "Inline editing - {} edits in the row, ↑/↓ move while editing",
+                        self.keymap.label(Action::Edit)
+                    )
+                } else {
+                    "Panel editing".to_string()
+                };
+            }
+            Action::Replace => {
+                // Replace text across every cue
+                self.prompt_buffer.clear();
+                self.prompt = Some(Prompt::FindText {
+                    mode: FindMode::IgnoreCase,
+                    error: None,
+                });
+            }
+            Action::Undo => match self.undo.take() {
+                Some((what, before, after)) if after == self.subtitles => {
+                    self.subtitles = before;
+                    self.selected_index = self
+                        .selected_index
+                        .min(self.subtitles.len().saturating_sub(1));
+                    self.progress_message = format!("Undid the last {}", what);
+                }
+                Some((what, _, _)) => {
+                    self.error_message = Some(format!(
+                        "Cues changed since the last {}; it can't be undone",
+                        what
+                    ));
+                }
+                None => self.progress_message = "Nothing to undo".to_string(),
+            },
+            Action::ResetOverlay => {
+                // Reset overlay settings to defaults
+                self.overlay_height = 200;
+                self.overlay_width = None;
+                self.overlay_x_offset = 0;
+                self.overlay_y_offset = 0;
+                self.clamp_overlay();
+                self.progress_message = "Overlay settings reset to defaults".to_string();
+                if self.preview_active {
+                    self.update_preview_overlay();
+                }
             }
-            _ => {}
         }
     }
 
-    fn draw(&self, frame: &mut Frame) {
-        match &self.state {
-            AppState::Home => ui::home::draw(frame, self),
+    fn handle_prompt_keys(&mut self, key: KeyCode) {
+        let Some(prompt) = self.prompt.clone() else {
+            return;
+        };
+
+        match prompt {
+            Prompt::RegenerateSettings => match key {
+                KeyCode::Esc => {
+                    self.prompt = None;
+                    self.prompt_buffer.clear();
+                }
+                KeyCode::Enter => {
+                    let mut parts = self.prompt_buffer.split_whitespace();
+                    let model = match parts.next() {
+                        Some(name) => match WhisperModel::from_str(name, true) {
+                            Ok(model) => model,
+                            Err(_) => {
+                                self.error_message = Some(format!(
+                                    "Unknown model '{}' (tiny, base, small, medium, large)",
+                                    name
+                                ));
+                                return;
+                            }
+                        },
+                        None => self.whisper_model,
+                    };
+                    let language = parts
+                        .next()
+                        .map(|l| l.to_string())
+                        .unwrap_or_else(|| self.whisper_language.clone());
+                    self.prompt_buffer.clear();
+                    self.prompt = Some(Prompt::ConfirmRegenerate { model, language });
+                }
+                KeyCode::Char(c) => self.prompt_buffer.push(c),
+                KeyCode::Backspace => {
+                    self.prompt_buffer.pop();
+                }
+                _ => {}
+            },
+            Prompt::ConfirmRegenerate { model, language } => match key {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.prompt = None;
+                    if self.preview_active {
+                        self.stop_preview();
+                    }
+                    self.whisper_model = model;
+                    self.whisper_language = language;
+                    self.regenerate_backup = Some(self.subtitles.clone());
+                    self.state = AppState::GeneratingSubtitles;
+                    self.stage = Some(StageTimer::new(1, 1));
+                    self.start_subtitle_generation();
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.prompt = None;
+                    self.progress_message = "Regeneration cancelled".to_string();
+                }
+                _ => {}
+            },
+            Prompt::SubtitlesPastEnd { media_end_ms, .. } => match key {
+                KeyCode::Char('t') | KeyCode::Char('y') | KeyCode::Enter => {
+                    self.prompt = None;
+                    timing::clamp_to_media_end(&mut self.subtitles, media_end_ms);
+                    self.selected_index = self
+                        .selected_index
+                        .min(self.subtitles.len().saturating_sub(1));
+                    // Burn the file as trimmed; nothing is left past the end now
+                    self.save_subtitles();
+                    self.start_burning();
+                }
+                KeyCode::Char('b') => {
+                    self.prompt = None;
+                    self.choose_burn_output();
+                }
+                KeyCode::Char('c') | KeyCode::Char('n') | KeyCode::Esc => {
+                    self.prompt = None;
+                    self.progress_message = "Burn cancelled".to_string();
+                }
+                _ => {}
+            },
+            Prompt::OverwriteOutput { path } => match key {
+                KeyCode::Char('o') | KeyCode::Char('y') => {
+                    self.prompt = None;
+                    self.burn_to(path);
+                }
+                KeyCode::Char('r') => {
+                    self.prompt = None;
+                    self.burn_to(unique_path(&path));
+                }
+                KeyCode::Char('c') | KeyCode::Char('n') | KeyCode::Esc => {
+                    self.prompt = None;
+                    self.progress_message = "Burn cancelled".to_string();
+                }
+                _ => {}
+            },
+            Prompt::OpenDocument => match key {
+                KeyCode::Esc => {
+                    self.prompt = None;
+                    self.prompt_buffer.clear();
+                }
+                KeyCode::Enter => {
+                    self.prompt = None;
+                    let path = PathBuf::from(std::mem::take(&mut self.prompt_buffer).trim());
+                    if let Err(e) = self.open_document(&path) {
+                        self.error_message = Some(format!("Failed to open: {}", e));
+                    }
+                }
+                KeyCode::Char(c) => self.prompt_buffer.push(c),
+                KeyCode::Backspace => {
+                    self.prompt_buffer.pop();
+                }
+                _ => {}
+            },
+            Prompt::FindText { mode, .. } => match key {
+                KeyCode::Esc => {
+                    self.prompt = None;
+                    self.prompt_buffer.clear();
+                }
+                KeyCode::Tab => {
+                    self.prompt = Some(Prompt::FindText {
+                        mode: mode.next(),
+                        error: None,
+                    });
+                }
+                KeyCode::Enter => match mode.replacement(&self.prompt_buffer, "") {
+                    Ok(_) => {
+                        let find = std::mem::take(&mut self.prompt_buffer);
+                        self.prompt = Some(Prompt::ReplaceWith { find, mode });
+                    }
+                    Err(e) => {
+                        self.prompt = Some(Prompt::FindText {
+                            mode,
+                            error: Some(format!("{:#}", e)),
+                        });
+                    }
+                },
+                KeyCode::Char(c) => self.prompt_buffer.push(c),
+                KeyCode::Backspace => {
+                    self.prompt_buffer.pop();
+                }
+                _ => {}
+            },
+            Prompt::ReplaceWith { find, mode } => match key {
+                KeyCode::Esc => {
+                    self.prompt = None;
+                    self.prompt_buffer.clear();
+                }
+                KeyCode::Enter => {
+                    let to = std::mem::take(&mut self.prompt_buffer);
+                    self.prompt = None;
+                    // Validated when the text to find was entered
+                    let Ok(replacement) = mode.replacement(&find, &to) else {
+                        return;
+                    };
+                    let (occurrences, cues) = replacement.count(&self.subtitles);
+                    if occurrences == 0 {
+                        self.progress_message = format!("No matches for \"{}\"", find);
+                    } else {
+                        self.prompt = Some(Prompt::ConfirmReplace {
+                            find,
+                            to,
+                            mode,
+                            occurrences,
+                            cues,
+                        });
+                    }
+                }
+                KeyCode::Char(c) => self.prompt_buffer.push(c),
+                KeyCode::Backspace => {
+                    self.prompt_buffer.pop();
+                }
+                _ => {}
+            },
+            Prompt::ConfirmResegment { first, last } => match key {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.prompt = None;
+                    self.resegment(first, last);
+                }
+                KeyCode::Char('n') | KeyCode::Esc => self.prompt = None,
+                _ => {}
+            },
+            Prompt::ConfirmReplace { find, to, mode, .. } => match key {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.prompt = None;
+                    if let Ok(replacement) = mode.replacement(&find, &to) {
+                        let before = self.subtitles.clone();
+                        let (occurrences, cues) = replacement.count(&self.subtitles);
+                        replacement.apply(&mut self.subtitles);
+                        self.undo = Some(("replace", before, self.subtitles.clone()));
+                        self.progress_message = format!(
+                            "Replaced {} occurrences in {} cues - {} undoes",
+                            occurrences,
+                            cues,
+                            self.keymap.label(Action::Undo)
+                        );
+                    }
+                }
+                KeyCode::Char('n') | KeyCode::Esc => self.prompt = None,
+                _ => {}
+            },
+            Prompt::CueTiming { .. } => match key {
+                KeyCode::Esc => {
+                    self.prompt = None;
+                    self.prompt_buffer.clear();
+                }
+                KeyCode::Enter => match parse_cue_timing(&self.prompt_buffer) {
+                    Ok((start_time, end_time)) => {
+                        if let Some(sub) = self.subtitles.get_mut(self.selected_index) {
+                            let old_end = sub.end_time;
+                            sub.start_time = start_time;
+                            sub.end_time = end_time;
+                            self.ripple_end_change(old_end);
+                        }
+                        self.prompt = None;
+                        self.prompt_buffer.clear();
+                    }
+                    Err(e) => {
+                        self.prompt = Some(Prompt::CueTiming {
+                            error: Some(e.to_string()),
+                        });
+                    }
+                },
+                KeyCode::Char(c) => self.prompt_buffer.push(c),
+                KeyCode::Backspace => {
+                    self.prompt_buffer.pop();
+                }
+                _ => {}
+            },
+            Prompt::GoToTime { .. } => match key {
+                KeyCode::Esc => {
+                    self.prompt = None;
+                    self.prompt_buffer.clear();
+                }
+                KeyCode::Enter => match Subtitle::parse_time(self.prompt_buffer.trim()) {
+                    Ok(time) => {
+                        if let Some((index, covered)) = timing::cue_at(&self.subtitles, time) {
+                            self.selected_index = index;
+                            if !covered {
+                                self.progress_message = format!(
+                                    "No cue at that time - went to the nearest, cue {}",
+                                    index + 1
+                                );
+                            }
+                        }
+                        self.prompt = None;
+                        self.prompt_buffer.clear();
+                    }
+                    Err(e) => {
+                        self.prompt = Some(Prompt::GoToTime {
+                            error: Some(e.to_string()),
+                        });
+                    }
+                },
+                KeyCode::Char(c) => self.prompt_buffer.push(c),
+                KeyCode::Backspace => {
+                    self.prompt_buffer.pop();
+                }
+                _ => {}
+            },
+            Prompt::EmbeddedSubtitles { tracks } => match key {
+                KeyCode::Char(c) if c.is_ascii_digit() => {
+                    let index = c.to_digit(10).unwrap() as usize;
+                    match tracks.get(index) {
+                        Some(track) if !track.is_text() => {
+                            self.error_message = Some(format!(
+                                "Track {} is image-based and can't be converted to SRT without OCR",
+                                track.label()
+                            ));
+                        }
+                        Some(_) => match self.load_embedded_track(index) {
+                            Ok(()) => self.prompt = None,
+                            Err(e) => self.error_message = Some(e.to_string()),
+                        },
+                        None => {}
+                    }
+                }
+                KeyCode::Char('w') | KeyCode::Enter => {
+                    self.prompt = None;
+                    self.start_audio_extraction();
+                }
+                KeyCode::Esc => {
+                    self.prompt = None;
+                    self.set_video(None);
+                    self.state = AppState::Home;
+                }
+                _ => {}
+            },
+            Prompt::ReviewRegenerate { .. } => match key {
+                KeyCode::Char('k') | KeyCode::Enter => {
+                    self.prompt = None;
+                    self.regenerate_backup = None;
+                }
+                KeyCode::Char('r') | KeyCode::Esc => {
+                    self.prompt = None;
+                    if let Some(old) = self.regenerate_backup.take() {
+                        self.subtitles = old;
+                        self.selected_index = 0;
+                        // The generator overwrote the SRT; put the edited version back
+                        self.save_subtitles();
+                        self.progress_message = "Restored the previous subtitles".to_string();
+                    }
+                }
+                _ => {}
+            },
+            Prompt::RestoreAutosave { path } => match key {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.prompt = None;
+                    match crate::subtitle::srt::parse_srt(&path) {
+                        Ok(subtitles) => {
+                            self.subtitles = subtitles;
+                            self.selected_index = 0;
+                            self.dirty = true;
+                            self.progress_message =
+                                "Restored unsaved edits - press s to save them".to_string();
+                        }
+                        Err(e) => {
+                            self.error_message = Some(format!("Failed to restore autosave: {}", e));
+                        }
+                    }
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.prompt = None;
+                    self.discard_autosave();
+                }
+                _ => {}
+            },
+            Prompt::UnsavedChanges { then } => match key {
+                KeyCode::Char('s') | KeyCode::Char('y') | KeyCode::Enter => {
+                    self.prompt = None;
+                    self.save_subtitles();
+                    // Stay put if saving failed so the edits aren't lost
+                    if !self.dirty {
+                        self.leave_editor(then);
+                    }
+                }
+                KeyCode::Char('d') => {
+                    self.prompt = None;
+                    self.dirty = false;
+                    self.unsaved_edits = 0;
+                    self.discard_autosave();
+                    self.leave_editor(then);
+                }
+                KeyCode::Char('c') | KeyCode::Char('n') | KeyCode::Esc => {
+                    self.prompt = None;
+                }
+                _ => {}
+            },
+            Prompt::NoAudioStream => match key {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.prompt = None;
+                    self.progress_message =
+                        "No audio - press a to add subtitles manually".to_string();
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.prompt = None;
+                    self.srt_path = None;
+                    self.state = AppState::Home;
+                }
+                _ => {}
+            },
+        }
+    }
+
+    fn handle_done_keys(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => {
+                self.should_quit = true;
+            }
+            KeyCode::Char('r') => {
+                // Start over, but keep the settings loaded at startup
+                let config = std::mem::take(&mut self.config);
+                *self = App::new()
+                    .with_config(config)
+                    .with_overwrite_policy(self.overwrite);
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        match &self.state {
+            AppState::Home => ui::home::draw(frame, self),
             AppState::SelectingFile => ui::file_picker::draw(frame, self),
             AppState::ExtractingAudio => ui::progress::draw(frame, self, "Extracting Audio"),
             AppState::GeneratingSubtitles => {
@@ -640,28 +1837,117 @@ impl App {
             AppState::BurningSubtitles => ui::progress::draw(frame, self, "Burning Subtitles"),
             AppState::ExtractingOverlay => ui::progress::draw(frame, self, "Extracting Overlay"),
             AppState::PreviewingOverlay => ui::progress::draw(frame, self, "Preview"),
+            AppState::TestBurning => ui::progress::draw(frame, self, "Test Burn"),
             AppState::Done => ui::done::draw(frame, self),
         }
     }
 
+    /// Ask whether to edit a subtitle track already in the video before
+    /// transcribing it; goes straight to transcription when there is none
+    fn offer_embedded_subtitles(&mut self) {
+        let video_path = self.video_path.clone().unwrap();
+        match embedded::probe_tracks(&video_path) {
+            Ok(tracks) if !tracks.is_empty() => {
+                self.subtitles.clear();
+                self.selected_index = 0;
+                self.state = AppState::Editing;
+                self.prompt = Some(Prompt::EmbeddedSubtitles { tracks });
+            }
+            _ => self.start_audio_extraction(),
+        }
+    }
+
+    /// Convert embedded subtitle track `index` to `<video>.srt` and edit it
+    fn load_embedded_track(&mut self, index: usize) -> Result<()> {
+        let video_path = self.video_path.clone().unwrap();
+        let srt_path = video_path.with_extension("srt");
+        embedded::extract_track(&video_path, index, &srt_path)?;
+        self.load_srt_file(&srt_path)?;
+        self.progress_message = format!("Loaded embedded subtitle track {}", index);
+        Ok(())
+    }
+
     fn start_audio_extraction(&mut self) {
+        let video_path = self.video_path.clone().unwrap();
+
+        // A silent clip can't be transcribed, but can still be captioned by hand
+        match AudioExtractor::new().has_audio_stream(&video_path) {
+            Ok(false) => {
+                self.subtitles.clear();
+                self.selected_index = 0;
+                self.srt_path = Some(video_path.with_extension("srt"));
+                self.state = AppState::Editing;
+                self.prompt = Some(Prompt::NoAudioStream);
+                return;
+            }
+            Ok(true) => {}
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+                return;
+            }
+        }
+
+        // The audio is only needed while editing, so it goes to a temp
+        // workspace unless intermediate files or the audio should be kept
+        let keep_audio = self.config.behavior.keep_files || self.config.behavior.keep_audio;
+        let audio_path = if keep_audio {
+            video_path.with_extension("wav")
+        } else {
+            match TempWorkspace::new(self.config.paths.temp_dir.as_deref()) {
+                Ok(workspace) => {
+                    let path = workspace.file("audio.wav");
+                    self.workspace = Some(workspace);
+                    path
+                }
+                Err(e) => {
+                    self.error_message = Some(e.to_string());
+                    return;
+                }
+            }
+        };
+        self.audio_path = Some(audio_path.clone());
+        // The SRT is the user's work, so it stays next to the video
+        self.srt_path = Some(video_path.with_extension("srt"));
+
+        // Audio kept from an earlier run is reused while the video is unchanged,
+        // so comparing models doesn't re-extract every time
+        if keep_audio && AudioExtractor::new().is_up_to_date(&video_path, &audio_path) {
+            self.state = AppState::GeneratingSubtitles;
+            self.stage = Some(StageTimer::new(2, 3));
+            self.start_subtitle_generation();
+            self.progress_message = format!("Reusing audio {}", audio_path.display());
+            return;
+        }
+
         self.state = AppState::ExtractingAudio;
         self.progress = 0.0;
         self.progress_message = "Starting audio extraction...".to_string();
+        self.stage = Some(StageTimer::new(1, 3));
 
         let (tx, rx) = mpsc::channel();
         self.progress_rx = Some(rx);
 
-        let video_path = self.video_path.clone().unwrap();
-        let audio_path = video_path.with_extension("wav");
-        self.audio_path = Some(audio_path.clone());
-
+        let cancel = self.new_job();
         std::thread::spawn(move || {
-            let extractor = AudioExtractor::new();
+            let extractor = AudioExtractor::new().with_cancel(cancel);
             if let Err(e) = extractor.extract(&video_path, &audio_path, tx.clone()) {
                 let _ = tx.send(ProgressMessage::Error(e.to_string()));
             }
         });
+
+        // Fetch the model while the audio extracts; the two don't depend on
+        // each other and a first-run download can take minutes
+        let mut generator = SubtitleGenerator::new()
+            .with_model(self.whisper_model)
+            .with_language(&self.whisper_language);
+        if let Some(source) = &self.config.whisper.model_base_url {
+            generator = generator.with_model_source(source);
+        }
+        self.model_download = Some(std::thread::spawn(move || {
+            // Download progress isn't shown over the extraction progress
+            let (tx, _rx) = mpsc::channel();
+            generator.ensure_model(&tx)
+        }));
     }
 
     fn start_subtitle_generation(&mut self) {
@@ -670,16 +1956,63 @@ impl App {
 
         let (tx, rx) = mpsc::channel();
         self.progress_rx = Some(rx);
+        self.subtitles.clear();
+        self.stream_draft.clear();
+        self.selected_index = 0;
+        self.transcribing = true;
+        self.detected_language = None;
 
         let audio_path = self.audio_path.clone().unwrap();
-        let srt_path = audio_path.with_extension("srt");
+        let srt_path = self
+            .srt_path
+            .clone()
+            .unwrap_or_else(|| audio_path.with_extension("srt"));
         self.srt_path = Some(srt_path.clone());
 
+        let model = self.whisper_model;
+        let language = self.whisper_language.clone();
+        let model_source = self.config.whisper.model_base_url.clone();
+        let min_gap_ms = self.config.min_gap_ms();
+        let min_duration_ms = self.config.subtitles.min_duration_ms;
+        let max_duration_ms = self.config.subtitles.max_duration_ms;
+        let write_options = self.config.srt_write_options();
+        let model_download = self.model_download.take();
+        let cancel = self.new_job();
+
         std::thread::spawn(move || {
-            let generator = SubtitleGenerator::new();
+            if let Some(download) = model_download {
+                if !download.is_finished() {
+                    let _ = tx.send(ProgressMessage::Progress(
+                        0.05,
+                        "Waiting for model download...".to_string(),
+                    ));
+                }
+                let result = download
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("download thread panicked")));
+                if let Err(e) = result {
+                    let _ = tx.send(ProgressMessage::Error(format!(
+                        "Model download failed: {}",
+                        e
+                    )));
+                    return;
+                }
+            }
+
+            let mut generator = SubtitleGenerator::new()
+                .with_model(model)
+                .with_language(&language);
+            if let Some(source) = &model_source {
+                generator = generator.with_model_source(source);
+            }
+            generator = generator
+                .with_min_gap(min_gap_ms)
+                .with_duration_limits(min_duration_ms, max_duration_ms)
+                .with_write_options(write_options)
+                .with_cancel(cancel);
             match generator.generate(&audio_path, &srt_path, tx.clone()) {
                 Ok(_) => {
-                    // Subtitles are saved to file, will be loaded when Complete is received
+                    // Subtitles were streamed as segments and saved to file
                 }
                 Err(e) => {
                     let _ = tx.send(ProgressMessage::Error(e.to_string()));
@@ -688,14 +2021,239 @@ impl App {
         });
     }
 
+    /// The editor's burn settings as a style for exported subtitles
+    fn export_style(&self) -> AssStyle {
+        SubtitleBurner::new()
+            .with_overlay(self.config.use_overlay())
+            .with_overlay_height(self.overlay_height)
+            .ass_style(
+                self.video_size
+                    .map_or(DEFAULT_FRAME_HEIGHT, |(_, height)| height),
+            )
+    }
+
     fn save_subtitles(&mut self) {
         if let Some(srt_path) = &self.srt_path {
-            if let Err(e) = crate::subtitle::srt::save_srt(srt_path, &self.subtitles) {
+            let options = self.config.srt_write_options();
+            if let Err(e) = format::save_subtitles(
+                srt_path,
+                &self.subtitles,
+                self.subtitle_format,
+                &options,
+                &self.export_style(),
+            ) {
                 self.error_message = Some(format!("Failed to save SRT: {}", e));
             } else {
                 self.progress_message = format!("Saved to {}", srt_path.display());
+                self.dirty = false;
+                self.last_save = Instant::now();
+                self.unsaved_edits = 0;
+                self.discard_autosave();
+            }
+        }
+    }
+
+    /// Quit or go home from the editor, asking first if there are unsaved edits
+    fn leave_editor(&mut self, then: LeaveAction) {
+        // Ask about each tab with unsaved edits in turn
+        if !self.dirty {
+            if let Some(tab) = self
+                .documents
+                .iter()
+                .position(|doc| doc.as_ref().is_some_and(|doc| doc.dirty))
+            {
+                self.switch_document(tab);
             }
         }
+        if self.dirty {
+            self.prompt = Some(Prompt::UnsavedChanges { then });
+        } else {
+            self.finish_leaving(then);
+        }
+    }
+
+    fn finish_leaving(&mut self, then: LeaveAction) {
+        // Don't leave Whisper transcribing in the background
+        if self.transcribing {
+            self.job.cancel();
+            self.progress_rx = None;
+            self.transcribing = false;
+        }
+        match then {
+            LeaveAction::Quit => {
+                // Stop preview before quitting
+                if self.preview_active {
+                    self.stop_preview();
+                }
+                self.should_quit = true;
+            }
+            LeaveAction::Home => {
+                self.documents.clear();
+                self.state = AppState::Home;
+            }
+        }
+    }
+
+    /// Index of the active tab
+    pub fn active_document(&self) -> usize {
+        self.documents.iter().position(Option::is_none).unwrap_or(0)
+    }
+
+    /// File name of every open tab, marked `*` when it has unsaved edits,
+    /// in tab order
+    pub fn document_labels(&self) -> Vec<String> {
+        let label = |path: Option<&PathBuf>, dirty: bool| {
+            let name = path
+                .and_then(|p| p.file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| "untitled".to_string());
+            if dirty {
+                format!("{}*", name)
+            } else {
+                name
+            }
+        };
+        self.documents
+            .iter()
+            .map(|doc| match doc {
+                Some(doc) => label(doc.srt_path.as_ref(), doc.dirty),
+                None => label(self.srt_path.as_ref(), self.dirty),
+            })
+            .collect()
+    }
+
+    /// Open a subtitle file in a new tab and switch to it. A relative path is
+    /// taken from the current file's folder; an already open file is just
+    /// switched to.
+    pub fn open_document(&mut self, path: &PathBuf) -> Result<()> {
+        let path = match self.srt_path.as_ref().and_then(|p| p.parent()) {
+            Some(dir) if path.is_relative() && !path.exists() => dir.join(path),
+            _ => path.clone(),
+        };
+        if !path.exists() {
+            anyhow::bail!("File not found: {}", path.display());
+        }
+        if self.srt_path.as_ref() == Some(&path) {
+            return Ok(());
+        }
+        if let Some(tab) = self.documents.iter().position(|doc| {
+            doc.as_ref()
+                .is_some_and(|doc| doc.srt_path.as_ref() == Some(&path))
+        }) {
+            self.switch_document(tab);
+            return Ok(());
+        }
+
+        let active = self.active_document();
+        let parked = self.park_document();
+        if let Err(e) = self.load_srt_file(&path) {
+            self.unpark_document(parked);
+            return Err(e);
+        }
+        if self.documents.is_empty() {
+            self.documents.push(Some(parked));
+        } else {
+            self.documents[active] = Some(parked);
+        }
+        self.documents.push(None);
+        self.progress_message =
+            format!("Opened {} in tab {}", path.display(), self.documents.len());
+        Ok(())
+    }
+
+    /// Make another tab the active one
+    pub fn switch_document(&mut self, tab: usize) {
+        let active = self.active_document();
+        if tab == active || tab >= self.documents.len() {
+            return;
+        }
+        // The preview plays the file it was started with
+        if self.preview_active {
+            self.stop_preview();
+        }
+        let doc = self.documents[tab].take().unwrap();
+        self.documents[active] = Some(self.park_document());
+        self.unpark_document(doc);
+        self.progress_message = format!("Tab {}: {}", tab + 1, self.document_labels()[tab]);
+    }
+
+    /// Move the active tab's state out of the editor fields
+    fn park_document(&mut self) -> Document {
+        // Keep unsaved edits safe while the tab is in the background
+        if self.dirty && self.unsaved_edits > 0 {
+            self.write_autosave();
+        }
+        // Undo belongs to the cues it was recorded on
+        self.undo = None;
+        Document {
+            srt_path: self.srt_path.take(),
+            subtitles: std::mem::take(&mut self.subtitles),
+            selected_index: std::mem::take(&mut self.selected_index),
+            text_scroll: std::mem::take(&mut self.text_scroll),
+            selection_anchor: self.selection_anchor.take(),
+            selection: self.selection.take(),
+            dirty: std::mem::take(&mut self.dirty),
+            last_save: std::mem::replace(&mut self.last_save, Instant::now()),
+            unsaved_edits: std::mem::take(&mut self.unsaved_edits),
+            subtitle_format: self.subtitle_format.take(),
+        }
+    }
+
+    fn unpark_document(&mut self, doc: Document) {
+        self.srt_path = doc.srt_path;
+        self.subtitles = doc.subtitles;
+        self.selected_index = doc.selected_index;
+        self.text_scroll = doc.text_scroll;
+        self.selection_anchor = doc.selection_anchor;
+        self.selection = doc.selection;
+        self.dirty = doc.dirty;
+        self.last_save = doc.last_save;
+        self.unsaved_edits = doc.unsaved_edits;
+        self.subtitle_format = doc.subtitle_format;
+    }
+
+    /// Write unsaved edits to the autosave file once the configured time has
+    /// passed or enough edits have piled up
+    fn autosave_if_due(&mut self) {
+        if !self.dirty || self.unsaved_edits == 0 {
+            return;
+        }
+        let Some(interval) = self.config.autosave_interval() else {
+            return;
+        };
+        let too_many = self
+            .config
+            .autosave_edits()
+            .is_some_and(|edits| self.unsaved_edits >= edits);
+        if too_many || self.last_save.elapsed() >= interval {
+            self.write_autosave();
+        }
+    }
+
+    fn write_autosave(&mut self) {
+        let Some(srt_path) = &self.srt_path else {
+            return;
+        };
+        let path = autosave::autosave_path(srt_path, self.config.paths.temp_dir.as_deref());
+        let options = self.config.srt_write_options();
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(anyhow::Error::from)
+            .and_then(|_| crate::subtitle::srt::save_srt(&path, &self.subtitles, &options));
+
+        // Retry on the next interval rather than on every tick
+        self.last_save = Instant::now();
+        self.unsaved_edits = 0;
+        if let Err(e) = result {
+            self.error_message = Some(format!("Autosave failed: {}", e));
+        }
+    }
+
+    fn discard_autosave(&self) {
+        if let Some(srt_path) = &self.srt_path {
+            autosave::remove(srt_path, self.config.paths.temp_dir.as_deref());
+        }
     }
 
     fn start_burning(&mut self) {
@@ -704,20 +2262,103 @@ impl App {
             return;
         }
 
+        // Only the cues that will be burned matter
+        let burned = match self.selected_range() {
+            Some((first, last)) if self.selection_window().is_some() => {
+                &self.subtitles[first..=last]
+            }
+            _ => &self.subtitles[..],
+        };
+        if let Some(media_end_ms) = self.video_duration_ms {
+            let count = timing::count_past_end(burned, media_end_ms);
+            if count > 0 {
+                self.prompt = Some(Prompt::SubtitlesPastEnd {
+                    count,
+                    media_end_ms,
+                });
+                return;
+            }
+        }
+
+        self.choose_burn_output();
+    }
+
+    /// Work out where the burn goes, asking before replacing an existing file
+    fn choose_burn_output(&mut self) {
+        let Some(video_path) = self.video_path.as_ref() else {
+            self.error_message = Some("No video to burn into; open the video instead".to_string());
+            return;
+        };
+        let values = TemplateValues {
+            model: Some(self.whisper_model.as_str().to_string()),
+            lang: Some(self.whisper_language.clone()),
+        };
+        let output_path = match template::resolve(
+            None,
+            self.config.paths.output_template.as_deref(),
+            video_path,
+            &SubtitleBurner::output_extension(video_path),
+            &values,
+            || SubtitleBurner::default_output_path(video_path),
+        ) {
+            Ok(path) => path,
+            Err(e) => {
+                self.error_message = Some(format!("{:#}", e));
+                return;
+            }
+        };
+        let output_path = match self.selection_window() {
+            Some((start_ms, duration_ms)) => {
+                SubtitleBurner::clip_output_path(&output_path, start_ms, start_ms + duration_ms)
+            }
+            None => output_path,
+        };
+        if output_path.exists() {
+            match self.overwrite {
+                OverwritePolicy::Always => {}
+                OverwritePolicy::Never => {
+                    self.error_message = Some(format!(
+                        "{} already exists (started with --no-overwrite)",
+                        output_path.display()
+                    ));
+                    return;
+                }
+                OverwritePolicy::Ask => {
+                    self.prompt = Some(Prompt::OverwriteOutput { path: output_path });
+                    return;
+                }
+            }
+        }
+
+        self.burn_to(output_path);
+    }
+
+    /// Burn the current subtitles into `output_path`
+    fn burn_to(&mut self, output_path: PathBuf) {
         self.state = AppState::BurningSubtitles;
         self.progress = 0.0;
-        self.progress_message = "Starting subtitle burning...".to_string();
+        let window = self.selection_window();
+        self.progress_message = match window {
+            Some((start_ms, duration_ms)) => format!(
+                "Burning the selection ({} to {})...",
+                Subtitle::format_time(start_ms),
+                Subtitle::format_time(start_ms + duration_ms)
+            ),
+            None => "Starting subtitle burning...".to_string(),
+        };
+        // Burning is the last step of the full pipeline, or a step of its own
+        // when the session started from an existing SRT file
+        self.stage = Some(if self.audio_path.is_some() {
+            StageTimer::new(3, 3)
+        } else {
+            StageTimer::new(1, 1)
+        });
 
         let (tx, rx) = mpsc::channel();
         self.progress_rx = Some(rx);
 
         let video_path = self.video_path.clone().unwrap();
         let srt_path = self.srt_path.clone().unwrap();
-        let output_path = video_path.with_file_name(format!(
-            "{}_subtitled.{}",
-            video_path.file_stem().unwrap().to_string_lossy(),
-            video_path.extension().unwrap().to_string_lossy()
-        ));
         self.output_path = Some(output_path.clone());
 
         // Get overlay settings from app state
@@ -725,15 +2366,28 @@ impl App {
         let overlay_width = self.overlay_width;
         let overlay_x_offset = self.overlay_x_offset;
         let overlay_y_offset = self.overlay_y_offset;
+        let temp_dir = self.config.paths.temp_dir.clone();
+        let use_overlay = self.config.use_overlay();
+        let cancel = self.new_job();
 
         std::thread::spawn(move || {
-            let mut burner = SubtitleBurner::new().with_overlay_height(overlay_height);
+            let mut burner = SubtitleBurner::new()
+                .with_overlay(use_overlay)
+                .with_overlay_height(overlay_height)
+                .with_cancel(cancel);
 
             if let Some(width) = overlay_width {
                 burner = burner.with_overlay_width(width);
             }
+            if let Some(dir) = temp_dir {
+                burner = burner.with_temp_dir(dir);
+            }
             burner = burner.with_overlay_x_offset(overlay_x_offset);
             burner = burner.with_overlay_y_offset(overlay_y_offset);
+            // Only the selected cues' span; their times are shifted to match the clip
+            if let Some((start_ms, duration_ms)) = window {
+                burner = burner.with_time_range(start_ms, duration_ms);
+            }
 
             if let Err(e) = burner.burn(&video_path, &srt_path, &output_path, tx.clone()) {
                 let _ = tx.send(ProgressMessage::Error(e.to_string()));
@@ -741,33 +2395,108 @@ impl App {
         });
     }
 
+    fn start_test_burn(&mut self) {
+        let Some(sub) = self.subtitles.get(self.selected_index) else {
+            self.error_message = Some("No subtitles to burn".to_string());
+            return;
+        };
+        let Some(video_path) = self.video_path.clone() else {
+            self.error_message = Some("No video to burn into; open the video instead".to_string());
+            return;
+        };
+        let middle = (sub.start_time + sub.end_time) / 2;
+        let start_ms = middle.saturating_sub(TEST_BURN_WINDOW_MS / 2);
+
+        let srt_path = self.srt_path.clone().unwrap();
+        let workspace = match TempWorkspace::new(self.config.paths.temp_dir.as_deref()) {
+            Ok(workspace) => workspace,
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+                return;
+            }
+        };
+        let extension = video_path
+            .extension()
+            .map(|e| e.to_string_lossy().to_string())
+            .unwrap_or_else(|| burner::DEFAULT_CONTAINER.to_string());
+        let clip_path = workspace.file(&format!("test_burn.{}", extension));
+        self.test_burn = Some((workspace, clip_path.clone()));
+
+        self.state = AppState::TestBurning;
+        self.progress = 0.0;
+        self.progress_message = format!(
+            "Burning {}s around cue {}...",
+            TEST_BURN_WINDOW_MS / 1000,
+            sub.index
+        );
+        self.stage = Some(StageTimer::new(1, 1));
+
+        let (tx, rx) = mpsc::channel();
+        self.progress_rx = Some(rx);
+
+        // Same overlay settings as a full burn, so the clip is representative
+        let overlay_height = self.overlay_height;
+        let overlay_width = self.overlay_width;
+        let overlay_x_offset = self.overlay_x_offset;
+        let overlay_y_offset = self.overlay_y_offset;
+        let temp_dir = self.config.paths.temp_dir.clone();
+        let use_overlay = self.config.use_overlay();
+        let cancel = self.new_job();
+
+        std::thread::spawn(move || {
+            let mut burner = SubtitleBurner::new()
+                .with_overlay(use_overlay)
+                .with_overlay_height(overlay_height)
+                .with_time_range(start_ms, TEST_BURN_WINDOW_MS)
+                .with_cancel(cancel);
+
+            if let Some(width) = overlay_width {
+                burner = burner.with_overlay_width(width);
+            }
+            if let Some(dir) = temp_dir {
+                burner = burner.with_temp_dir(dir);
+            }
+            burner = burner.with_overlay_x_offset(overlay_x_offset);
+            burner = burner.with_overlay_y_offset(overlay_y_offset);
+
+            if let Err(e) = burner.burn(&video_path, &srt_path, &clip_path, tx.clone()) {
+                let _ = tx.send(ProgressMessage::Error(e.to_string()));
+            }
+        });
+    }
+
     fn start_overlay_extraction(&mut self) {
         if self.subtitles.is_empty() {
             self.error_message = Some("No subtitles to extract overlay from".to_string());
             return;
         }
+        let Some(video_path) = self.video_path.clone() else {
+            self.error_message = Some("No video to extract overlay from".to_string());
+            return;
+        };
 
         self.state = AppState::ExtractingOverlay;
         self.progress = 0.0;
         self.progress_message = "Extracting subtitle overlay...".to_string();
+        self.stage = Some(StageTimer::new(1, 1));
 
         let (tx, rx) = mpsc::channel();
         self.progress_rx = Some(rx);
 
-        let video_path = self.video_path.clone().unwrap();
         let srt_path = self.srt_path.clone().unwrap();
-        let overlay_output = video_path.with_file_name(format!(
-            "{}_overlay.mp4",
-            video_path.file_stem().unwrap().to_string_lossy()
-        ));
+        // Never replace an existing file; pick the next free name instead
+        let overlay_output = unique_path(&SubtitleBurner::overlay_output_path(&video_path));
         self.output_path = Some(overlay_output.clone());
 
         // Get overlay settings from app state
         let overlay_height = self.overlay_height;
         let overlay_width = self.overlay_width;
+        let cancel = self.new_job();
 
         std::thread::spawn(move || {
-            let mut burner = SubtitleBurner::new().with_overlay_height(overlay_height);
+            let mut burner = SubtitleBurner::new()
+                .with_overlay_height(overlay_height)
+                .with_cancel(cancel);
 
             if let Some(width) = overlay_width {
                 burner = burner.with_overlay_width(width);
@@ -781,6 +2510,213 @@ impl App {
         });
     }
 
+    /// Select the next (or previous) cue with a lint issue, wrapping around,
+    /// and describe its problems in the status line
+    fn jump_to_issue(&mut self, forward: bool) {
+        let issues = self.lint_issues();
+        let mut positions: Vec<usize> = issues.iter().map(|issue| issue.position).collect();
+        positions.dedup();
+
+        let target = if forward {
+            positions
+                .iter()
+                .find(|&&p| p > self.selected_index)
+                .or(positions.first())
+        } else {
+            positions
+                .iter()
+                .rev()
+                .find(|&&p| p < self.selected_index)
+                .or(positions.last())
+        };
+        let Some(&target) = target else {
+            self.progress_message = "No lint issues".to_string();
+            return;
+        };
+
+        self.selected_index = target;
+        let messages: Vec<&str> = issues
+            .iter()
+            .filter(|issue| issue.position == target)
+            .map(|issue| issue.message.as_str())
+            .collect();
+        self.progress_message = format!("Cue {}: {}", target + 1, messages.join("; "));
+    }
+
+    /// With ripple on, move the cues after the selected one by as much as its
+    /// end moved from `old_end`
+    fn ripple_end_change(&mut self, old_end: u64) {
+        if !self.ripple {
+            return;
+        }
+        let Some(sub) = self.subtitles.get(self.selected_index) else {
+            return;
+        };
+        let delta = sub.end_time as i64 - old_end as i64;
+        if delta != 0 {
+            timing::ripple_from(
+                &mut self.subtitles,
+                self.selected_index + 1,
+                delta,
+                self.config.min_gap_ms(),
+            );
+        }
+    }
+
+    /// Set the selected cue's start (or end) to mpv's current playback position
+    fn mark_from_preview(&mut self, start: bool) {
+        let socket_path = match (&self.preview_socket_path, self.preview_active) {
+            (Some(path), true) => path.clone(),
+            _ => {
+                self.progress_message = "Start the preview (p) to mark times".to_string();
+                return;
+            }
+        };
+
+        let position = match MpvIpc::connect(&socket_path).and_then(|mut mpv| mpv.time_pos_ms()) {
+            Ok(position) => position,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to read preview position: {}", e));
+                return;
+            }
+        };
+
+        let Some(sub) = self.subtitles.get_mut(self.selected_index) else {
+            return;
+        };
+        let old_end = sub.end_time;
+        if start {
+            // Keep the cue's duration when the new start passes its end
+            if position >= sub.end_time {
+                sub.end_time = position + (sub.end_time - sub.start_time).max(100);
+            }
+            sub.start_time = position;
+            self.progress_message = format!("Cue start set to {}", Subtitle::format_time(position));
+        } else if position <= sub.start_time {
+            self.error_message = Some("Out point is before the cue start".to_string());
+        } else {
+            sub.end_time = position;
+            self.progress_message = format!("Cue end set to {}", Subtitle::format_time(position));
+        }
+        self.ripple_end_change(old_end);
+    }
+
+    /// Send a command to the running preview's mpv; false if it couldn't be
+    fn control_preview(&mut self, command: impl FnOnce(&mut MpvIpc) -> Result<()>) -> bool {
+        let socket_path = match (&self.preview_socket_path, self.preview_active) {
+            (Some(path), true) => path.clone(),
+            _ => {
+                self.progress_message = "Start the preview (p) first".to_string();
+                return false;
+            }
+        };
+        match MpvIpc::connect(&socket_path).and_then(|mut mpv| command(&mut mpv)) {
+            Ok(()) => true,
+            Err(e) => {
+                self.error_message = Some(format!("Preview didn't respond: {}", e));
+                false
+            }
+        }
+    }
+
+    /// Re-cut cues `first` to `last` at the pauses in the audio, keeping
+    /// their words; undoable like a replace
+    fn resegment(&mut self, first: usize, last: usize) {
+        let Some(audio_path) = self.audio_path.clone() else {
+            return;
+        };
+        let last = last.min(self.subtitles.len().saturating_sub(1));
+        if first > last {
+            return;
+        }
+        let silences = match silence::read_silences(&audio_path, resegment::MIN_PAUSE_MS) {
+            Ok(silences) => silences,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to find pauses in the audio: {:#}", e));
+                return;
+            }
+        };
+        let max_duration_ms = self
+            .config
+            .subtitles
+            .max_duration_ms
+            .unwrap_or(resegment::DEFAULT_MAX_CUE_MS);
+        let cues = resegment::resegment(&self.subtitles[first..=last], &silences, max_duration_ms);
+        let count = cues.len();
+
+        let before = self.subtitles.clone();
+        self.subtitles.splice(first..=last, cues);
+        fix::reindex(&mut self.subtitles);
+        // The range's cues were replaced, so the selection no longer fits them
+        self.selection = None;
+        self.selection_anchor = None;
+        self.selected_index = first;
+        self.undo = Some(("re-segment", before, self.subtitles.clone()));
+        self.progress_message = format!(
+            "Re-cut cues {}-{} into {} at pauses in the audio - {} undoes",
+            first + 1,
+            last + 1,
+            count,
+            self.keymap.label(Action::Undo)
+        );
+    }
+
+    /// Play just the selected cue's span in the preview, pausing at its end
+    fn play_cue(&mut self) {
+        let Some((index, start, end)) = self
+            .subtitles
+            .get(self.selected_index)
+            .map(|s| (s.index, s.start_time, s.end_time))
+        else {
+            return;
+        };
+        if !self.preview_active {
+            self.progress_message = "Start the preview (p) to play a cue".to_string();
+            return;
+        }
+        if self.control_preview(|mpv| {
+            mpv.seek_ms(start)?;
+            mpv.set_pause(false)
+        }) {
+            self.audition_end_ms = Some(end);
+            self.progress_message = format!(
+                "Playing cue {} ({} - {})",
+                index,
+                Subtitle::format_time(start),
+                Subtitle::format_time(end)
+            );
+        }
+    }
+
+    /// Pause the preview once a cue being auditioned has played to its end.
+    /// Checked every loop, so it stops within about 100ms of the end.
+    fn check_audition(&mut self) {
+        let Some(end) = self.audition_end_ms else {
+            return;
+        };
+        let socket_path = match (&self.preview_socket_path, self.preview_active) {
+            (Some(path), true) => path.clone(),
+            _ => {
+                self.audition_end_ms = None;
+                return;
+            }
+        };
+        let Ok(mut mpv) = MpvIpc::connect(&socket_path) else {
+            self.audition_end_ms = None;
+            return;
+        };
+        match mpv.time_pos_ms() {
+            Ok(position) if position < end => {}
+            Ok(_) => {
+                self.audition_end_ms = None;
+                if let Err(e) = mpv.set_pause(true) {
+                    self.error_message = Some(format!("Preview didn't respond: {}", e));
+                }
+            }
+            Err(_) => self.audition_end_ms = None,
+        }
+    }
+
     fn toggle_preview(&mut self) {
         if self.preview_active {
             // Stop preview
@@ -796,6 +2732,8 @@ impl App {
             let _ = child.kill();
             let _ = child.wait();
         }
+        self.preview_stderr = None;
+        self.audition_end_ms = None;
         // Clean up socket file - retry a few times if needed
         if let Some(socket_path) = self.preview_socket_path.take() {
             for _ in 0..5 {
@@ -816,12 +2754,32 @@ impl App {
         }
     }
 
+    /// Move the preview's band and cues to the current overlay settings,
+    /// through IPC so playback carries on
     fn update_preview_overlay(&mut self) {
-        if !self.preview_active {
+        // Audio-only playback has no overlay to redraw
+        if !self.preview_active || self.video_path.is_none() {
             return;
         }
 
-        // Simple approach: stop and restart preview with new settings
+        if let (Some(socket_path), Some((video_width, video_height))) =
+            (&self.preview_socket_path, self.video_size)
+        {
+            let band = self
+                .preview_burner()
+                .preview_band(video_width, video_height);
+            let updated = MpvIpc::connect(socket_path).and_then(|mut mpv| {
+                for (name, value) in band.sub_options() {
+                    mpv.set(name, &value)?;
+                }
+                mpv.set_video_filters(&band.drawbox())
+            });
+            if updated.is_ok() {
+                return;
+            }
+        }
+
+        // mpv didn't take the update: restart it with the new settings
         self.stop_preview();
 
         // Wait for full cleanup
@@ -831,19 +2789,20 @@ impl App {
     }
 
     fn start_preview(&mut self) {
+        // Without a video, play the audio so cues can still be timed by ear
+        let Some(video_path) = self.video_path.clone() else {
+            match self.audio_path.clone().filter(|p| p.exists()) {
+                Some(audio_path) => self.start_audio_preview(&audio_path),
+                None => self.error_message = Some("No video or audio loaded".to_string()),
+            }
+            return;
+        };
+
         if self.subtitles.is_empty() {
             self.error_message = Some("No subtitles to preview".to_string());
             return;
         }
 
-        let video_path = match &self.video_path {
-            Some(p) => p.clone(),
-            None => {
-                self.error_message = Some("No video loaded".to_string());
-                return;
-            }
-        };
-
         let srt_path = match &self.srt_path {
             Some(p) => p.clone(),
             None => {
@@ -852,35 +2811,27 @@ impl App {
             }
         };
 
-        // Create socket path for IPC
-        let socket_path = std::env::temp_dir().join("auto-subs-preview.sock");
-        // Remove old socket if it exists
-        let _ = std::fs::remove_file(&socket_path);
-
-        // Get overlay settings
-        let overlay_height = self.overlay_height;
-        let overlay_width = self.overlay_width;
-        let overlay_x_offset = self.overlay_x_offset;
-        let overlay_y_offset = self.overlay_y_offset;
-
-        // Create burner with current settings
-        let mut burner = SubtitleBurner::new().with_overlay_height(overlay_height);
-        if let Some(width) = overlay_width {
-            burner = burner.with_overlay_width(width);
+        // Create socket path for IPC, unique to this instance
+        let socket_path = mpv::socket_path(self.config.paths.socket_dir.as_deref());
+        if let Err(e) = mpv::claim_socket(&socket_path) {
+            self.error_message = Some(format!("Failed to start preview: {}", e));
+            return;
         }
-        burner = burner.with_overlay_x_offset(overlay_x_offset);
-        burner = burner.with_overlay_y_offset(overlay_y_offset);
 
         // Launch preview process with IPC
+        let burner = self.preview_burner();
         match burner.launch_preview_process_with_ipc(&video_path, &srt_path, &socket_path) {
-            Ok((child, video_width, video_height)) => {
+            Ok((mut child, band)) => {
+                self.preview_stderr = child
+                    .stderr
+                    .take()
+                    .map(|stderr| OutputTail::capture(stderr, 5));
                 self.preview_process = Some(child);
                 self.preview_socket_path = Some(socket_path);
-                self.preview_video_width = video_width;
-                self.preview_video_height = video_height;
+                self.video_size = Some((band.video_width, band.video_height));
                 self.preview_active = true;
                 self.progress_message =
-                    "Live preview active - adjust with h/H/w/W/x/X/y/Y (Press p to stop)"
+                    "Live preview in mpv - adjust with h/H/w/W/x/X/y/Y (Press p to stop)"
                         .to_string();
             }
             Err(e) => {
@@ -888,4 +2839,69 @@ impl App {
             }
         }
     }
+
+    /// A burner with the current overlay settings, as the preview shows them
+    fn preview_burner(&self) -> SubtitleBurner {
+        let mut burner = SubtitleBurner::new()
+            .with_overlay_height(self.overlay_height)
+            .with_overlay_x_offset(self.overlay_x_offset)
+            .with_overlay_y_offset(self.overlay_y_offset);
+        if let Some(width) = self.overlay_width {
+            burner = burner.with_overlay_width(width);
+        }
+        burner
+    }
+
+    /// Play the audio alone, from the selected cue, in an mpv with no window
+    fn start_audio_preview(&mut self, audio_path: &Path) {
+        let socket_path = mpv::socket_path(self.config.paths.socket_dir.as_deref());
+        if let Err(e) = mpv::claim_socket(&socket_path) {
+            self.error_message = Some(format!("Failed to start playback: {}", e));
+            return;
+        }
+
+        let start_ms = self
+            .subtitles
+            .get(self.selected_index)
+            .map_or(0, |sub| sub.start_time);
+        match mpv::launch_audio(audio_path, &socket_path, start_ms) {
+            Ok(mut child) => {
+                self.preview_stderr = child
+                    .stderr
+                    .take()
+                    .map(|stderr| OutputTail::capture(stderr, 5));
+                self.preview_process = Some(child);
+                self.preview_socket_path = Some(socket_path);
+                self.preview_active = true;
+                self.progress_message = format!(
+                    "Playing audio - {} pause, {} play from cue, {} play cue, {}/{} mark (p to stop)",
+                    self.keymap.label(Action::PlayPause),
+                    self.keymap.label(Action::SeekToCue),
+                    self.keymap.label(Action::PlayCue),
+                    self.keymap.label(Action::MarkStart),
+                    self.keymap.label(Action::MarkEnd)
+                );
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to start playback: {}", e));
+            }
+        }
+    }
+}
+
+/// Parse "HH:MM:SS,mmm --> HH:MM:SS,mmm" (or the two timecodes separated by
+/// spaces) as typed into the cue timing prompt
+fn parse_cue_timing(input: &str) -> Result<(u64, u64)> {
+    let input = input.replace("-->", " ");
+    let mut parts = input.split_whitespace();
+    let (Some(start), Some(end), None) = (parts.next(), parts.next(), parts.next()) else {
+        anyhow::bail!("Expected a start and an end time, e.g. 00:01:02,500 --> 00:01:04,000");
+    };
+
+    let start_time = Subtitle::parse_time(start)?;
+    let end_time = Subtitle::parse_time(end)?;
+    if start_time >= end_time {
+        anyhow::bail!("Start time must be before end time");
+    }
+    Ok((start_time, end_time))
 }
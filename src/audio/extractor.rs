@@ -4,22 +4,107 @@ use std::process::{Command, Stdio};
 use std::sync::mpsc::Sender;
 
 use crate::app::ProgressMessage;
+use crate::audio::wav::WavLayout;
+use crate::subtitle::srt::Subtitle;
+use crate::utils::cancel::CancelHandle;
+use crate::utils::ffmpeg;
+use crate::utils::temp::{self, TempWorkspace};
 
-pub struct AudioExtractor;
+pub struct AudioExtractor {
+    /// Kills the running FFmpeg process when the job is cancelled
+    cancel: CancelHandle,
+}
 
 impl AudioExtractor {
     pub fn new() -> Self {
-        Self
+        Self {
+            cancel: CancelHandle::new(),
+        }
+    }
+
+    /// Run FFmpeg through `cancel`, so the job can be stopped from the UI
+    pub fn with_cancel(mut self, cancel: CancelHandle) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Whether the input has at least one audio stream
+    pub fn has_audio_stream(&self, video_path: &Path) -> Result<bool> {
+        let output = Command::new("ffprobe")
+            .args([
+                "-v",
+                "error",
+                "-select_streams",
+                "a",
+                "-show_entries",
+                "stream=index",
+                "-of",
+                "csv=p=0",
+            ])
+            .arg(video_path)
+            .output()
+            .context("Failed to run ffprobe")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("ffprobe failed: {}", stderr.trim());
+        }
+
+        Ok(!String::from_utf8_lossy(&output.stdout).trim().is_empty())
+    }
+
+    /// Length of the input in milliseconds, if ffprobe can tell
+    pub fn duration_ms(&self, video_path: &Path) -> Option<u64> {
+        let output = Command::new("ffprobe")
+            .args([
+                "-v",
+                "error",
+                "-show_entries",
+                "format=duration",
+                "-of",
+                "default=noprint_wrappers=1:nokey=1",
+            ])
+            .arg(video_path)
+            .output()
+            .ok()?;
+        let seconds: f64 = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .ok()?;
+        Some((seconds * 1000.0) as u64)
+    }
+
+    /// Whether `audio_path` holds audio already extracted from `video_path`:
+    /// it exists, was written after the video last changed and is complete
+    /// (an extraction killed halfway leaves a newer but cut-off file)
+    pub fn is_up_to_date(&self, video_path: &Path, audio_path: &Path) -> bool {
+        let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let newer = match (modified(video_path), modified(audio_path)) {
+            (Some(video), Some(audio)) => audio >= video,
+            _ => false,
+        };
+        newer && WavLayout::read(audio_path).is_ok_and(|layout| layout.is_complete())
     }
 
     /// Extract audio from video file to WAV format suitable for Whisper
-    /// (16kHz, mono, 16-bit PCM)
+    /// (16kHz, mono, 16-bit PCM). A `video_path` of `-` reads from stdin.
     pub fn extract(
         &self,
         video_path: &Path,
         output_path: &Path,
         progress_tx: Sender<ProgressMessage>,
     ) -> Result<()> {
+        if temp::is_stdin(video_path) {
+            let _ = progress_tx.send(ProgressMessage::Progress(
+                0.05,
+                "Reading input from stdin...".to_string(),
+            ));
+            // Removed again when the workspace drops at the end of this call
+            let workspace = TempWorkspace::new(None)?;
+            let buffered = workspace.buffer_stdin("stdin-input")?;
+            return self.extract(&buffered, output_path, progress_tx);
+        }
+
         let _ = progress_tx.send(ProgressMessage::Progress(
             0.1,
             "Starting FFmpeg...".to_string(),
@@ -33,10 +118,18 @@ impl AudioExtractor {
             .status()
             .context("FFmpeg not found. Please install FFmpeg and ensure it's in your PATH.")?;
 
+        // Fail early with a clear message instead of a cryptic FFmpeg error
+        if !self.has_audio_stream(video_path)? {
+            anyhow::bail!("Input has no audio stream to transcribe");
+        }
+
         let _ = progress_tx.send(ProgressMessage::Progress(
             0.2,
             "Extracting audio...".to_string(),
         ));
+        let duration_ms = self.duration_ms(video_path).filter(|&ms| ms > 0);
+        // Reports arrive twice a second; pass on one per whole percent
+        let mut last_percent = 0;
 
         // Run ffmpeg to extract audio
         // -i input: input file
@@ -45,29 +138,44 @@ impl AudioExtractor {
         // -ac 1: mono channel
         // -c:a pcm_s16le: 16-bit PCM
         // -y: overwrite output file
-        let output = Command::new("ffmpeg")
-            .args([
-                "-i",
-                video_path.to_str().unwrap(),
-                "-vn",
-                "-ar",
-                "16000",
-                "-ac",
-                "1",
-                "-c:a",
-                "pcm_s16le",
-                "-y",
-                output_path.to_str().unwrap(),
-            ])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+        // Progress reports on stdout move the bar from 0.2 to 1.0
+        let output = self
+            .cancel
+            .output_with_lines(
+                Command::new("ffmpeg")
+                    .args(ffmpeg::PROGRESS_ARGS)
+                    .arg("-i")
+                    .arg(video_path)
+                    .args(["-vn", "-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le", "-y"])
+                    .arg(output_path),
+                |line| {
+                    let (Some(done), Some(total)) = (ffmpeg::progress_time_ms(line), duration_ms)
+                    else {
+                        return;
+                    };
+                    let fraction = (done as f32 / total as f32).min(1.0);
+                    let percent = (fraction * 100.0) as u32;
+                    if percent <= last_percent {
+                        return;
+                    }
+                    last_percent = percent;
+                    let _ = progress_tx.send(ProgressMessage::Progress(
+                        0.2 + 0.8 * fraction,
+                        format!(
+                            "Extracting audio... {} / {}",
+                            Subtitle::format_time(done.min(total)),
+                            Subtitle::format_time(total)
+                        ),
+                    ));
+                },
+            )
             .context("Failed to run FFmpeg")?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             anyhow::bail!("FFmpeg failed: {}", stderr);
         }
+        ffmpeg::check_output(output_path, "Audio extraction", &output.stderr)?;
 
         let _ = progress_tx.send(ProgressMessage::Progress(
             1.0,
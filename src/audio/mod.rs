@@ -1 +1,3 @@
 pub mod extractor;
+pub mod silence;
+pub mod wav;
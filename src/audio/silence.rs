@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// RMS level below which a window counts as silence (about -40 dBFS)
+pub const SILENCE_RMS_THRESHOLD: f32 = 0.01;
+
+/// Windows per second when measuring audio energy (20ms each)
+const WINDOWS_PER_SEC: u64 = 50;
+
+/// Whether a window of samples is loud enough to be speech
+pub fn is_loud(window: &[f32]) -> bool {
+    let energy: f32 = window.iter().map(|s| s * s).sum::<f32>() / window.len().max(1) as f32;
+    energy.sqrt() >= SILENCE_RMS_THRESHOLD
+}
+
+/// Collects the quiet stretches of a stream of 20ms windows
+struct SilenceRuns {
+    min_ms: u64,
+    window: u64,
+    quiet_since: Option<u64>,
+    runs: Vec<(u64, u64)>,
+}
+
+impl SilenceRuns {
+    fn new(min_ms: u64) -> Self {
+        Self {
+            min_ms,
+            window: 0,
+            quiet_since: None,
+            runs: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, loud: bool) {
+        let at = self.window * 1000 / WINDOWS_PER_SEC;
+        if loud {
+            self.close(at);
+        } else if self.quiet_since.is_none() {
+            self.quiet_since = Some(at);
+        }
+        self.window += 1;
+    }
+
+    fn close(&mut self, at: u64) {
+        if let Some(start) = self.quiet_since.take() {
+            if at - start >= self.min_ms {
+                self.runs.push((start, at));
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<(u64, u64)> {
+        self.close(self.window * 1000 / WINDOWS_PER_SEC);
+        self.runs
+    }
+}
+
+/// Silences of at least `min_ms` in a stream of `channels` interleaved
+/// channels at `sample_rate` Hz, as (start, end) in milliseconds
+fn silences(
+    samples: impl Iterator<Item = f32>,
+    sample_rate: u32,
+    channels: u16,
+    min_ms: u64,
+) -> Vec<(u64, u64)> {
+    let window = (sample_rate as u64 / WINDOWS_PER_SEC).max(1) as usize * channels as usize;
+    let mut runs = SilenceRuns::new(min_ms);
+    let mut buffer = Vec::with_capacity(window);
+    for sample in samples {
+        buffer.push(sample);
+        if buffer.len() == window {
+            runs.push(is_loud(&buffer));
+            buffer.clear();
+        }
+    }
+    if !buffer.is_empty() {
+        runs.push(is_loud(&buffer));
+    }
+    runs.finish()
+}
+
+/// Silences of at least `min_ms` in the WAV file at `path`, read a window at
+/// a time so long recordings aren't loaded whole
+pub fn read_silences(path: &Path, min_ms: u64) -> Result<Vec<(u64, u64)>> {
+    let reader = hound::WavReader::open(path)
+        .with_context(|| format!("{} is not a readable WAV file", path.display()))?;
+    let spec = reader.spec();
+    let samples: Box<dyn Iterator<Item = f32>> = match spec.sample_format {
+        hound::SampleFormat::Int => {
+            let max_value = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            Box::new(
+                reader
+                    .into_samples::<i32>()
+                    .map_while(Result::ok)
+                    .map(move |s| s as f32 / max_value),
+            )
+        }
+        hound::SampleFormat::Float => Box::new(reader.into_samples::<f32>().map_while(Result::ok)),
+    };
+
+    Ok(silences(samples, spec.sample_rate, spec.channels, min_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_pauses_between_speech() {
+        let second = 16000;
+        // Speech, a 0.5s pause, speech, a 0.1s pause, speech, then silence
+        let mut samples = vec![0.5; second];
+        samples.extend(vec![0.0; second / 2]);
+        samples.extend(vec![0.5; second]);
+        samples.extend(vec![0.0; second / 10]);
+        samples.extend(vec![0.5; second]);
+        samples.extend(vec![0.0; second]);
+
+        assert_eq!(
+            silences(samples.iter().copied(), 16000, 1, 300),
+            vec![(1000, 1500), (3600, 4600)]
+        );
+        assert_eq!(silences(samples.iter().copied(), 16000, 1, 100).len(), 3);
+    }
+}
@@ -0,0 +1,172 @@
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Value FFmpeg writes into the RIFF and data sizes until it finishes the
+/// file; still there means it was killed while writing
+const PLACEHOLDER_SIZE: u64 = 0xFFFF_FFFF;
+
+/// What a WAV file's header claims next to what the file actually holds,
+/// used to tell a complete extraction from one that was cut off
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WavLayout {
+    /// File length according to the RIFF header, in bytes
+    pub riff_len: u64,
+    /// Actual file length in bytes
+    pub file_len: u64,
+    /// Sample data length according to the data chunk header, in bytes
+    pub data_len: u64,
+    /// Sample data actually present, in bytes
+    pub data_available: u64,
+    /// Bytes of sample data per second of audio
+    pub byte_rate: u64,
+}
+
+impl WavLayout {
+    /// Read the layout of the WAV file at `path`. Fails when it has no RIFF
+    /// header, format or data chunk, i.e. nothing of it can be used.
+    pub fn read(path: &Path) -> Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        Self::from_reader(BufReader::new(file))
+    }
+
+    pub fn from_reader(mut reader: impl Read + Seek) -> Result<Self> {
+        let file_len = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(0))?;
+
+        let mut header = [0; 12];
+        reader
+            .read_exact(&mut header)
+            .context("File is too short for a WAV header")?;
+        if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+            anyhow::bail!("No RIFF/WAVE header");
+        }
+        let riff_len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as u64 + 8;
+
+        let mut byte_rate = None;
+        loop {
+            let mut chunk = [0; 8];
+            reader
+                .read_exact(&mut chunk)
+                .context("No data chunk; the header was never finished")?;
+            let len = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]) as u64;
+            let body = reader.stream_position()?;
+            match &chunk[0..4] {
+                b"fmt " => {
+                    let mut fmt = [0; 12];
+                    reader
+                        .read_exact(&mut fmt)
+                        .context("Format chunk is cut off")?;
+                    byte_rate = Some(u32::from_le_bytes([fmt[8], fmt[9], fmt[10], fmt[11]]) as u64);
+                }
+                b"data" => {
+                    let byte_rate =
+                        byte_rate.context("Data chunk comes before the format chunk")?;
+                    return Ok(Self {
+                        riff_len,
+                        file_len,
+                        data_len: len,
+                        data_available: file_len - body,
+                        byte_rate,
+                    });
+                }
+                _ => {}
+            }
+            // Chunks are padded to an even length
+            reader.seek(SeekFrom::Start(body + len + len % 2))?;
+        }
+    }
+
+    /// The data chunk is shorter than its header says
+    pub fn is_truncated(&self) -> bool {
+        self.data_available < self.data_len
+    }
+
+    /// The RIFF header gives a different file length than the file has
+    pub fn riff_size_mismatch(&self) -> bool {
+        self.riff_len != self.file_len
+    }
+
+    /// Neither cut off nor mis-sized: safe to reuse as a finished extraction
+    pub fn is_complete(&self) -> bool {
+        !self.is_truncated() && !self.riff_size_mismatch()
+    }
+
+    /// Seconds of audio the file holds
+    pub fn available_secs(&self) -> f64 {
+        self.data_len.min(self.data_available) as f64 / self.byte_rate.max(1) as f64
+    }
+
+    /// What is wrong with the file, worded for the user; `None` if nothing
+    pub fn problem(&self) -> Option<String> {
+        if self.riff_len == PLACEHOLDER_SIZE + 8 || self.data_len == PLACEHOLDER_SIZE {
+            Some(format!(
+                "its header was never filled in, so the extraction was interrupted after {:.1}s of audio",
+                self.available_secs()
+            ))
+        } else if self.is_truncated() {
+            Some(format!(
+                "it is cut off after {:.1}s of the {:.1}s of audio its header promises",
+                self.available_secs(),
+                self.data_len as f64 / self.byte_rate.max(1) as f64
+            ))
+        } else if self.riff_size_mismatch() {
+            Some(format!(
+                "its RIFF header gives a size of {} bytes but the file has {}",
+                self.riff_len, self.file_len
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// A 16kHz mono 16-bit WAV with the given header sizes and `samples`
+    /// bytes of data actually present
+    fn wav(riff_len: u32, data_len: u32, samples: usize) -> Cursor<Vec<u8>> {
+        let mut data = b"RIFF".to_vec();
+        data.extend(riff_len.to_le_bytes());
+        data.extend(b"WAVEfmt ");
+        data.extend(16u32.to_le_bytes());
+        data.extend([1, 0, 1, 0]);
+        data.extend(16000u32.to_le_bytes());
+        data.extend(32000u32.to_le_bytes());
+        data.extend([2, 0, 16, 0]);
+        data.extend(b"data");
+        data.extend(data_len.to_le_bytes());
+        data.extend(vec![0; samples]);
+        Cursor::new(data)
+    }
+
+    #[test]
+    fn test_spots_cut_off_files() {
+        let complete = WavLayout::from_reader(wav(36 + 32000, 32000, 32000)).unwrap();
+        assert!(complete.is_complete());
+        assert_eq!(complete.problem(), None);
+        assert_eq!(complete.available_secs(), 1.0);
+
+        // Killed mid-write: FFmpeg's placeholder sizes are still in the header
+        let killed = WavLayout::from_reader(wav(u32::MAX, u32::MAX, 16000)).unwrap();
+        assert!(killed.is_truncated());
+        assert_eq!(killed.available_secs(), 0.5);
+        assert!(killed.problem().unwrap().contains("interrupted after 0.5s"));
+
+        let cut = WavLayout::from_reader(wav(36 + 32000, 32000, 9600)).unwrap();
+        assert!(cut
+            .problem()
+            .unwrap()
+            .contains("cut off after 0.3s of the 1.0s"));
+
+        let resized = WavLayout::from_reader(wav(100, 32000, 32000)).unwrap();
+        assert!(!resized.is_truncated() && resized.riff_size_mismatch());
+
+        assert!(WavLayout::from_reader(Cursor::new(b"RIFF".to_vec())).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
+use crate::subtitle::format::SubtitleFormat;
+
 #[derive(Parser, Debug)]
 #[command(name = "auto-subs-tui")]
 #[command(author = "tmih06")]
@@ -28,9 +30,19 @@ pub struct Cli {
     #[arg(short = 'n', long, global = true)]
     pub no_overwrite: bool,
 
+    /// Never prompt or wait for input (for scripts and CI): existing outputs
+    /// are kept unless --yes is given, and anything that would ask fails instead
+    #[arg(long, global = true)]
+    pub non_interactive: bool,
+
     /// Use custom config file
     #[arg(long, global = true, value_name = "FILE")]
     pub config: Option<PathBuf>,
+
+    /// Draw the TUI on the main screen instead of the alternate screen
+    /// (for terminals and multiplexers where the alternate screen misbehaves)
+    #[arg(long, global = true)]
+    pub no_altscreen: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -47,23 +59,64 @@ pub enum Commands {
     /// Burn subtitles into video
     Burn(BurnArgs),
 
+    /// Render subtitles to a transparent overlay video for compositing elsewhere
+    Overlay(OverlayArgs),
+
     /// Launch TUI editor for existing SRT file
     Edit(EditArgs),
 
+    /// Clean up common transcription problems in an SRT file
+    Fix(FixArgs),
+
+    /// Check an SRT file for timing and readability problems
+    Lint(LintArgs),
+
+    /// Shift every cue in an SRT file by a fixed offset
+    Shift(ShiftArgs),
+
+    /// Preview subtitles on a video in an external player (ffplay or mpv)
+    Preview(PreviewArgs),
+
+    /// List or remove downloaded Whisper models
+    Models(ModelsArgs),
+
+    /// Time several Whisper models on the same sample and compare their output
+    Benchmark(BenchmarkArgs),
+
     /// Manage configuration
     Config(ConfigArgs),
 }
 
 #[derive(Parser, Debug)]
 pub struct ProcessArgs {
-    /// Input video file path
+    /// Input video file path, or an http(s) URL to download first (with
+    /// yt-dlp when installed, otherwise FFmpeg)
     #[arg(value_name = "VIDEO")]
     pub input: PathBuf,
 
+    /// Use the video's embedded subtitle track N (0-based, text tracks only)
+    /// instead of transcribing
+    #[arg(long, value_name = "N")]
+    pub from_embedded: Option<usize>,
+
     /// Output video file path (default: <input>_subtitled.<ext>)
     #[arg(short = 'o', long, value_name = "FILE")]
     pub output: Option<PathBuf>,
 
+    /// Name the output from a template instead, e.g. "{dir}/subbed/{stem}.{model}.{ext}"
+    /// (tokens: {dir}, {stem}, {ext}, {model}, {lang}, {date})
+    #[arg(long, value_name = "TEMPLATE", conflicts_with = "output")]
+    pub output_template: Option<String>,
+
+    /// Also cut the output into parts of at most N minutes
+    /// (<output>_part01.<ext>, ...), each with its own <part>.srt
+    #[arg(long, value_name = "N", conflicts_with = "split_chapters")]
+    pub split_minutes: Option<u64>,
+
+    /// Also cut the output into one part per chapter of the video
+    #[arg(long)]
+    pub split_chapters: bool,
+
     /// Whisper model to use
     #[arg(short = 'm', long, default_value = "base")]
     pub model: WhisperModel,
@@ -72,10 +125,19 @@ pub struct ProcessArgs {
     #[arg(short = 'l', long, default_value = "auto")]
     pub language: String,
 
-    /// SRT file output path (default: <input>.srt)
+    /// Base URL or local directory to fetch Whisper models from (default: Hugging Face)
+    #[arg(long, value_name = "URL_OR_DIR")]
+    pub model_url: Option<String>,
+
+    /// SRT file output path (default: <input>.srt); a .vtt, .sbv or .lrc extension picks that format
     #[arg(long, value_name = "FILE")]
     pub srt_output: Option<PathBuf>,
 
+    /// Write --srt-output in this format instead of the one its extension names
+    /// (alone: write <input>.<format>)
+    #[arg(long, value_name = "FORMAT")]
+    pub output_format: Option<SubtitleFormat>,
+
     /// Audio file output path (default: <input>.wav)
     #[arg(long, value_name = "FILE")]
     pub audio_output: Option<PathBuf>,
@@ -84,29 +146,141 @@ pub struct ProcessArgs {
     #[arg(short = 'k', long)]
     pub keep_files: bool,
 
+    /// Keep the extracted audio (<input>.wav) and reuse it while the video is unchanged
+    #[arg(long)]
+    pub keep_audio: bool,
+
+    /// Refuse URL inputs instead of downloading them (burning needs the whole video)
+    #[arg(long)]
+    pub no_download: bool,
+
+    /// Write the settings used to <output>.json (also done with --keep-files)
+    #[arg(long)]
+    pub metadata: bool,
+
+    /// Shell command run on the SRT before burning, with its path appended as
+    /// the last argument ($1); it must rewrite the file in place
+    #[arg(long, value_name = "CMD")]
+    pub post_hook: Option<String>,
+
     /// Translate to English (if source is not English)
     #[arg(long)]
     pub translate: bool,
 
+    /// Keep the English-only model even when --language asks for another
+    /// language or auto (by default its multilingual counterpart is used)
+    #[arg(long)]
+    pub force: bool,
+
+    /// Skip leading and trailing silence when transcribing (cue times still match the video)
+    #[arg(long)]
+    pub trim_silence: bool,
+
+    /// Continuous captions: keep each cue on screen until the next one starts,
+    /// and the last until the end of the media (no gaps, ignores --min-gap)
+    #[arg(long)]
+    pub continuous: bool,
+
+    /// Keep each Whisper segment as one cue instead of splitting it at sentence
+    /// ends. Better for lyrics, poetry or dictation, where "sentences" are
+    /// short or unpunctuated; long segments then stay long
+    #[arg(long)]
+    pub no_split: bool,
+
+    /// Minimum gap in milliseconds between generated cues (default: 40)
+    #[arg(long, value_name = "MS")]
+    pub min_gap: Option<u64>,
+
+    /// Extend generated cues shorter than this, into the following silence
+    #[arg(long, value_name = "MS")]
+    pub min_duration: Option<u64>,
+
+    /// Split generated cues longer than this at sentence boundaries
+    #[arg(long, value_name = "MS")]
+    pub max_duration: Option<u64>,
+
+    /// Whisper sampling temperature (0.0 = deterministic)
+    #[arg(long, default_value = "0.0", value_name = "TEMP")]
+    pub temperature: f32,
+
+    /// Probability above which a low-confidence segment is dropped as silence
+    #[arg(long, default_value = "0.6", value_name = "PROB")]
+    pub no_speech_threshold: f32,
+
+    /// Average log probability below which a decode is considered failed
+    #[arg(
+        long,
+        default_value = "-1.0",
+        value_name = "LOGPROB",
+        allow_hyphen_values = true
+    )]
+    pub logprob_threshold: f32,
+
     /// Subtitle font size
     #[arg(long, default_value = "24")]
     pub font_size: u32,
 
-    /// Subtitle font color in hex (e.g., FFFFFF for white)
+    /// Subtitle font color as RRGGBB hex, optionally with # (e.g., FFFFFF for white)
     #[arg(long, default_value = "FFFFFF")]
     pub font_color: String,
 
-    /// Subtitle outline color in hex
+    /// Subtitle outline color as RRGGBB hex, optionally with #
     #[arg(long, default_value = "000000")]
     pub outline_color: String,
 
+    /// Karaoke: highlight each word as it is spoken, from Whisper's word
+    /// timings (burns normally, with a warning, when there are none)
+    #[arg(long)]
+    pub karaoke: bool,
+
+    /// Color karaoke words change to as they are spoken, as RRGGBB hex;
+    /// they start in --font-color
+    #[arg(long, default_value = "FFFF00", requires = "karaoke")]
+    pub karaoke_color: String,
+
+    /// Outline thickness in pixels (default: libass default)
+    #[arg(long, value_name = "PX")]
+    pub outline: Option<f32>,
+
+    /// Shadow depth in pixels (default: libass default)
+    #[arg(long, value_name = "PX")]
+    pub shadow: Option<f32>,
+
+    /// Horizontal alignment of the text within the overlay band (default: center)
+    #[arg(long)]
+    pub align: Option<TextAlign>,
+
+    /// Wrap burned cues at spaces to fit the overlay band at the burned font size
+    #[arg(long, conflicts_with = "karaoke")]
+    pub wrap: bool,
+
+    /// Font to measure --wrap with (TTF/OTF); without it widths are
+    /// estimated from the character count
+    #[arg(long, value_name = "FILE", requires = "wrap")]
+    pub font_file: Option<PathBuf>,
+
     /// Custom FFmpeg subtitle style string
     #[arg(long)]
     pub style: Option<String>,
 
-    /// Use overlay method (creates separate subtitle overlay video)
-    #[arg(long, default_value = "true")]
-    pub use_overlay: bool,
+    /// Named style preset (netflix, youtube, bold-yellow, or one from styles.toml
+    /// in the config directory); --style overrides individual fields
+    #[arg(long, value_name = "NAME")]
+    pub style_preset: Option<String>,
+
+    /// Target video bitrate for the final encode, e.g. 4M or 2500k
+    /// (ignored when a CRF is set)
+    #[arg(long, value_name = "RATE")]
+    pub video_bitrate: Option<String>,
+
+    /// Bitrate of the subtitle overlay, e.g. 2M (default: scaled to its size)
+    #[arg(long, value_name = "RATE")]
+    pub overlay_bitrate: Option<String>,
+
+    /// Use overlay method (creates separate subtitle overlay video);
+    /// `--use-overlay false` burns directly (default: burn_method from config, else overlay)
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub use_overlay: Option<bool>,
 
     /// Keep overlay file for reuse or customization
     #[arg(long)]
@@ -127,11 +301,19 @@ pub struct ProcessArgs {
     /// Overlay width in pixels (default: video width)
     #[arg(long)]
     pub overlay_width: Option<u32>,
+
+    /// Font size as a fraction of the overlay height (default: 0.38)
+    #[arg(long, value_name = "RATIO")]
+    pub font_scale: Option<f64>,
+
+    /// Minimum font size when scaling to the overlay height (default: 24)
+    #[arg(long, value_name = "SIZE")]
+    pub min_font_size: Option<u32>,
 }
 
 #[derive(Parser, Debug)]
 pub struct ExtractArgs {
-    /// Input video file path
+    /// Input video file path (`-` to read from stdin)
     #[arg(value_name = "VIDEO")]
     pub input: PathBuf,
 
@@ -139,6 +321,11 @@ pub struct ExtractArgs {
     #[arg(short = 'o', long, value_name = "FILE")]
     pub output: Option<PathBuf>,
 
+    /// Name the output from a template instead, e.g. "{dir}/audio/{stem}.{ext}"
+    /// (tokens: {dir}, {stem}, {ext}, {date})
+    #[arg(long, value_name = "TEMPLATE", conflicts_with = "output")]
+    pub output_template: Option<String>,
+
     /// Audio sample rate in Hz
     #[arg(long, default_value = "16000")]
     pub sample_rate: u32,
@@ -154,14 +341,25 @@ pub struct ExtractArgs {
 
 #[derive(Parser, Debug)]
 pub struct TranscribeArgs {
-    /// Input audio file path
+    /// Input audio file path (`-` to read a WAV from stdin), or an http(s)
+    /// URL whose audio is fetched with yt-dlp or FFmpeg
     #[arg(value_name = "AUDIO")]
     pub input: PathBuf,
 
-    /// Output SRT file path (default: <input>.srt)
+    /// Output subtitle path (default: <input>.srt); a .vtt, .sbv or .lrc extension picks
+    /// that format
     #[arg(short = 'o', long, value_name = "FILE")]
     pub output: Option<PathBuf>,
 
+    /// Name the output from a template instead, e.g. "{dir}/{stem}.{lang}.{ext}"
+    /// (tokens: {dir}, {stem}, {ext}, {model}, {lang}, {date})
+    #[arg(long, value_name = "TEMPLATE", conflicts_with = "output")]
+    pub output_template: Option<String>,
+
+    /// Write this format instead of the one the output's extension names
+    #[arg(long, value_name = "FORMAT")]
+    pub output_format: Option<SubtitleFormat>,
+
     /// Whisper model to use
     #[arg(short = 'm', long, default_value = "base")]
     pub model: WhisperModel,
@@ -170,10 +368,77 @@ pub struct TranscribeArgs {
     #[arg(short = 'l', long, default_value = "auto")]
     pub language: String,
 
+    /// Base URL or local directory to fetch Whisper models from (default: Hugging Face)
+    #[arg(long, value_name = "URL_OR_DIR")]
+    pub model_url: Option<String>,
+
+    /// Write the settings used to <output>.json next to the SRT
+    #[arg(long)]
+    pub metadata: bool,
+
+    /// Append the new cues to this existing SRT instead of overwriting
+    /// (written back to it unless --output is given)
+    #[arg(long, value_name = "SRT")]
+    pub append_to: Option<PathBuf>,
+
+    /// Shift appended cues by this many milliseconds (default: the end of the
+    /// last existing cue)
+    #[arg(long, value_name = "MS", requires = "append_to")]
+    pub offset: Option<u64>,
+
     /// Translate to English (if source is not English)
     #[arg(long)]
     pub translate: bool,
 
+    /// Keep the English-only model even when --language asks for another
+    /// language or auto (by default its multilingual counterpart is used)
+    #[arg(long)]
+    pub force: bool,
+
+    /// Skip leading and trailing silence when transcribing (cue times still match the video)
+    #[arg(long)]
+    pub trim_silence: bool,
+
+    /// Continuous captions: keep each cue on screen until the next one starts,
+    /// and the last until the end of the media (no gaps, ignores --min-gap)
+    #[arg(long)]
+    pub continuous: bool,
+
+    /// Keep each Whisper segment as one cue instead of splitting it at sentence
+    /// ends. Better for lyrics, poetry or dictation, where "sentences" are
+    /// short or unpunctuated; long segments then stay long
+    #[arg(long)]
+    pub no_split: bool,
+
+    /// Minimum gap in milliseconds between generated cues (default: 40)
+    #[arg(long, value_name = "MS")]
+    pub min_gap: Option<u64>,
+
+    /// Extend generated cues shorter than this, into the following silence
+    #[arg(long, value_name = "MS")]
+    pub min_duration: Option<u64>,
+
+    /// Split generated cues longer than this at sentence boundaries
+    #[arg(long, value_name = "MS")]
+    pub max_duration: Option<u64>,
+
+    /// Whisper sampling temperature (0.0 = deterministic)
+    #[arg(long, default_value = "0.0", value_name = "TEMP")]
+    pub temperature: f32,
+
+    /// Probability above which a low-confidence segment is dropped as silence
+    #[arg(long, default_value = "0.6", value_name = "PROB")]
+    pub no_speech_threshold: f32,
+
+    /// Average log probability below which a decode is considered failed
+    #[arg(
+        long,
+        default_value = "-1.0",
+        value_name = "LOGPROB",
+        allow_hyphen_values = true
+    )]
+    pub logprob_threshold: f32,
+
     /// Transcription provider to use
     #[arg(long, default_value = "whisper")]
     pub provider: String,
@@ -193,41 +458,106 @@ pub struct BurnArgs {
     #[arg(short = 'o', long, value_name = "FILE")]
     pub output: Option<PathBuf>,
 
+    /// Name the output from a template instead, e.g. "{dir}/subbed/{stem}.{ext}"
+    /// (tokens: {dir}, {stem}, {ext}, {date})
+    #[arg(long, value_name = "TEMPLATE", conflicts_with = "output")]
+    pub output_template: Option<String>,
+
+    /// Also cut the output into parts of at most N minutes
+    /// (<output>_part01.<ext>, ...), each with its own <part>.srt
+    #[arg(long, value_name = "N", conflicts_with = "split_chapters")]
+    pub split_minutes: Option<u64>,
+
+    /// Also cut the output into one part per chapter of the video
+    #[arg(long)]
+    pub split_chapters: bool,
+
     /// Subtitle font size
     #[arg(long, default_value = "24")]
     pub font_size: u32,
 
-    /// Subtitle font color in hex (e.g., FFFFFF for white)
+    /// Subtitle font color as RRGGBB hex, optionally with # (e.g., FFFFFF for white)
     #[arg(long, default_value = "FFFFFF")]
     pub font_color: String,
 
-    /// Subtitle outline color in hex
+    /// Subtitle outline color as RRGGBB hex, optionally with #
     #[arg(long, default_value = "000000")]
     pub outline_color: String,
 
+    /// Outline thickness in pixels (default: libass default)
+    #[arg(long, value_name = "PX")]
+    pub outline: Option<f32>,
+
+    /// Shadow depth in pixels (default: libass default)
+    #[arg(long, value_name = "PX")]
+    pub shadow: Option<f32>,
+
+    /// Horizontal alignment of the text within the overlay band (default: center)
+    #[arg(long)]
+    pub align: Option<TextAlign>,
+
+    /// Wrap burned cues at spaces to fit the overlay band at the burned font size
+    #[arg(long)]
+    pub wrap: bool,
+
+    /// Font to measure --wrap with (TTF/OTF); without it widths are
+    /// estimated from the character count
+    #[arg(long, value_name = "FILE", requires = "wrap")]
+    pub font_file: Option<PathBuf>,
+
     /// Subtitle position
     #[arg(long, default_value = "bottom")]
     pub position: SubtitlePosition,
 
+    /// Additional subtitle file to burn in its own band, e.g. forced.srt:top
+    /// (repeatable; position defaults to bottom, stacked above the main track)
+    #[arg(long = "subtitles", value_name = "SRT[:POSITION]")]
+    pub extra_subtitles: Vec<String>,
+
+    /// Trim cues that run past the end of the video before burning
+    #[arg(long)]
+    pub clamp: bool,
+
+    /// Don't burn: estimate the output size and encode time from a 2-second
+    /// test encode with these settings
+    #[arg(long)]
+    pub dry_run: bool,
+
     /// Custom FFmpeg subtitle style string (overrides individual style options)
     #[arg(long)]
     pub style: Option<String>,
 
+    /// Named style preset (netflix, youtube, bold-yellow, or one from styles.toml
+    /// in the config directory); --style overrides individual fields
+    #[arg(long, value_name = "NAME")]
+    pub style_preset: Option<String>,
+
     /// Video codec (use 'copy' to preserve original)
     #[arg(long, default_value = "libx264")]
     pub video_codec: String,
 
-    /// Constant Rate Factor for quality (lower = better quality, 18-28 typical)
-    #[arg(long, default_value = "23")]
-    pub crf: u32,
+    /// Constant Rate Factor for quality (lower = better quality, 18-28 typical;
+    /// default: the encoder's, 23 for libx264)
+    #[arg(long)]
+    pub crf: Option<u32>,
+
+    /// Encoding preset (ultrafast, fast, medium, slow, veryslow; default: the encoder's)
+    #[arg(long)]
+    pub preset: Option<String>,
+
+    /// Target video bitrate for the final encode, e.g. 4M or 2500k
+    /// (ignored when a CRF is set)
+    #[arg(long, value_name = "RATE")]
+    pub video_bitrate: Option<String>,
 
-    /// Encoding preset (ultrafast, fast, medium, slow, veryslow)
-    #[arg(long, default_value = "medium")]
-    pub preset: String,
+    /// Bitrate of the subtitle overlay, e.g. 2M (default: scaled to its size)
+    #[arg(long, value_name = "RATE")]
+    pub overlay_bitrate: Option<String>,
 
-    /// Use overlay method (creates separate subtitle overlay video)
-    #[arg(long, default_value = "true")]
-    pub use_overlay: bool,
+    /// Use overlay method (creates separate subtitle overlay video);
+    /// `--use-overlay false` burns directly (default: burn_method from config, else overlay)
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub use_overlay: Option<bool>,
 
     /// Keep overlay file for reuse or customization
     #[arg(long)]
@@ -248,13 +578,289 @@ pub struct BurnArgs {
     /// Overlay width in pixels (default: video width)
     #[arg(long)]
     pub overlay_width: Option<u32>,
+
+    /// Font size as a fraction of the overlay height (default: 0.38)
+    #[arg(long, value_name = "RATIO")]
+    pub font_scale: Option<f64>,
+
+    /// Minimum font size when scaling to the overlay height (default: 24)
+    #[arg(long, value_name = "SIZE")]
+    pub min_font_size: Option<u32>,
+}
+
+#[derive(Parser, Debug)]
+pub struct OverlayArgs {
+    /// Video the overlay is made for (sets its width, length and frame rate)
+    #[arg(value_name = "VIDEO")]
+    pub video: PathBuf,
+
+    /// Input SRT subtitle file path
+    #[arg(value_name = "SUBTITLES")]
+    pub subtitles: PathBuf,
+
+    /// Output path: .webm or .mkv (VP9) or .mov (ProRes 4444), all with alpha
+    /// (default: <video>_overlay.webm)
+    #[arg(short = 'o', long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Overlay height in pixels (default: 200)
+    #[arg(long, value_name = "PX")]
+    pub overlay_height: Option<u32>,
+
+    /// Overlay width in pixels (default: video width)
+    #[arg(long, value_name = "PX")]
+    pub overlay_width: Option<u32>,
+
+    /// Font size as a fraction of the overlay height (default: 0.38)
+    #[arg(long, value_name = "RATIO")]
+    pub font_scale: Option<f64>,
+
+    /// Minimum font size when scaling to the overlay height (default: 24)
+    #[arg(long, value_name = "SIZE")]
+    pub min_font_size: Option<u32>,
+
+    /// Subtitle font color as RRGGBB hex, optionally with #
+    #[arg(long, default_value = "FFFFFF")]
+    pub font_color: String,
+
+    /// Subtitle outline color as RRGGBB hex, optionally with #
+    #[arg(long, default_value = "000000")]
+    pub outline_color: String,
+
+    /// Outline thickness in pixels (default: libass default)
+    #[arg(long, value_name = "PX")]
+    pub outline: Option<f32>,
+
+    /// Shadow depth in pixels (default: libass default)
+    #[arg(long, value_name = "PX")]
+    pub shadow: Option<f32>,
+
+    /// Horizontal alignment of the text within the overlay band (default: center)
+    #[arg(long)]
+    pub align: Option<TextAlign>,
+
+    /// Custom FFmpeg subtitle style string (overrides individual style options)
+    #[arg(long)]
+    pub style: Option<String>,
+
+    /// Named style preset (see `burn --style-preset`)
+    #[arg(long, value_name = "NAME")]
+    pub style_preset: Option<String>,
+
+    /// Overlay bitrate for .webm/.mkv, e.g. 2M (default: scaled to its size)
+    #[arg(long, value_name = "RATE")]
+    pub overlay_bitrate: Option<String>,
 }
 
 #[derive(Parser, Debug)]
 pub struct EditArgs {
-    /// SRT file to edit
+    /// SRT file to edit (a video file with --from-embedded)
     #[arg(value_name = "SRT_FILE")]
     pub input: PathBuf,
+
+    /// More subtitle files to open in tabs alongside the first
+    #[arg(value_name = "MORE_FILES")]
+    pub more: Vec<PathBuf>,
+
+    /// Treat the input as a video and edit its embedded subtitle track N
+    /// (0-based), saved next to it as <video>.srt
+    #[arg(long, value_name = "N")]
+    pub from_embedded: Option<usize>,
+
+    /// Read the input as this format instead of guessing from its extension
+    #[arg(long, value_name = "FORMAT")]
+    pub input_format: Option<SubtitleFormat>,
+
+    /// Audio to play while timing cues (p) when there is no video
+    #[arg(long, value_name = "FILE")]
+    pub audio: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct FixArgs {
+    /// SRT file to fix
+    #[arg(value_name = "SRT_FILE")]
+    pub input: PathBuf,
+
+    /// Output path (default: overwrite the input); a .vtt, .sbv or .lrc extension
+    /// converts
+    #[arg(short = 'o', long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Read the input as this format instead of guessing from its extension
+    #[arg(long, value_name = "FORMAT")]
+    pub input_format: Option<SubtitleFormat>,
+
+    /// Write this format instead of the one the output's extension names
+    #[arg(long, value_name = "FORMAT")]
+    pub output_format: Option<SubtitleFormat>,
+
+    /// Collapse adjacent cues that repeat the same text (Whisper hallucinations)
+    #[arg(long)]
+    pub dedupe: bool,
+
+    /// Largest gap in milliseconds between repeats that are still collapsed
+    #[arg(long, default_value = "500", value_name = "MS")]
+    pub dedupe_gap: u64,
+
+    /// Shorten cues that touch or overlap the next one
+    #[arg(long)]
+    pub enforce_gap: bool,
+
+    /// Extend cues across short silences up to the next cue
+    #[arg(long)]
+    pub fill_gaps: bool,
+
+    /// Capitalize the start of every sentence and the pronoun "I"
+    #[arg(long)]
+    pub sentence_case: bool,
+
+    /// Collapse repeated spaces and fix spacing around punctuation
+    #[arg(long)]
+    pub whitespace: bool,
+
+    /// With --whitespace, keep a space before ? ! : ; as French typography does
+    #[arg(long, requires = "whitespace")]
+    pub french_spacing: bool,
+
+    /// Trim cues that run past the end of this video and drop those after it
+    #[arg(long, value_name = "VIDEO")]
+    pub clamp_to_video: Option<PathBuf>,
+
+    /// Replace text in every cue, e.g. a name transcribed wrong (repeatable)
+    #[arg(long, value_name = "FROM=TO")]
+    pub replace: Vec<String>,
+
+    /// Make --replace match case instead of ignoring it
+    #[arg(long, requires = "replace")]
+    pub match_case: bool,
+
+    /// Wrap lines wider than this many pixels (the overlay width) at spaces
+    #[arg(long, value_name = "PX")]
+    pub wrap_width: Option<u32>,
+
+    /// Font to measure --wrap-width with (TTF/OTF); without it widths are
+    /// estimated from the character count
+    #[arg(long, value_name = "FILE", requires = "wrap_width")]
+    pub font_file: Option<PathBuf>,
+
+    /// Font size the subtitles are burned at (default: the size burn uses
+    /// for a 200px overlay)
+    #[arg(long, value_name = "PX", requires = "wrap_width")]
+    pub font_size: Option<u32>,
+
+    /// Longest silence in milliseconds that --fill-gaps closes
+    #[arg(long, default_value = "500", value_name = "MS")]
+    pub max_fill: u64,
+
+    /// Minimum gap in milliseconds kept between cues (default: 40)
+    #[arg(long, value_name = "MS")]
+    pub min_gap: Option<u64>,
+}
+
+#[derive(Parser, Debug)]
+pub struct LintArgs {
+    /// SRT file to check
+    #[arg(value_name = "SRT_FILE")]
+    pub input: PathBuf,
+
+    /// Read the input as this format instead of guessing from its extension
+    #[arg(long, value_name = "FORMAT")]
+    pub input_format: Option<SubtitleFormat>,
+
+    /// Highest reading speed in characters per second
+    #[arg(long, default_value = "20", value_name = "CPS")]
+    pub max_cps: f64,
+
+    /// Longest a cue may stay on screen, in milliseconds
+    #[arg(long, default_value = "7000", value_name = "MS")]
+    pub max_duration: u64,
+
+    /// Shortest a cue may stay on screen, in milliseconds
+    #[arg(long, default_value = "700", value_name = "MS")]
+    pub min_duration: u64,
+
+    /// Overlay width in pixels; warns about lines likely to be cut off
+    #[arg(long, value_name = "PX")]
+    pub overlay_width: Option<u32>,
+
+    /// Overlay height in pixels, used to work out the font size (default: 200)
+    #[arg(long, default_value = "200", value_name = "PX")]
+    pub overlay_height: u32,
+
+    /// Font size as a fraction of overlay height (default: 0.38)
+    #[arg(long, value_name = "SCALE")]
+    pub font_scale: Option<f64>,
+
+    /// Average glyph width as a fraction of the font size (default: 0.6)
+    #[arg(long, value_name = "FACTOR")]
+    pub char_width_factor: Option<f64>,
+
+    /// Video the subtitles belong to; warns about cues that run past its end
+    #[arg(long, value_name = "VIDEO")]
+    pub video: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct ShiftArgs {
+    /// SRT file to shift
+    #[arg(value_name = "SRT_FILE")]
+    pub input: PathBuf,
+
+    /// Offset to apply: 500ms, +1.5s, -2s or -00:00:02,000
+    #[arg(value_name = "OFFSET", allow_hyphen_values = true)]
+    pub offset: String,
+
+    /// Output path (default: overwrite the input)
+    #[arg(short = 'o', long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Read the input as this format instead of guessing from its extension
+    #[arg(long, value_name = "FORMAT")]
+    pub input_format: Option<SubtitleFormat>,
+
+    /// Write this format instead of the one the output's extension names
+    #[arg(long, value_name = "FORMAT")]
+    pub output_format: Option<SubtitleFormat>,
+}
+
+#[derive(Parser, Debug)]
+pub struct PreviewArgs {
+    /// Input video file path
+    #[arg(value_name = "VIDEO")]
+    pub video: PathBuf,
+
+    /// Input SRT subtitle file path
+    #[arg(value_name = "SUBTITLES")]
+    pub subtitles: PathBuf,
+
+    /// Overlay video height in pixels (default: 200)
+    #[arg(long)]
+    pub overlay_height: Option<u32>,
+
+    /// Overlay X position offset in pixels (default: 0, centered)
+    #[arg(long)]
+    pub overlay_x_offset: Option<i32>,
+
+    /// Overlay Y position offset in pixels (default: 0, at bottom)
+    #[arg(long)]
+    pub overlay_y_offset: Option<i32>,
+
+    /// Overlay width in pixels (default: video width)
+    #[arg(long)]
+    pub overlay_width: Option<u32>,
+
+    /// Font size as a fraction of the overlay height (default: 0.38)
+    #[arg(long, value_name = "RATIO")]
+    pub font_scale: Option<f64>,
+
+    /// Minimum font size when scaling to the overlay height (default: 24)
+    #[arg(long, value_name = "SIZE")]
+    pub min_font_size: Option<u32>,
+
+    /// Horizontal alignment of the text within the overlay band (default: center)
+    #[arg(long)]
+    pub align: Option<TextAlign>,
 }
 
 #[derive(Parser, Debug)]
@@ -272,7 +878,46 @@ pub struct ConfigArgs {
     pub path: bool,
 }
 
-#[derive(ValueEnum, Clone, Debug)]
+#[derive(Parser, Debug)]
+pub struct ModelsArgs {
+    /// Show each model, whether it is cached, and its size (default)
+    #[arg(long)]
+    pub list: bool,
+
+    /// Delete cached models to free disk space (asks first unless --yes)
+    #[arg(long)]
+    pub clean: bool,
+
+    /// Only remove this model when cleaning
+    #[arg(value_name = "MODEL", requires = "clean")]
+    pub model: Option<WhisperModel>,
+}
+
+#[derive(Parser, Debug)]
+pub struct BenchmarkArgs {
+    /// Audio or video file to transcribe (a short clip keeps this quick)
+    #[arg(value_name = "FILE")]
+    pub input: PathBuf,
+
+    /// Models to compare, comma-separated; the last one is the agreement reference
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "tiny,base,small",
+        value_name = "MODELS"
+    )]
+    pub models: Vec<WhisperModel>,
+
+    /// Language code (e.g., en, fr, es) or 'auto' for auto-detection
+    #[arg(short = 'l', long, default_value = "auto")]
+    pub language: String,
+
+    /// Base URL or local directory to fetch Whisper models from (default: Hugging Face)
+    #[arg(long, value_name = "URL_OR_DIR")]
+    pub model_url: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
 pub enum WhisperModel {
     /// Tiny model (~75MB, fastest, least accurate)
     Tiny,
@@ -287,7 +932,7 @@ pub enum WhisperModel {
 }
 
 impl WhisperModel {
-    pub fn filename(&self) -> &str {
+    pub fn filename(&self) -> &'static str {
         match self {
             WhisperModel::Tiny => "ggml-tiny.en.bin",
             WhisperModel::Base => "ggml-base.en.bin",
@@ -297,6 +942,23 @@ impl WhisperModel {
         }
     }
 
+    /// The same size's model for every language, used when an English-only
+    /// default is asked for another language
+    pub fn multilingual_filename(&self) -> &'static str {
+        match self {
+            WhisperModel::Tiny => "ggml-tiny.bin",
+            WhisperModel::Base => "ggml-base.bin",
+            WhisperModel::Small => "ggml-small.bin",
+            WhisperModel::Medium => "ggml-medium.bin",
+            WhisperModel::Large => "ggml-large.bin",
+        }
+    }
+
+    /// Whether `filename()` is an English-only (`.en`) model
+    pub fn is_english_only(&self) -> bool {
+        self.filename() != self.multilingual_filename()
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             WhisperModel::Tiny => "tiny",
@@ -325,7 +987,7 @@ impl AudioFormat {
     }
 }
 
-#[derive(ValueEnum, Clone, Debug)]
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
 pub enum SubtitlePosition {
     Top,
     Middle,
@@ -333,7 +995,7 @@ pub enum SubtitlePosition {
 }
 
 impl SubtitlePosition {
-    pub fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &'static str {
         match self {
             SubtitlePosition::Top => "top",
             SubtitlePosition::Middle => "middle",
@@ -341,3 +1003,22 @@ impl SubtitlePosition {
         }
     }
 }
+
+/// Where cue text sits across the overlay band
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum TextAlign {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+impl TextAlign {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TextAlign::Left => "left",
+            TextAlign::Center => "center",
+            TextAlign::Right => "right",
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::audio::extractor::AudioExtractor;
+use crate::cli::args::{BenchmarkArgs, WhisperModel};
+use crate::config::Config;
+use crate::subtitle::diff;
+use crate::subtitle::generator::SubtitleGenerator;
+use crate::subtitle::srt::Subtitle;
+use crate::utils::eta::format_duration;
+use crate::utils::temp::TempWorkspace;
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// One model's run over the sample
+struct BenchResult {
+    model: WhisperModel,
+    elapsed: Duration,
+    subtitles: Vec<Subtitle>,
+}
+
+pub async fn execute(args: BenchmarkArgs, config: &Config) -> Result<()> {
+    println!("╔════════════════════════════════════════════════════════════╗");
+    println!("║       AUTO-SUBS TUI - BENCHMARK MODE                       ║");
+    println!("╚════════════════════════════════════════════════════════════╝\n");
+
+    if !args.input.exists() {
+        anyhow::bail!("Input file not found: {}", args.input.display());
+    }
+
+    let mut models = args.models.clone();
+    models.dedup();
+
+    println!("📁 Input: {}", args.input.display());
+    println!(
+        "🎯 Models: {}",
+        models
+            .iter()
+            .map(|m| m.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    println!("🌍 Language: {}\n", args.language);
+
+    // Every model gets the same 16kHz mono WAV, whatever the input was
+    let workspace = TempWorkspace::new(config.paths.temp_dir.as_deref())?;
+    let audio_path = workspace.file("sample.wav");
+    println!("Extracting audio...");
+    let (tx, _rx) = mpsc::channel();
+    AudioExtractor::new().extract(&args.input, &audio_path, tx)?;
+    let audio_length = wav_duration(&audio_path)?;
+    println!("   Audio length: {}\n", format_duration(audio_length));
+
+    let model_source = args
+        .model_url
+        .clone()
+        .or(config.whisper.model_base_url.clone());
+
+    let mut results = Vec::new();
+    for model in &models {
+        let mut generator = SubtitleGenerator::new()
+            .with_model(*model)
+            .with_language(&args.language)
+            .with_min_gap(config.min_gap_ms())
+            .with_write_options(config.srt_write_options());
+        if let Some(source) = &model_source {
+            generator = generator.with_model_source(source);
+        }
+        if let Some(note) = generator.model_note() {
+            println!("⚠️  {}: {}", model.as_str(), note);
+        }
+
+        // Download outside the timed run so only transcription is measured
+        println!("⏱  {}: preparing model...", model.as_str());
+        let (tx, _rx) = mpsc::channel();
+        generator.ensure_model(&tx)?;
+
+        println!("⏱  {}: transcribing...", model.as_str());
+        let srt_path = workspace.file(&format!("{}.srt", model.as_str()));
+        let started = Instant::now();
+        let subtitles = generator
+            .generate(&audio_path, &srt_path, tx)
+            .with_context(|| format!("Transcription with {} failed", model.as_str()))?;
+        results.push(BenchResult {
+            model: *model,
+            elapsed: started.elapsed(),
+            subtitles,
+        });
+    }
+
+    print_table(&results, audio_length);
+    Ok(())
+}
+
+/// Print timings, plus how closely each model's cues match the last model's,
+/// which is usually the largest and taken as the reference
+fn print_table(results: &[BenchResult], audio_length: Duration) {
+    let Some(reference) = results.last() else {
+        return;
+    };
+
+    println!();
+    println!(
+        "  {:<8} {:>9} {:>8} {:>6} {:>10}",
+        "Model", "Time", "RTF", "Cues", "Agreement"
+    );
+    println!("  {}", "─".repeat(45));
+    for result in results {
+        let rtf = result.elapsed.as_secs_f64() / audio_length.as_secs_f64().max(0.001);
+        let agreement = if std::ptr::eq(result, reference) {
+            "ref".to_string()
+        } else {
+            let summary = diff::diff_subtitles(&reference.subtitles, &result.subtitles);
+            format!("{:.0}%", summary.agreement() * 100.0)
+        };
+        println!(
+            "  {:<8} {:>9} {:>7.2}x {:>6} {:>10}",
+            result.model.as_str(),
+            format_duration(result.elapsed),
+            rtf,
+            result.subtitles.len(),
+            agreement
+        );
+    }
+    println!();
+    println!(
+        "RTF is transcription time divided by audio length (below 1.0x is faster than real time)."
+    );
+    if results.len() > 1 {
+        println!(
+            "Agreement is the share of cues matching a cue of {} in time and text.",
+            reference.model.as_str()
+        );
+    }
+}
+
+fn wav_duration(path: &Path) -> Result<Duration> {
+    let reader = hound::WavReader::open(path).context("Failed to open extracted audio")?;
+    let spec = reader.spec();
+    Ok(Duration::from_secs_f64(
+        reader.duration() as f64 / spec.sample_rate as f64,
+    ))
+}
@@ -1,10 +1,26 @@
 use crate::app::ProgressMessage;
-use crate::cli::args::BurnArgs;
-use crate::subtitle::burner::SubtitleBurner;
-use anyhow::Result;
+use crate::cli::args::{BurnArgs, SubtitlePosition};
+use crate::config::Config;
+use crate::subtitle::burner::{self, SubtitleBurner, SubtitleTrack};
+use crate::subtitle::format;
+use crate::subtitle::split::{self, SplitMode};
+use crate::subtitle::srt::{self, SrtWriteOptions, Subtitle};
+use crate::subtitle::style;
+use crate::subtitle::timing;
+use crate::subtitle::wrap::WrapFont;
+use crate::utils::eta;
+use crate::utils::overwrite::OverwritePolicy;
+use crate::utils::temp::TempWorkspace;
+use crate::utils::template::{self, TemplateValues};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::path::Path;
 use std::sync::mpsc;
+use std::time::Duration;
 
-pub async fn execute(args: BurnArgs) -> Result<()> {
+const MB: f64 = 1024.0 * 1024.0;
+
+pub async fn execute(args: BurnArgs, config: &Config, overwrite: OverwritePolicy) -> Result<()> {
     println!("╔════════════════════════════════════════════════════════════╗");
     println!("║          AUTO-SUBS TUI - BURN MODE                         ║");
     println!("╚════════════════════════════════════════════════════════════╝\n");
@@ -17,24 +33,97 @@ pub async fn execute(args: BurnArgs) -> Result<()> {
         anyhow::bail!("Subtitle file not found: {}", args.subtitles.display());
     }
 
+    // The main subtitles plus any extra --subtitles tracks, each in its own band
+    let mut tracks = vec![SubtitleTrack::new(&args.subtitles, args.position)];
+    for spec in &args.extra_subtitles {
+        let track = parse_track(spec);
+        if !track.srt_path.exists() {
+            anyhow::bail!("Subtitle file not found: {}", track.srt_path.display());
+        }
+        tracks.push(track);
+    }
+
+    let use_overlay = args.use_overlay.unwrap_or(config.use_overlay());
+    let style = style::build_style(args.style_preset.as_deref(), args.style.as_deref())?;
+    let font_color = style::parse_hex_color(&args.font_color).context("Invalid --font-color")?;
+    let outline_color =
+        style::parse_hex_color(&args.outline_color).context("Invalid --outline-color")?;
+    let video_bitrate = args
+        .video_bitrate
+        .as_deref()
+        .map(burner::parse_bitrate)
+        .transpose()
+        .context("Invalid --video-bitrate")?;
+    let overlay_bitrate = args
+        .overlay_bitrate
+        .as_deref()
+        .map(burner::parse_bitrate)
+        .transpose()
+        .context("Invalid --overlay-bitrate")?;
+
+    let split_mode = SplitMode::from_args(args.split_minutes, args.split_chapters)?;
+
     // Determine output path
-    let output_path = args.output.clone().unwrap_or_else(|| {
-        args.video.with_file_name(format!(
-            "{}_subtitled.{}",
-            args.video.file_stem().unwrap().to_string_lossy(),
-            args.video.extension().unwrap().to_string_lossy()
-        ))
-    });
+    let output_path = template::resolve(
+        args.output.clone(),
+        args.output_template
+            .as_deref()
+            .or(config.paths.output_template.as_deref()),
+        &args.video,
+        &SubtitleBurner::output_extension(&args.video),
+        &TemplateValues::default(),
+        || SubtitleBurner::default_output_path(&args.video),
+    )?;
+
+    // Check both files we'd write before spending time on encoding
+    if !args.dry_run {
+        overwrite.check(&output_path)?;
+    }
+    if !args.dry_run && split_mode.is_some() {
+        overwrite.check(&split::part_path(&output_path, 1))?;
+    }
+    if !args.dry_run && use_overlay && args.keep_overlay {
+        for i in 0..tracks.len() {
+            overwrite.check(&SubtitleBurner::kept_overlay_path_for_track(
+                &output_path,
+                i,
+            ))?;
+        }
+    }
 
     println!("📹 Input video: {}", args.video.display());
-    println!("📄 Input subtitles: {}", args.subtitles.display());
+    for track in &tracks {
+        println!(
+            "📄 Input subtitles: {} ({})",
+            track.srt_path.display(),
+            track.position.as_str()
+        );
+    }
     println!("🎬 Output video: {}", output_path.display());
+    // The output container follows the --output extension
+    let input_ext = args.video.extension().map(|e| e.to_ascii_lowercase());
+    let output_ext = output_path.extension().map(|e| e.to_ascii_lowercase());
+    if input_ext != output_ext {
+        println!(
+            "🔄 Container: {} → {}",
+            input_ext.unwrap_or_default().to_string_lossy(),
+            output_ext.unwrap_or_default().to_string_lossy()
+        );
+    }
     println!("⚙️  Font size: {}", args.font_size);
     println!("⚙️  Font color: #{}", args.font_color);
     println!("⚙️  Outline color: #{}", args.outline_color);
-    println!("⚙️  Position: {}", args.position.as_str());
+    if let Some(outline) = args.outline {
+        println!("⚙️  Outline: {}px", outline);
+    }
+    if let Some(shadow) = args.shadow {
+        println!("⚙️  Shadow: {}px", shadow);
+    }
+    if let Some(preset) = &args.style_preset {
+        println!("🎨 Style preset: {}", preset);
+    }
 
-    if args.use_overlay {
+    if use_overlay {
         println!("🎨 Method: Overlay (separate subtitle layer)");
         if let Some(height) = args.overlay_height {
             println!("⚙️  Overlay height: {}px", height);
@@ -48,27 +137,83 @@ pub async fn execute(args: BurnArgs) -> Result<()> {
         if let Some(y_offset) = args.overlay_y_offset {
             println!("⚙️  Overlay Y offset: {}px", y_offset);
         }
+        if let Some(scale) = args.font_scale {
+            println!("⚙️  Font scale: {}", scale);
+        }
         if args.keep_overlay {
             println!("💾 Keeping overlay file for customization");
         }
     } else {
         println!("🎨 Method: Direct burn");
+        if args.overlay_width.is_some()
+            || args.overlay_x_offset.is_some()
+            || args.overlay_y_offset.is_some()
+            || args.keep_overlay
+        {
+            println!("⚠️  Overlay width, offsets and --keep-overlay are ignored by direct burn");
+        }
     }
 
     println!("⚙️  Video codec: {}", args.video_codec);
-    if args.video_codec != "copy" {
-        println!("⚙️  CRF: {}", args.crf);
-        println!("⚙️  Preset: {}\n", args.preset);
-    } else {
-        println!();
+    match (args.crf, &args.video_bitrate) {
+        (Some(crf), bitrate) => {
+            println!("⚙️  CRF: {}", crf);
+            if bitrate.is_some() {
+                println!("⚠️  --video-bitrate is ignored when --crf is set");
+            }
+        }
+        (None, Some(bitrate)) => println!("⚙️  Video bitrate: {}", bitrate),
+        (None, None) => {}
+    }
+    if let Some(preset) = &args.preset {
+        println!("⚙️  Preset: {}", preset);
+    }
+    if use_overlay {
+        if let Some(bitrate) = &args.overlay_bitrate {
+            println!("⚙️  Overlay bitrate: {}", bitrate);
+        }
     }
 
-    // Burn subtitles with overlay method
-    println!("Burning subtitles into video...");
-    let (tx, rx) = mpsc::channel();
+    // Cues past the end of the video would be cut off without a trace; trimmed
+    // copies go to a temporary directory so the input files are left alone
+    let workspace = if args.clamp {
+        Some(TempWorkspace::new(config.paths.temp_dir.as_deref())?)
+    } else {
+        None
+    };
+    if let Ok(media_end) = SubtitleBurner::new().get_video_duration_ms(&args.video) {
+        for (i, track) in tracks.iter_mut().enumerate() {
+            let mut subtitles = format::load_subtitles(&track.srt_path, None)?;
+            let past_end = timing::count_past_end(&subtitles, media_end);
+            if past_end == 0 {
+                continue;
+            }
+            match &workspace {
+                Some(workspace) => {
+                    timing::clamp_to_media_end(&mut subtitles, media_end);
+                    let clamped = workspace.file(&format!("clamped_{}.srt", i));
+                    srt::save_srt(&clamped, &subtitles, &SrtWriteOptions::default())?;
+                    println!(
+                        "⏹️  Trimmed {} cues in {} to the end of the video ({})",
+                        past_end,
+                        track.srt_path.display(),
+                        Subtitle::format_time(media_end)
+                    );
+                    track.srt_path = clamped;
+                }
+                None => println!(
+                    "⚠️  {} cues in {} run past the end of the video ({}) and will be cut off; use --clamp to trim them",
+                    past_end,
+                    track.srt_path.display(),
+                    Subtitle::format_time(media_end)
+                ),
+            }
+        }
+    }
+    println!();
 
     let mut burner = SubtitleBurner::new()
-        .with_overlay(args.use_overlay)
+        .with_overlay(use_overlay)
         .keep_overlay_file(args.keep_overlay);
 
     if let Some(height) = args.overlay_height {
@@ -83,17 +228,94 @@ pub async fn execute(args: BurnArgs) -> Result<()> {
     if let Some(y_offset) = args.overlay_y_offset {
         burner = burner.with_overlay_y_offset(y_offset);
     }
+    if let Some(scale) = args.font_scale {
+        burner = burner.with_font_scale(scale);
+    }
+    if let Some(min_size) = args.min_font_size {
+        burner = burner.with_min_font_size(min_size);
+    }
+    burner = burner
+        .with_font_color(font_color)
+        .with_outline_color(outline_color);
+    if let Some(outline) = args.outline {
+        burner = burner.with_outline(outline);
+    }
+    if let Some(shadow) = args.shadow {
+        burner = burner.with_shadow(shadow);
+    }
+    if let Some(align) = args.align {
+        burner = burner.with_text_align(align);
+    }
+    if args.wrap {
+        burner = burner.with_wrap(WrapFont::load(
+            args.font_file.as_deref(),
+            config.char_width_factor(),
+        )?);
+    }
+    burner = burner.with_style(&style);
+    if let Some(crf) = args.crf {
+        burner = burner.with_crf(crf);
+    }
+    if let Some(preset) = &args.preset {
+        burner = burner.with_preset(preset);
+    }
+    if let Some(bitrate) = video_bitrate {
+        burner = burner.with_video_bitrate(bitrate);
+    }
+    if let Some(bitrate) = overlay_bitrate {
+        burner = burner.with_overlay_bitrate(bitrate);
+    }
 
+    if args.dry_run {
+        println!(
+            "🧮 Dry run: estimating from a {}s test encode (the output isn't written)...",
+            burner::ESTIMATE_SAMPLE_MS / 1000
+        );
+        let estimate = burner.estimate(&args.video, &tracks, &output_path)?;
+        println!(
+            "   Input: {}x{}, {}",
+            estimate.width,
+            estimate.height,
+            Subtitle::format_time(estimate.duration_ms)
+        );
+        let output_mb = estimate.output_bytes as f64 / MB;
+        println!("   📦 {}: ~{:.1} MB", output_path.display(), output_mb);
+        let mut total_mb = output_mb;
+        if split_mode.is_some() {
+            // The parts are stream copies of the output, so they add its size again
+            println!(
+                "   📦 {} ...: ~{:.1} MB in all",
+                split::part_path(&output_path, 1).display(),
+                output_mb
+            );
+            total_mb += output_mb;
+        }
+        println!("   💽 Total: ~{:.1} MB", total_mb);
+        println!(
+            "   ⏱️  Encode time: ~{}",
+            eta::format_duration(Duration::from_secs_f64(estimate.encode_secs))
+        );
+        return Ok(());
+    }
+
+    // Burn subtitles with overlay method
+    println!("Burning subtitles into video...");
+    let (tx, rx) = mpsc::channel();
     let video_clone = args.video.clone();
-    let srt_clone = args.subtitles.clone();
+    let tracks_clone = tracks.clone();
     let output_clone = output_path.clone();
     std::thread::spawn(move || {
-        let _ = burner.burn(&video_clone, &srt_clone, &output_clone, tx);
+        if let Err(e) = burner.burn_tracks(&video_clone, &tracks_clone, &output_clone, tx.clone()) {
+            let _ = tx.send(ProgressMessage::Error(e.to_string()));
+        }
     });
 
     while let Ok(msg) = rx.recv() {
         match msg {
             ProgressMessage::Progress(p, m) => println!("  [{:3.0}%] {}", p * 100.0, m),
+            ProgressMessage::Segment(_)
+            | ProgressMessage::Transcript(_)
+            | ProgressMessage::Language(_) => {}
             ProgressMessage::Complete => break,
             ProgressMessage::Error(e) => {
                 anyhow::bail!("Subtitle burning failed: {}", e);
@@ -104,13 +326,39 @@ pub async fn execute(args: BurnArgs) -> Result<()> {
     println!("\n✅ Subtitle burning complete!");
     println!("   Output: {}", output_path.display());
 
-    if args.keep_overlay {
-        let overlay_path = output_path.with_file_name(format!(
-            "{}_overlay.mp4",
-            output_path.file_stem().unwrap().to_string_lossy()
-        ));
-        println!("   Overlay: {}", overlay_path.display());
+    if use_overlay && args.keep_overlay {
+        for i in 0..tracks.len() {
+            let overlay_path = SubtitleBurner::kept_overlay_path_for_track(&output_path, i);
+            println!("   Overlay: {}", overlay_path.display());
+        }
+    }
+
+    if let Some(mode) = split_mode {
+        // Cut times come from the main track; the others are burned in anyway
+        let subtitles = format::load_subtitles(&tracks[0].srt_path, None)?;
+        println!("\n✂️  Splitting the output...");
+        let parts = split::split(&output_path, &subtitles, mode, &config.srt_write_options())?;
+        for part in &parts {
+            println!(
+                "   {} ({} - {}, {} cues)",
+                part.video.display(),
+                Subtitle::format_time(part.start_ms),
+                Subtitle::format_time(part.end_ms),
+                part.cues
+            );
+        }
     }
 
     Ok(())
 }
+
+/// Parse a `--subtitles` value: `file.srt` or `file.srt:<top|middle|bottom>`.
+/// A suffix that isn't a position is treated as part of the path.
+fn parse_track(spec: &str) -> SubtitleTrack {
+    if let Some((path, position)) = spec.rsplit_once(':') {
+        if let Ok(position) = SubtitlePosition::from_str(position, true) {
+            return SubtitleTrack::new(Path::new(path), position);
+        }
+    }
+    SubtitleTrack::new(Path::new(spec), SubtitlePosition::Bottom)
+}
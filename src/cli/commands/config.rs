@@ -1,9 +1,10 @@
 use crate::cli::args::ConfigArgs;
+use crate::config::Config;
 use anyhow::Result;
 use std::path::PathBuf;
 
 pub async fn execute(args: ConfigArgs) -> Result<()> {
-    let config_path = get_config_path();
+    let config_path = Config::default_path();
 
     if args.path {
         println!("Configuration file path:");
@@ -41,15 +42,6 @@ pub async fn execute(args: ConfigArgs) -> Result<()> {
     Ok(())
 }
 
-fn get_config_path() -> PathBuf {
-    if let Some(config_dir) = dirs::config_dir() {
-        let app_config_dir = config_dir.join("auto-subs-tui");
-        app_config_dir.join("config.toml")
-    } else {
-        PathBuf::from("config.toml")
-    }
-}
-
 fn create_default_config(path: &PathBuf) -> Result<()> {
     // Create parent directory if it doesn't exist
     if let Some(parent) = path.parent() {
@@ -66,6 +58,9 @@ model = "base"
 language = "en"
 # Model storage directory
 model_dir = "~/.cache/auto-subs-tui/models"
+# Where models are downloaded from: a base URL or a local directory holding
+# the ggml-*.bin files under their original names
+# model_base_url = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main"
 
 [audio]
 # Audio sample rate in Hz
@@ -86,8 +81,29 @@ font_color = "FFFFFF"
 outline_color = "000000"
 # Default position (top, middle, bottom)
 position = "bottom"
+# Minimum gap in milliseconds kept between cues by automatic timing changes
+# (about one frame at 25fps); keeps players from rendering cues back to back
+min_gap_ms = 40
+# Extend generated cues shorter than this many milliseconds (unset = off)
+# min_duration_ms = 700
+# Split generated cues longer than this many milliseconds at sentence
+# boundaries (unset = off)
+# max_duration_ms = 7000
+# Number written for the first cue in saved SRT files (some tools expect 0)
+index_base = 1
+# Zero-pad cue indices to this many digits (0 = no padding)
+index_width = 0
+# Write cue index lines at all (false gives VTT-like cues)
+write_index = true
+# Average glyph width as a fraction of the font size; used to warn about lines
+# too wide for the overlay. Raise it for wide fonts, lower it for condensed ones
+char_width_factor = 0.6
 
 [video]
+# How subtitles are burned: "overlay" renders a compact subtitle layer and merges
+# it (honours overlay size/offsets), "direct" is faster but ignores overlay geometry.
+# Override per run with --use-overlay true/false
+burn_method = "overlay"
 # Video codec (libx264, libx265, vp9, or "copy" to preserve original)
 codec = "libx264"
 # Constant Rate Factor for quality (18-28, lower = better quality)
@@ -95,15 +111,42 @@ crf = 23
 # Encoding preset (ultrafast, superfast, veryfast, faster, fast, medium, slow, slower, veryslow)
 preset = "medium"
 
+[editor]
+# Autosave unsaved edits this often, in seconds (0 = never). Autosaves live
+# under temp_dir and are offered for restore when the SRT is opened again
+autosave_secs = 30
+# Also autosave once this many edits are unsaved (0 = only on the timer)
+autosave_edits = 20
+# How far the time nudge and move keys shift a cue, in ms, and with Shift held
+nudge_ms = 100
+large_nudge_ms = 1000
+
+[keys]
+# Rebind editor actions: a key ("x"), a name ("Delete", "Space", "PageDown",
+# "Shift-Tab", "F5") or a list of them. A rebound action loses its default
+# keys, and the key is taken away from any action that had it by default
+# delete = "x"
+# add_cue = ["a", "Insert"]
+
 [paths]
 # Default output directory (use "." for current directory)
 output_dir = "."
-# Temporary files directory
+# Directory for intermediate files (audio, overlays); a private subdirectory
+# is created per run and removed afterwards
 temp_dir = "/tmp"
+# Directory for the live preview's mpv control socket; each running instance
+# uses its own auto-subs-preview-<pid>.sock in it (default: system temp dir)
+# socket_dir = "/run/user/1000"
+# Name outputs from a template when no path is given (see --output-template),
+# e.g. burned videos into a subfolder and transcripts tagged with the model
+# output_template = "{dir}/subbed/{stem}.{model}.{ext}"
 
 [behavior]
 # Keep intermediate files (audio, SRT) after processing
 keep_files = false
+# Keep the extracted audio next to the video and reuse it on later runs
+# while the video is unchanged (handy when comparing models)
+keep_audio = false
 # Overwrite output files without asking
 auto_overwrite = false
 "#;
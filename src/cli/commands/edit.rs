@@ -1,40 +1,60 @@
 use crate::app::App;
 use crate::cli::args::EditArgs;
+use crate::config::Config;
+use crate::subtitle::embedded;
+use crate::tui;
+use crate::utils::overwrite::OverwritePolicy;
 use anyhow::Result;
-use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
-use ratatui::prelude::*;
-use std::io;
 
-pub async fn execute(args: EditArgs) -> Result<()> {
+pub async fn execute(
+    args: EditArgs,
+    config: Config,
+    overwrite: OverwritePolicy,
+    use_altscreen: bool,
+) -> Result<()> {
     // Validate input file
     if !args.input.exists() {
-        anyhow::bail!("SRT file not found: {}", args.input.display());
+        anyhow::bail!("File not found: {}", args.input.display());
+    }
+    if let Some(audio) = &args.audio {
+        if !audio.exists() {
+            anyhow::bail!("Audio file not found: {}", audio.display());
+        }
     }
 
-    println!("📄 Opening SRT file: {}", args.input.display());
-    println!("Launching TUI editor...\n");
+    // With --from-embedded the input is a video whose subtitle track is
+    // converted to an SRT next to it, and the video stays available for burning
+    let (video_path, srt_path) = match args.from_embedded {
+        Some(track) => {
+            let srt_path = args.input.with_extension("srt");
+            overwrite.check(&srt_path)?;
+            println!(
+                "🎞️  Extracting embedded subtitle track {} from {}",
+                track,
+                args.input.display()
+            );
+            embedded::extract_track(&args.input, track, &srt_path)?;
+            (Some(args.input.clone()), srt_path)
+        }
+        None => (None, args.input.clone()),
+    };
 
-    // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    println!("📄 Opening subtitle file: {}", srt_path.display());
+    println!("Launching TUI editor...\n");
 
     // Create app with pre-loaded SRT file
-    let mut app = App::new();
-    app.load_srt_file(&args.input)?;
-
-    // Run the application
-    let result = app.run(&mut terminal).await;
-
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    let mut app = App::new()
+        .with_config(config)
+        .with_overwrite_policy(overwrite)
+        .with_subtitle_format(args.input_format);
+    app.load_srt_file(&srt_path)?;
+    for path in &args.more {
+        println!("📄 Opening in a new tab: {}", path.display());
+        app.open_document(path)?;
+    }
+    app.switch_document(0);
+    app.set_video(video_path);
+    app.audio_path = args.audio;
 
-    result
+    tui::run_app(&mut app, use_altscreen).await
 }
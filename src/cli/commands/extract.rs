@@ -1,26 +1,44 @@
 use crate::app::ProgressMessage;
 use crate::audio::extractor::AudioExtractor;
 use crate::cli::args::ExtractArgs;
+use crate::config::Config;
+use crate::utils::temp;
+use crate::utils::template::{self, TemplateValues};
 use anyhow::Result;
 use std::sync::mpsc;
 
-pub async fn execute(args: ExtractArgs) -> Result<()> {
+pub async fn execute(args: ExtractArgs, config: &Config) -> Result<()> {
     println!("╔════════════════════════════════════════════════════════════╗");
     println!("║         AUTO-SUBS TUI - EXTRACT MODE                       ║");
     println!("╚════════════════════════════════════════════════════════════╝\n");
 
-    // Validate input file
-    if !args.input.exists() {
+    // Validate input file (`-` is read from stdin by the extractor)
+    let from_stdin = temp::is_stdin(&args.input);
+    if from_stdin {
+        if args.output.is_none() {
+            anyhow::bail!("Reading from stdin requires --output");
+        }
+    } else if !args.input.exists() {
         anyhow::bail!("Video file not found: {}", args.input.display());
     }
 
     // Determine output path
-    let output_path = args
-        .output
-        .clone()
-        .unwrap_or_else(|| args.input.with_extension(args.format.as_str()));
+    let output_path = template::resolve(
+        args.output.clone(),
+        args.output_template
+            .as_deref()
+            .or(config.paths.output_template.as_deref()),
+        &args.input,
+        args.format.as_str(),
+        &TemplateValues::default(),
+        || args.input.with_extension(args.format.as_str()),
+    )?;
 
-    println!("📹 Input video: {}", args.input.display());
+    if from_stdin {
+        println!("📹 Input video: <stdin>");
+    } else {
+        println!("📹 Input video: {}", args.input.display());
+    }
     println!("🎵 Output audio: {}", output_path.display());
     println!("⚙️  Sample rate: {}Hz", args.sample_rate);
     println!("⚙️  Channels: {}", args.channels);
@@ -34,12 +52,17 @@ pub async fn execute(args: ExtractArgs) -> Result<()> {
     let input_clone = args.input.clone();
     let output_clone = output_path.clone();
     std::thread::spawn(move || {
-        let _ = extractor.extract(&input_clone, &output_clone, tx);
+        if let Err(e) = extractor.extract(&input_clone, &output_clone, tx.clone()) {
+            let _ = tx.send(ProgressMessage::Error(e.to_string()));
+        }
     });
 
     while let Ok(msg) = rx.recv() {
         match msg {
             ProgressMessage::Progress(p, m) => println!("  [{:3.0}%] {}", p * 100.0, m),
+            ProgressMessage::Segment(_)
+            | ProgressMessage::Transcript(_)
+            | ProgressMessage::Language(_) => {}
             ProgressMessage::Complete => break,
             ProgressMessage::Error(e) => {
                 anyhow::bail!("Audio extraction failed: {}", e);
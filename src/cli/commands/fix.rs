@@ -0,0 +1,125 @@
+use crate::cli::args::FixArgs;
+use crate::config::Config;
+use crate::subtitle::ass::AssStyle;
+use crate::subtitle::burner::SubtitleBurner;
+use crate::subtitle::fix;
+use crate::subtitle::format;
+use crate::subtitle::srt::Subtitle;
+use crate::subtitle::timing;
+use crate::subtitle::wrap::WrapFont;
+use anyhow::{Context, Result};
+
+pub async fn execute(args: FixArgs, config: &Config) -> Result<()> {
+    // Validate input file
+    if !args.input.exists() {
+        anyhow::bail!("SRT file not found: {}", args.input.display());
+    }
+
+    let output_path = args.output.clone().unwrap_or_else(|| args.input.clone());
+    // Written back over the input, the file keeps the format it was read as
+    let output_format = match &args.output {
+        Some(_) => args.output_format,
+        None => args.output_format.or(args.input_format),
+    };
+
+    // With no rule selected, run the default rules
+    let run_all = !args.dedupe
+        && !args.enforce_gap
+        && !args.fill_gaps
+        && !args.sentence_case
+        && !args.whitespace
+        && args.replace.is_empty()
+        && args.wrap_width.is_none()
+        && args.clamp_to_video.is_none();
+    let min_gap = args.min_gap.unwrap_or(config.min_gap_ms());
+
+    let mut subtitles = format::load_subtitles(&args.input, args.input_format)?;
+    println!(
+        "📄 Input: {} ({} cues)",
+        args.input.display(),
+        subtitles.len()
+    );
+
+    for rule in &args.replace {
+        let (from, to) = rule
+            .split_once('=')
+            .with_context(|| format!("Invalid --replace '{}': expected FROM=TO", rule))?;
+        let replaced = fix::replace_all(&mut subtitles, from, to, args.match_case);
+        println!("   🔤 Replaced {} occurrences of \"{}\"", replaced, from);
+    }
+
+    if args.dedupe || run_all {
+        let removed = fix::dedupe_consecutive(&mut subtitles, args.dedupe_gap);
+        println!("   🔁 Removed {} repeated cues", removed);
+    }
+
+    if args.whitespace {
+        let changed = fix::normalize_whitespace_all(&mut subtitles, args.french_spacing);
+        println!("   🧹 Fixed spacing in {} cues", changed);
+    }
+
+    if args.sentence_case {
+        let changed = fix::sentence_case_all(&mut subtitles);
+        println!("   🔠 Fixed capitalization in {} cues", changed);
+    }
+
+    if let Some(width) = args.wrap_width {
+        let font = WrapFont::load(args.font_file.as_deref(), config.char_width_factor())?;
+        let size = args
+            .font_size
+            .unwrap_or_else(|| SubtitleBurner::new().font_size_for_height(200));
+        let changed = font.wrap_all(&mut subtitles, size as f64, width as f64);
+        println!(
+            "   ↩️  Wrapped {} cues to {}px at font size {}{}",
+            changed,
+            width,
+            size,
+            if font.metrics.is_some() {
+                ""
+            } else {
+                " (estimated)"
+            }
+        );
+    }
+
+    if args.fill_gaps {
+        let extended = timing::fill_gaps(&mut subtitles, args.max_fill, min_gap);
+        println!("   ↔️  Extended {} cues across short silences", extended);
+    }
+
+    if let Some(video) = &args.clamp_to_video {
+        let media_end = SubtitleBurner::new()
+            .get_video_duration_ms(video)
+            .with_context(|| format!("Failed to probe {}", video.display()))?;
+        let changed = timing::clamp_to_media_end(&mut subtitles, media_end);
+        println!(
+            "   ⏹️  Trimmed {} cues to the end of the video ({})",
+            changed,
+            Subtitle::format_time(media_end)
+        );
+    }
+
+    // Runs last so no earlier rule leaves cues touching
+    if args.enforce_gap || args.fill_gaps || run_all {
+        let adjusted = timing::enforce_min_gap(&mut subtitles, min_gap);
+        println!(
+            "   ✂️  Shortened {} cues to keep a {}ms gap",
+            adjusted, min_gap
+        );
+    }
+
+    format::save_subtitles(
+        &output_path,
+        &subtitles,
+        output_format,
+        &config.srt_write_options(),
+        &AssStyle::default(),
+    )?;
+    println!(
+        "✅ Saved {} cues to {}",
+        subtitles.len(),
+        output_path.display()
+    );
+
+    Ok(())
+}
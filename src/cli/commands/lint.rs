@@ -0,0 +1,78 @@
+use crate::cli::args::LintArgs;
+use crate::config::Config;
+use crate::subtitle::burner::SubtitleBurner;
+use crate::subtitle::format;
+use crate::subtitle::lint::{self, LintRules, Severity};
+use crate::subtitle::timing;
+use anyhow::{Context, Result};
+
+pub async fn execute(args: LintArgs, config: &Config) -> Result<()> {
+    // Validate input file
+    if !args.input.exists() {
+        anyhow::bail!("SRT file not found: {}", args.input.display());
+    }
+
+    let mut burner = SubtitleBurner::new();
+    if let Some(scale) = args.font_scale {
+        burner = burner.with_font_scale(scale);
+    }
+
+    let media_end_ms = match &args.video {
+        Some(video) => Some(
+            burner
+                .get_video_duration_ms(video)
+                .with_context(|| format!("Failed to probe {}", video.display()))?,
+        ),
+        None => None,
+    };
+
+    let rules = LintRules {
+        max_cps: args.max_cps,
+        max_duration_ms: args.max_duration,
+        min_duration_ms: args.min_duration,
+        overlay_width: args.overlay_width,
+        font_size: burner.font_size_for_height(args.overlay_height),
+        char_width_factor: args.char_width_factor.unwrap_or(config.char_width_factor()),
+        media_end_ms,
+    };
+
+    let subtitles = format::load_subtitles(&args.input, args.input_format)?;
+    let issues = lint::lint(&subtitles, &rules);
+
+    for issue in &issues {
+        println!(
+            "{}: cue {}: {}",
+            issue.severity.as_str(),
+            subtitles[issue.position].index,
+            issue.message
+        );
+    }
+
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count();
+    println!(
+        "{} cues checked, {} errors, {} warnings",
+        subtitles.len(),
+        errors,
+        issues.len() - errors
+    );
+
+    if let (Some(video), Some(media_end)) = (&args.video, media_end_ms) {
+        if timing::count_past_end(&subtitles, media_end) > 0 {
+            println!(
+                "💡 Trim them with: auto-subs-tui fix {} --clamp-to-video {}",
+                args.input.display(),
+                video.display()
+            );
+        }
+    }
+
+    // A non-zero exit lets scripts stop on broken timing
+    if errors > 0 {
+        anyhow::bail!("{} lint errors in {}", errors, args.input.display());
+    }
+
+    Ok(())
+}
@@ -1,6 +1,13 @@
+pub mod benchmark;
 pub mod burn;
 pub mod config;
 pub mod edit;
 pub mod extract;
+pub mod fix;
+pub mod lint;
+pub mod models;
+pub mod overlay;
+pub mod preview;
 pub mod process;
+pub mod shift;
 pub mod transcribe;
@@ -0,0 +1,144 @@
+use crate::cli::args::{ModelsArgs, WhisperModel};
+use crate::subtitle::generator::SubtitleGenerator;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+const MB: f64 = 1024.0 * 1024.0;
+
+pub async fn execute(args: ModelsArgs, assume_yes: bool, interactive: bool) -> Result<()> {
+    let cache_dir = SubtitleGenerator::model_cache_dir();
+
+    if args.clean {
+        return clean(&cache_dir, args.model, assume_yes, interactive);
+    }
+
+    list(&cache_dir);
+    Ok(())
+}
+
+/// Print every known model with its cache status and size
+fn list(cache_dir: &Path) {
+    println!("Model cache: {}", cache_dir.display());
+    println!();
+
+    let mut total = 0;
+    for model in WhisperModel::value_variants() {
+        let path = cache_dir.join(model.filename());
+        match file_size(&path) {
+            Some(size) => {
+                total += size;
+                println!(
+                    "  ✅ {:<8} {:>9.1} MB  {}",
+                    model.as_str(),
+                    size as f64 / MB,
+                    path.display()
+                );
+            }
+            None => println!(
+                "  ·  {:<8} {:>12}  {}",
+                model.as_str(),
+                "not cached",
+                path.display()
+            ),
+        }
+        // The multilingual variant, fetched when another language was asked for
+        if model.is_english_only() {
+            let path = cache_dir.join(model.multilingual_filename());
+            if let Some(size) = file_size(&path) {
+                total += size;
+                println!(
+                    "  ✅ {:<8} {:>9.1} MB  {}",
+                    model.as_str(),
+                    size as f64 / MB,
+                    path.display()
+                );
+            }
+        }
+    }
+
+    println!();
+    println!("Total: {:.1} MB", total as f64 / MB);
+}
+
+/// Remove one cached model, or all of them, after confirming
+fn clean(
+    cache_dir: &Path,
+    model: Option<WhisperModel>,
+    assume_yes: bool,
+    interactive: bool,
+) -> Result<()> {
+    let targets: Vec<(PathBuf, u64)> = WhisperModel::value_variants()
+        .iter()
+        .filter(|m| model.is_none_or(|wanted| wanted == **m))
+        .flat_map(|m| {
+            let mut files = vec![m.filename()];
+            if m.is_english_only() {
+                files.push(m.multilingual_filename());
+            }
+            files.into_iter().filter_map(|file| {
+                let path = cache_dir.join(file);
+                file_size(&path).map(|size| (path, size))
+            })
+        })
+        .collect();
+
+    if targets.is_empty() {
+        match model {
+            Some(m) => println!("Model '{}' is not cached.", m.as_str()),
+            None => println!("No cached models in {}", cache_dir.display()),
+        }
+        return Ok(());
+    }
+
+    let total: u64 = targets.iter().map(|(_, size)| size).sum();
+    let names: Vec<String> = targets.iter().map(|(path, _)| file_label(path)).collect();
+    println!(
+        "Removing {} ({:.1} MB) from {}",
+        names.join(", "),
+        total as f64 / MB,
+        cache_dir.display()
+    );
+
+    if !assume_yes && !confirm("Delete these models?", interactive)? {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    for (path, _) in &targets {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove {}", path.display()))?;
+        println!("  🗑  {}", file_label(path));
+    }
+
+    println!("✅ Freed {:.1} MB", total as f64 / MB);
+    Ok(())
+}
+
+/// A cached model's file name, e.g. "ggml-base.en.bin"
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string()
+}
+
+fn confirm(question: &str, interactive: bool) -> Result<bool> {
+    if !interactive || !std::io::stdin().is_terminal() {
+        anyhow::bail!("Refusing to delete models without confirmation (use --yes)");
+    }
+
+    print!("⚠️  {} [y/N] ", question);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+fn file_size(path: &Path) -> Option<u64> {
+    std::fs::metadata(path)
+        .ok()
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+}
@@ -0,0 +1,98 @@
+use crate::app::ProgressMessage;
+use crate::cli::args::OverlayArgs;
+use crate::config::Config;
+use crate::subtitle::burner::{self, SubtitleBurner};
+use crate::subtitle::style;
+use crate::utils::overwrite::OverwritePolicy;
+use anyhow::{Context, Result};
+use std::sync::mpsc;
+
+pub async fn execute(args: OverlayArgs, config: &Config, overwrite: OverwritePolicy) -> Result<()> {
+    // Validate input files
+    if !args.video.exists() {
+        anyhow::bail!("Video file not found: {}", args.video.display());
+    }
+    if !args.subtitles.exists() {
+        anyhow::bail!("Subtitle file not found: {}", args.subtitles.display());
+    }
+
+    let style = style::build_style(args.style_preset.as_deref(), args.style.as_deref())?;
+    let font_color = style::parse_hex_color(&args.font_color).context("Invalid --font-color")?;
+    let outline_color =
+        style::parse_hex_color(&args.outline_color).context("Invalid --outline-color")?;
+    let overlay_bitrate = args
+        .overlay_bitrate
+        .as_deref()
+        .map(burner::parse_bitrate)
+        .transpose()
+        .context("Invalid --overlay-bitrate")?;
+
+    let output_path = args
+        .output
+        .clone()
+        .unwrap_or_else(|| SubtitleBurner::overlay_output_path(&args.video));
+    overwrite.check(&output_path)?;
+
+    let mut burner = SubtitleBurner::new()
+        .with_font_color(font_color)
+        .with_outline_color(outline_color)
+        .with_style(&style);
+    if let Some(height) = args.overlay_height {
+        burner = burner.with_overlay_height(height);
+    }
+    if let Some(width) = args.overlay_width {
+        burner = burner.with_overlay_width(width);
+    }
+    if let Some(scale) = args.font_scale {
+        burner = burner.with_font_scale(scale);
+    }
+    if let Some(min_size) = args.min_font_size {
+        burner = burner.with_min_font_size(min_size);
+    }
+    if let Some(outline) = args.outline {
+        burner = burner.with_outline(outline);
+    }
+    if let Some(shadow) = args.shadow {
+        burner = burner.with_shadow(shadow);
+    }
+    if let Some(align) = args.align {
+        burner = burner.with_text_align(align);
+    }
+    if let Some(bitrate) = overlay_bitrate {
+        burner = burner.with_overlay_bitrate(bitrate);
+    }
+    if let Some(temp_dir) = &config.paths.temp_dir {
+        burner = burner.with_temp_dir(temp_dir.clone());
+    }
+
+    println!("📹 Input video: {}", args.video.display());
+    println!("📄 Input subtitles: {}", args.subtitles.display());
+    println!("🎞️  Overlay: {}", output_path.display());
+
+    let (tx, rx) = mpsc::channel();
+    let video = args.video.clone();
+    let subtitles = args.subtitles.clone();
+    let output_clone = output_path.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = burner.extract_overlay(&video, &subtitles, &output_clone, tx.clone()) {
+            let _ = tx.send(ProgressMessage::Error(e.to_string()));
+        }
+    });
+
+    while let Ok(msg) = rx.recv() {
+        match msg {
+            ProgressMessage::Progress(p, m) => println!("  [{:3.0}%] {}", p * 100.0, m),
+            ProgressMessage::Segment(_)
+            | ProgressMessage::Transcript(_)
+            | ProgressMessage::Language(_) => {}
+            ProgressMessage::Complete => break,
+            ProgressMessage::Error(e) => {
+                anyhow::bail!("Overlay creation failed: {}", e);
+            }
+        }
+    }
+
+    println!("\n✅ Overlay saved to {}", output_path.display());
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use crate::app::ProgressMessage;
+use crate::cli::args::PreviewArgs;
+use crate::subtitle::burner::SubtitleBurner;
+use anyhow::Result;
+use std::sync::mpsc;
+
+pub async fn execute(args: PreviewArgs) -> Result<()> {
+    println!("╔════════════════════════════════════════════════════════════╗");
+    println!("║         AUTO-SUBS TUI - PREVIEW MODE                       ║");
+    println!("╚════════════════════════════════════════════════════════════╝\n");
+
+    // Validate input files
+    if !args.video.exists() {
+        anyhow::bail!("Video file not found: {}", args.video.display());
+    }
+    if !args.subtitles.exists() {
+        anyhow::bail!("Subtitle file not found: {}", args.subtitles.display());
+    }
+
+    println!("📹 Input video: {}", args.video.display());
+    println!("📄 Input subtitles: {}", args.subtitles.display());
+    if let Some(height) = args.overlay_height {
+        println!("⚙️  Overlay height: {}px", height);
+    }
+    if let Some(width) = args.overlay_width {
+        println!("⚙️  Overlay width: {}px", width);
+    }
+    if let Some(x_offset) = args.overlay_x_offset {
+        println!("⚙️  Overlay X offset: {}px", x_offset);
+    }
+    if let Some(y_offset) = args.overlay_y_offset {
+        println!("⚙️  Overlay Y offset: {}px", y_offset);
+    }
+    if let Some(align) = args.align {
+        println!("⚙️  Text alignment: {}", align.as_str());
+    }
+    println!();
+
+    println!("Launching preview (close the player window to return)...");
+    let (tx, rx) = mpsc::channel();
+
+    let mut burner = SubtitleBurner::new();
+
+    if let Some(height) = args.overlay_height {
+        burner = burner.with_overlay_height(height);
+    }
+    if let Some(width) = args.overlay_width {
+        burner = burner.with_overlay_width(width);
+    }
+    if let Some(x_offset) = args.overlay_x_offset {
+        burner = burner.with_overlay_x_offset(x_offset);
+    }
+    if let Some(y_offset) = args.overlay_y_offset {
+        burner = burner.with_overlay_y_offset(y_offset);
+    }
+    if let Some(scale) = args.font_scale {
+        burner = burner.with_font_scale(scale);
+    }
+    if let Some(min_size) = args.min_font_size {
+        burner = burner.with_min_font_size(min_size);
+    }
+    if let Some(align) = args.align {
+        burner = burner.with_text_align(align);
+    }
+
+    let video_clone = args.video.clone();
+    let srt_clone = args.subtitles.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = burner.preview_with_overlay(&video_clone, &srt_clone, tx.clone()) {
+            let _ = tx.send(ProgressMessage::Error(e.to_string()));
+        }
+    });
+
+    while let Ok(msg) = rx.recv() {
+        match msg {
+            ProgressMessage::Progress(p, m) => println!("  [{:3.0}%] {}", p * 100.0, m),
+            ProgressMessage::Segment(_)
+            | ProgressMessage::Transcript(_)
+            | ProgressMessage::Language(_) => {}
+            ProgressMessage::Complete => break,
+            ProgressMessage::Error(e) => {
+                anyhow::bail!("Preview failed: {}", e);
+            }
+        }
+    }
+
+    println!("\n✅ Preview closed");
+
+    Ok(())
+}
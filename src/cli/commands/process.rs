@@ -1,90 +1,302 @@
 use crate::app::ProgressMessage;
 use crate::audio::extractor::AudioExtractor;
 use crate::cli::args::ProcessArgs;
-use crate::subtitle::burner::SubtitleBurner;
+use crate::config::Config;
+use crate::subtitle::burner::{self, SubtitleBurner};
+use crate::subtitle::embedded;
+use crate::subtitle::format::{self, SubtitleFormat};
 use crate::subtitle::generator::SubtitleGenerator;
-use anyhow::Result;
+use crate::subtitle::karaoke;
+use crate::subtitle::metadata::RunMetadata;
+use crate::subtitle::split::{self, SplitMode};
+use crate::subtitle::srt::{self, Subtitle};
+use crate::subtitle::style;
+use crate::subtitle::wrap::WrapFont;
+use crate::utils::hook;
+use crate::utils::overwrite::OverwritePolicy;
+use crate::utils::remote;
+use crate::utils::temp::TempWorkspace;
+use crate::utils::template::{self, TemplateValues};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
+use std::time::Instant;
 
-pub async fn execute(args: ProcessArgs) -> Result<()> {
+pub async fn execute(
+    mut args: ProcessArgs,
+    config: &Config,
+    overwrite: OverwritePolicy,
+    interactive: bool,
+) -> Result<()> {
     println!("╔════════════════════════════════════════════════════════════╗");
     println!("║         AUTO-SUBS TUI - PROCESS MODE                       ║");
     println!("╚════════════════════════════════════════════════════════════╝\n");
+    let started = Instant::now();
 
     // Validate input file
-    if !args.input.exists() {
+    let source = args.input.clone();
+    let is_url = remote::is_url(&args.input);
+    if is_url && args.no_download {
+        anyhow::bail!(
+            "{} is a URL, but burning needs the whole video downloaded (drop --no-download, or caption it with `transcribe` instead)",
+            args.input.display()
+        );
+    }
+    if !is_url && !args.input.exists() {
         anyhow::bail!("Video file not found: {}", args.input.display());
     }
     println!("📹 Input video: {}", args.input.display());
-    println!("🎯 Model: {}", args.model.as_str());
-    println!("🌍 Language: {}\n", args.language);
+    match args.from_embedded {
+        Some(track) => println!("🎞️  Subtitles: embedded track {}\n", track),
+        None => {
+            println!("🎯 Model: {}", args.model.as_str());
+            println!("🌍 Language: {}\n", args.language);
+        }
+    }
 
-    // Determine output paths
-    let audio_path = args
-        .audio_output
-        .clone()
-        .unwrap_or_else(|| args.input.with_extension("wav"));
+    // Intermediate files go to a temp workspace that is removed when this
+    // function returns, including on errors, unless they should be kept
+    let keep_files = args.keep_files || config.behavior.keep_files;
+    let workspace = if keep_files {
+        None
+    } else {
+        Some(TempWorkspace::new(config.paths.temp_dir.as_deref())?)
+    };
+    let keep_audio = args.keep_audio || config.behavior.keep_audio;
 
-    let srt_path = args
-        .srt_output
+    let output_template = args
+        .output_template
         .clone()
-        .unwrap_or_else(|| args.input.with_extension("srt"));
-
-    let output_path = args.output.clone().unwrap_or_else(|| {
-        args.input.with_file_name(format!(
-            "{}_subtitled.{}",
-            args.input.file_stem().unwrap().to_string_lossy(),
-            args.input.extension().unwrap().to_string_lossy()
-        ))
-    });
+        .or(config.paths.output_template.clone());
+    let output_template = output_template.as_deref();
+    let values = TemplateValues {
+        model: Some(args.model.as_str().to_string()),
+        lang: Some(args.language.clone()),
+    };
 
-    // Step 1: Extract audio
-    println!("[1/3] Extracting audio...");
-    let (tx, rx) = mpsc::channel();
-    let extractor = AudioExtractor::new();
+    // Download a URL input first: into the workspace, or the current
+    // directory when intermediate files are kept
+    if is_url {
+        let url = args.input.to_string_lossy().to_string();
+        println!("🌐 Downloading {}...", url);
+        if !remote::has_yt_dlp() {
+            println!("      yt-dlp not found; using FFmpeg (direct media links only)");
+        }
+        let dir = workspace.as_ref().map_or(Path::new("."), |w| w.path());
+        let video = remote::download(&url, dir, false)?;
+        println!("      ✅ Downloaded: {}\n", video.display());
+        // Outputs named after a temporary download belong in the current directory
+        if workspace.is_some() {
+            let name = PathBuf::from(video.file_name().unwrap_or_default());
+            args.output = Some(template::resolve(
+                args.output.take(),
+                output_template,
+                &name,
+                &SubtitleBurner::output_extension(&name),
+                &values,
+                || SubtitleBurner::default_output_path(&name),
+            )?);
+            if keep_audio {
+                args.audio_output
+                    .get_or_insert_with(|| name.with_extension("wav"));
+            }
+        }
+        args.input = video;
+    }
 
-    let video_clone = args.input.clone();
-    let audio_clone = audio_path.clone();
-    std::thread::spawn(move || {
-        let _ = extractor.extract(&video_clone, &audio_clone, tx);
+    let intermediate = |extension: &str| match &workspace {
+        Some(workspace) => workspace.file(&format!("audio.{}", extension)),
+        None => args.input.with_extension(extension),
+    };
+
+    // Determine output paths (explicit paths are always kept)
+    let audio_path = args.audio_output.clone().unwrap_or_else(|| {
+        if keep_audio {
+            args.input.with_extension("wav")
+        } else {
+            intermediate("wav")
+        }
     });
 
-    while let Ok(msg) = rx.recv() {
-        match msg {
-            ProgressMessage::Progress(p, m) => println!("      [{:3.0}%] {}", p * 100.0, m),
-            ProgressMessage::Complete => break,
-            ProgressMessage::Error(e) => {
-                anyhow::bail!("Audio extraction failed: {}", e);
+    // An --output-format on its own names its file after the input
+    let subtitles_output = args.srt_output.clone().or_else(|| {
+        args.output_format
+            .map(|format| args.input.with_extension(format.as_str()))
+    });
+
+    // Subtitles asked for in another format are worked on as SRT and
+    // written out once final
+    let subtitles_export = subtitles_output
+        .clone()
+        .filter(|path| SubtitleFormat::resolve(path, args.output_format) != SubtitleFormat::Srt);
+    let srt_path = match &subtitles_output {
+        Some(path) if subtitles_export.is_none() => path.clone(),
+        _ => intermediate("srt"),
+    };
+
+    let output_path = template::resolve(
+        args.output.clone(),
+        output_template,
+        &args.input,
+        &SubtitleBurner::output_extension(&args.input),
+        &values,
+        || SubtitleBurner::default_output_path(&args.input),
+    )?;
+
+    let use_overlay = args.use_overlay.unwrap_or(config.use_overlay());
+    // Embedded subtitles replace both the audio and transcription steps
+    let steps = if args.from_embedded.is_some() { 2 } else { 3 };
+    // Resolve the style up front so a bad preset fails before transcription
+    let style = style::build_style(args.style_preset.as_deref(), args.style.as_deref())?;
+    let font_color = style::parse_hex_color(&args.font_color).context("Invalid --font-color")?;
+    let outline_color =
+        style::parse_hex_color(&args.outline_color).context("Invalid --outline-color")?;
+    let karaoke_color =
+        style::parse_hex_color(&args.karaoke_color).context("Invalid --karaoke-color")?;
+    let video_bitrate = args
+        .video_bitrate
+        .as_deref()
+        .map(burner::parse_bitrate)
+        .transpose()
+        .context("Invalid --video-bitrate")?;
+    let overlay_bitrate = args
+        .overlay_bitrate
+        .as_deref()
+        .map(burner::parse_bitrate)
+        .transpose()
+        .context("Invalid --overlay-bitrate")?;
+
+    let split_mode = SplitMode::from_args(args.split_minutes, args.split_chapters)?;
+
+    // Check the files we'd write before spending time on the pipeline
+    overwrite.check(&output_path)?;
+    if split_mode.is_some() {
+        overwrite.check(&split::part_path(&output_path, 1))?;
+    }
+    if use_overlay && args.keep_overlay {
+        overwrite.check(&SubtitleBurner::kept_overlay_path(&output_path))?;
+    }
+
+    let model_source = args
+        .model_url
+        .clone()
+        .or(config.whisper.model_base_url.clone());
+    let min_gap = args.min_gap.unwrap_or(config.min_gap_ms());
+    let min_duration = args.min_duration.or(config.subtitles.min_duration_ms);
+    let max_duration = args.max_duration.or(config.subtitles.max_duration_ms);
+    // Whisper's cues with their word timings, which the saved SRT can't hold
+    let mut transcript = Vec::new();
+    let mut detected_language = None;
+
+    if let Some(track) = args.from_embedded {
+        // Step 1: Convert the embedded subtitle track instead of transcribing
+        println!(
+            "[1/{}] Extracting embedded subtitle track {}...",
+            steps, track
+        );
+        embedded::extract_track(&args.input, track, &srt_path)?;
+        println!("      ✅ Subtitles extracted: {}", srt_path.display());
+    } else {
+        // Step 1: Extract audio, unless audio kept from an earlier run still matches
+        println!("[1/{}] Extracting audio...", steps);
+        let extractor = AudioExtractor::new();
+        if keep_audio && extractor.is_up_to_date(&args.input, &audio_path) {
+            println!("      ♻️  Reusing audio: {}", audio_path.display());
+        } else {
+            let (tx, rx) = mpsc::channel();
+            let video_clone = args.input.clone();
+            let audio_clone = audio_path.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = extractor.extract(&video_clone, &audio_clone, tx.clone()) {
+                    let _ = tx.send(ProgressMessage::Error(e.to_string()));
+                }
+            });
+
+            while let Ok(msg) = rx.recv() {
+                match msg {
+                    ProgressMessage::Progress(p, m) => println!("      [{:3.0}%] {}", p * 100.0, m),
+                    ProgressMessage::Segment(_)
+                    | ProgressMessage::Transcript(_)
+                    | ProgressMessage::Language(_) => {}
+                    ProgressMessage::Complete => break,
+                    ProgressMessage::Error(e) => {
+                        anyhow::bail!("Audio extraction failed: {}", e);
+                    }
+                }
             }
+            println!("      ✅ Audio extracted: {}", audio_path.display());
         }
-    }
-    println!("      ✅ Audio extracted: {}", audio_path.display());
 
-    // Step 2: Generate subtitles
-    println!(
-        "\n[2/3] Generating subtitles with Whisper ({})...",
-        args.model.as_str()
-    );
-    println!("      (This may download the model on first run)");
-    let (tx, rx) = mpsc::channel();
-    let generator = SubtitleGenerator::new();
+        // Step 2: Generate subtitles
+        println!(
+            "\n[2/{}] Generating subtitles with Whisper ({})...",
+            steps,
+            args.model.as_str()
+        );
+        println!("      (This may download the model on first run)");
+        let (tx, rx) = mpsc::channel();
 
-    let audio_clone = audio_path.clone();
-    let srt_clone = srt_path.clone();
-    std::thread::spawn(move || {
-        let _ = generator.generate(&audio_clone, &srt_clone, tx);
-    });
+        let mut generator = SubtitleGenerator::new()
+            .with_model(args.model)
+            .with_language(&args.language)
+            .with_translate(args.translate)
+            .with_trim_silence(args.trim_silence)
+            .with_continuous(args.continuous)
+            .with_sentence_splitting(!args.no_split);
+        if let Some(source) = &model_source {
+            generator = generator.with_model_source(source);
+        }
+        generator = generator
+            .with_min_gap(min_gap)
+            .with_duration_limits(min_duration, max_duration)
+            .with_temperature(args.temperature)
+            .with_no_speech_threshold(args.no_speech_threshold)
+            .with_logprob_threshold(args.logprob_threshold)
+            .with_write_options(config.srt_write_options())
+            .with_force_model(args.force);
+        generator.check_model()?;
+        if let Some(note) = generator.model_note() {
+            println!("      ⚠️  {}", note);
+        }
 
-    while let Ok(msg) = rx.recv() {
-        match msg {
-            ProgressMessage::Progress(p, m) => println!("      [{:3.0}%] {}", p * 100.0, m),
-            ProgressMessage::Complete => break,
-            ProgressMessage::Error(e) => {
-                anyhow::bail!("Subtitle generation failed: {}", e);
+        let audio_clone = audio_path.clone();
+        let srt_clone = srt_path.clone();
+        std::thread::spawn(move || {
+            let _ = generator.generate(&audio_clone, &srt_clone, tx);
+        });
+
+        while let Ok(msg) = rx.recv() {
+            match msg {
+                ProgressMessage::Progress(p, m) => println!("      [{:3.0}%] {}", p * 100.0, m),
+                ProgressMessage::Transcript(cues) => transcript = cues,
+                ProgressMessage::Language(language) => {
+                    println!("      🌍 Detected: {}", language);
+                    detected_language = Some(language.code);
+                }
+                ProgressMessage::Segment(_) => {}
+                ProgressMessage::Complete => break,
+                ProgressMessage::Error(e) => {
+                    anyhow::bail!("Subtitle generation failed: {}", e);
+                }
             }
         }
+        if srt::parse_srt(&srt_path)?.is_empty() {
+            anyhow::bail!(
+                "No speech detected in {}; nothing to burn (check the audio, or lower --no-speech-threshold)",
+                args.input.display()
+            );
+        }
+        println!("      ✅ Subtitles generated: {}", srt_path.display());
+    }
+
+    // Let the user's own script adjust the subtitles (filters, terminology, ...)
+    if let Some(hook) = &args.post_hook {
+        println!("\n      🪝 Running post-hook: {}", hook);
+        hook::run_post_hook(hook, &srt_path, interactive)?;
+        let cues = srt::parse_srt(&srt_path)
+            .context("The post-hook left an SRT file that can't be parsed")?;
+        println!("      ✅ Post-hook done ({} cues)", cues.len());
     }
-    println!("      ✅ Subtitles generated: {}", srt_path.display());
 
     // Show preview of generated subtitles
     if let Ok(content) = std::fs::read_to_string(&srt_path) {
@@ -96,20 +308,46 @@ pub async fn execute(args: ProcessArgs) -> Result<()> {
         println!("      │ ...");
     }
 
+    // Karaoke burns an ASS file of the final cues, timed word by word
+    let mut burn_path = srt_path.clone();
+    let mut karaoke_burn = false;
+    if args.karaoke {
+        let mut cues = srt::parse_srt(&srt_path)?;
+        if karaoke::attach_word_timings(&mut cues, &transcript) == 0 {
+            println!(
+                "\n      ⚠️  No word timings to highlight (embedded or edited subtitles); burning without karaoke"
+            );
+        } else {
+            burn_path = intermediate("ass");
+            karaoke::save_karaoke(&burn_path, &cues)?;
+            karaoke_burn = true;
+        }
+    }
+
     // Step 3: Burn subtitles
-    println!("\n[3/3] Burning subtitles into video...");
-    if args.use_overlay {
+    println!("\n[{0}/{0}] Burning subtitles into video...", steps);
+    if use_overlay {
         println!("      Using overlay method (creates separate subtitle layer)");
         if args.keep_overlay {
             println!("      Keeping overlay file for customization");
         }
+    } else if args.overlay_width.is_some()
+        || args.overlay_x_offset.is_some()
+        || args.overlay_y_offset.is_some()
+        || args.keep_overlay
+    {
+        println!("      ⚠️  Overlay width, offsets and --keep-overlay are ignored by direct burn");
     }
     let (tx, rx) = mpsc::channel();
 
     let mut burner = SubtitleBurner::new()
-        .with_overlay(args.use_overlay)
+        .with_overlay(use_overlay)
         .keep_overlay_file(args.keep_overlay);
 
+    if let Some(temp_dir) = &config.paths.temp_dir {
+        burner = burner.with_temp_dir(temp_dir.clone());
+    }
+
     if let Some(height) = args.overlay_height {
         burner = burner.with_overlay_height(height);
     }
@@ -122,9 +360,56 @@ pub async fn execute(args: ProcessArgs) -> Result<()> {
     if let Some(y_offset) = args.overlay_y_offset {
         burner = burner.with_overlay_y_offset(y_offset);
     }
+    if let Some(scale) = args.font_scale {
+        burner = burner.with_font_scale(scale);
+    }
+    if let Some(min_size) = args.min_font_size {
+        burner = burner.with_min_font_size(min_size);
+    }
+    burner = burner
+        .with_font_color(font_color)
+        .with_outline_color(outline_color);
+    if karaoke_burn {
+        burner = burner.with_karaoke_color(karaoke_color);
+    }
+    if let Some(outline) = args.outline {
+        burner = burner.with_outline(outline);
+    }
+    if let Some(shadow) = args.shadow {
+        burner = burner.with_shadow(shadow);
+    }
+    if let Some(align) = args.align {
+        burner = burner.with_text_align(align);
+    }
+    if args.wrap {
+        burner = burner.with_wrap(WrapFont::load(
+            args.font_file.as_deref(),
+            config.char_width_factor(),
+        )?);
+    }
+    burner = burner.with_style(&style);
+    if let Some(bitrate) = video_bitrate {
+        burner = burner.with_video_bitrate(bitrate);
+    }
+    if let Some(bitrate) = overlay_bitrate {
+        burner = burner.with_overlay_bitrate(bitrate);
+    }
+
+    // Styled formats carry the burn's look: its font size, colors and alignment
+    if let Some(path) = &subtitles_export {
+        let (_, video_height) = burner.get_video_dimensions(&args.input)?;
+        format::save_subtitles(
+            path,
+            &srt::parse_srt(&srt_path)?,
+            args.output_format,
+            &config.srt_write_options(),
+            &burner.ass_style(video_height),
+        )?;
+        println!("      ✅ Subtitles written: {}", path.display());
+    }
 
     let video_clone = args.input.clone();
-    let srt_clone = srt_path.clone();
+    let srt_clone = burn_path.clone();
     let output_clone = output_path.clone();
     std::thread::spawn(move || {
         let _ = burner.burn(&video_clone, &srt_clone, &output_clone, tx);
@@ -133,6 +418,9 @@ pub async fn execute(args: ProcessArgs) -> Result<()> {
     while let Ok(msg) = rx.recv() {
         match msg {
             ProgressMessage::Progress(p, m) => println!("      [{:3.0}%] {}", p * 100.0, m),
+            ProgressMessage::Segment(_)
+            | ProgressMessage::Transcript(_)
+            | ProgressMessage::Language(_) => {}
             ProgressMessage::Complete => break,
             ProgressMessage::Error(e) => {
                 anyhow::bail!("Subtitle burning failed: {}", e);
@@ -141,27 +429,97 @@ pub async fn execute(args: ProcessArgs) -> Result<()> {
     }
     println!("      ✅ Output video: {}", output_path.display());
 
+    let parts = match split_mode {
+        Some(mode) => {
+            println!("\n✂️  Splitting the output...");
+            let parts = split::split(
+                &output_path,
+                &srt::parse_srt(&srt_path)?,
+                mode,
+                &config.srt_write_options(),
+            )?;
+            println!("      ✅ {} parts", parts.len());
+            parts
+        }
+        None => Vec::new(),
+    };
+
+    // Record the settings next to the output (read before the SRT is cleaned up)
+    let metadata_path = if args.metadata || keep_files {
+        let path = RunMetadata::sidecar_path(&output_path);
+        RunMetadata {
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: 0,
+            command: "process".to_string(),
+            input: source,
+            srt: subtitles_export
+                .clone()
+                .or_else(|| (keep_files || subtitles_output.is_some()).then(|| srt_path.clone())),
+            output: Some(output_path.clone()),
+            embedded_track: args.from_embedded,
+            post_hook: args.post_hook.clone(),
+            model: args.model.as_str().to_string(),
+            model_source,
+            language: args.language.clone(),
+            detected_language,
+            translate: args.translate,
+            trim_silence: args.trim_silence,
+            continuous: args.continuous,
+            no_split: args.no_split,
+            temperature: args.temperature,
+            no_speech_threshold: args.no_speech_threshold,
+            logprob_threshold: args.logprob_threshold,
+            min_gap_ms: min_gap,
+            min_duration_ms: min_duration,
+            max_duration_ms: max_duration,
+            cue_count: srt::parse_srt(&srt_path)?.len(),
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        }
+        .save(&path)?;
+        Some(path)
+    } else {
+        None
+    };
+
     // Cleanup if requested
-    if !args.keep_files {
+    if let Some(workspace) = workspace {
         println!("\n🧹 Cleaning up intermediate files...");
-        let _ = std::fs::remove_file(&audio_path);
-        let _ = std::fs::remove_file(&srt_path);
-        println!("      ✅ Removed: {}", audio_path.display());
-        println!("      ✅ Removed: {}", srt_path.display());
+        println!("      ✅ Removed: {}", workspace.path().display());
+        drop(workspace);
     }
 
     println!("\n╔════════════════════════════════════════════════════════════╗");
     println!("║                  PROCESSING COMPLETE!                      ║");
     println!("╚════════════════════════════════════════════════════════════╝");
 
-    if args.keep_files {
+    if keep_files {
         println!("\nGenerated files:");
-        println!("  📁 {}", audio_path.display());
+        if args.from_embedded.is_none() {
+            println!("  📁 {}", audio_path.display());
+        }
         println!("  📄 {}", srt_path.display());
+        if let Some(path) = &subtitles_export {
+            println!("  📄 {}", path.display());
+        }
         println!("  🎬 {}", output_path.display());
     } else {
         println!("\nOutput file:");
         println!("  🎬 {}", output_path.display());
+        if keep_audio && args.from_embedded.is_none() {
+            println!("  📁 {}", audio_path.display());
+        }
+    }
+    for part in &parts {
+        println!(
+            "  🎬 {} ({} - {}, {} cues)",
+            part.video.display(),
+            Subtitle::format_time(part.start_ms),
+            Subtitle::format_time(part.end_ms),
+            part.cues
+        );
+    }
+    if let Some(path) = metadata_path {
+        println!("  🧾 {}", path.display());
     }
 
     Ok(())
@@ -0,0 +1,46 @@
+use crate::cli::args::ShiftArgs;
+use crate::config::Config;
+use crate::subtitle::ass::AssStyle;
+use crate::subtitle::format;
+use crate::subtitle::timing;
+use anyhow::Result;
+
+pub async fn execute(args: ShiftArgs, config: &Config) -> Result<()> {
+    // Validate input file
+    if !args.input.exists() {
+        anyhow::bail!("SRT file not found: {}", args.input.display());
+    }
+
+    let offset_ms = timing::parse_offset(&args.offset)?;
+    let output_path = args.output.clone().unwrap_or_else(|| args.input.clone());
+    // Written back over the input, the file keeps the format it was read as
+    let output_format = match &args.output {
+        Some(_) => args.output_format,
+        None => args.output_format.or(args.input_format),
+    };
+
+    let mut subtitles = format::load_subtitles(&args.input, args.input_format)?;
+    let clamped = timing::shift_all(&mut subtitles, offset_ms);
+    if clamped > 0 {
+        eprintln!(
+            "⚠️  {} cues would start before 0:00 and were clamped to it",
+            clamped
+        );
+    }
+
+    format::save_subtitles(
+        &output_path,
+        &subtitles,
+        output_format,
+        &config.srt_write_options(),
+        &AssStyle::default(),
+    )?;
+    println!(
+        "✅ Shifted {} cues by {:+}ms to {}",
+        subtitles.len(),
+        offset_ms,
+        output_path.display()
+    );
+
+    Ok(())
+}
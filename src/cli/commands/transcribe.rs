@@ -1,27 +1,116 @@
 use crate::app::ProgressMessage;
+use crate::audio::extractor::AudioExtractor;
 use crate::cli::args::TranscribeArgs;
+use crate::config::Config;
+use crate::subtitle::ass::AssStyle;
+use crate::subtitle::format;
 use crate::subtitle::generator::SubtitleGenerator;
-use anyhow::Result;
+use crate::subtitle::metadata::RunMetadata;
+use crate::subtitle::srt::Subtitle;
+use crate::subtitle::timing;
+use crate::utils::remote;
+use crate::utils::temp::{self, TempWorkspace};
+use crate::utils::template::{self, TemplateValues};
+use anyhow::{Context, Result};
+use std::path::PathBuf;
 use std::sync::mpsc;
+use std::time::Instant;
 
-pub async fn execute(args: TranscribeArgs) -> Result<()> {
+pub async fn execute(args: TranscribeArgs, config: &Config) -> Result<()> {
     println!("╔════════════════════════════════════════════════════════════╗");
     println!("║       AUTO-SUBS TUI - TRANSCRIBE MODE                      ║");
     println!("╚════════════════════════════════════════════════════════════╝\n");
+    let started = Instant::now();
 
-    // Validate input file
-    if !args.input.exists() {
-        anyhow::bail!("Audio file not found: {}", args.input.display());
-    }
+    // Validate input file. With `-`, stdin is buffered to a temp WAV that is
+    // removed when `workspace` drops at the end of this function. A URL's
+    // audio is extracted into the workspace the same way.
+    let mut workspace = None;
+    let mut default_output = args.input.with_extension("srt");
+    let input_path = if remote::is_url(&args.input) {
+        let url = args.input.to_string_lossy().to_string();
+        let ws = TempWorkspace::new(config.paths.temp_dir.as_deref())?;
+        let source = if remote::has_yt_dlp() {
+            println!("🌐 Downloading audio with yt-dlp: {}", url);
+            remote::download(&url, ws.path(), true)?
+        } else {
+            println!(
+                "🌐 yt-dlp not found; FFmpeg reads the URL directly (direct media links only)"
+            );
+            args.input.clone()
+        };
+        // Name the SRT after the media, in the current directory
+        default_output = match source.file_name() {
+            Some(name) if source != args.input => PathBuf::from(name).with_extension("srt"),
+            _ => remote::name_from_url(&url).with_extension("srt"),
+        };
+
+        println!("🎵 Extracting audio...");
+        let audio = ws.file("remote.wav");
+        let (tx, _rx) = mpsc::channel();
+        AudioExtractor::new()
+            .extract(&source, &audio, tx)
+            .with_context(|| format!("Failed to get audio from {}", url))?;
+        workspace = Some(ws);
+        audio
+    } else if temp::is_stdin(&args.input) {
+        if args.output.is_none() && args.append_to.is_none() {
+            anyhow::bail!("Reading from stdin requires --output or --append-to");
+        }
+        let ws = TempWorkspace::new(config.paths.temp_dir.as_deref())?;
+        let buffered = ws.buffer_stdin("stdin.wav")?;
+        workspace = Some(ws);
+        buffered
+    } else {
+        if !args.input.exists() {
+            anyhow::bail!("Audio file not found: {}", args.input.display());
+        }
+        args.input.clone()
+    };
 
     // Determine output path
-    let output_path = args
-        .output
-        .clone()
-        .unwrap_or_else(|| args.input.with_extension("srt"));
+    let values = TemplateValues {
+        model: Some(args.model.as_str().to_string()),
+        lang: Some(args.language.clone()),
+    };
+    let output_path = template::resolve(
+        args.output.clone().or(args.append_to.clone()),
+        args.output_template
+            .as_deref()
+            .or(config.paths.output_template.as_deref()),
+        &default_output,
+        args.output_format.map_or("srt", |format| format.as_str()),
+        &values,
+        || match args.output_format {
+            Some(format) => default_output.with_extension(format.as_str()),
+            None => default_output.clone(),
+        },
+    )?;
+
+    // Read the cues being appended to before anything can overwrite them
+    let existing = match &args.append_to {
+        Some(path) => Some(
+            format::load_subtitles(path, None)
+                .with_context(|| format!("Failed to read {}", path.display()))?,
+        ),
+        None => None,
+    };
 
-    println!("🎵 Input audio: {}", args.input.display());
+    if remote::is_url(&args.input) {
+        println!("🎵 Input audio: {}", args.input.display());
+    } else if workspace.is_some() {
+        println!("🎵 Input audio: <stdin>");
+    } else {
+        println!("🎵 Input audio: {}", input_path.display());
+    }
     println!("📄 Output SRT: {}", output_path.display());
+    if let (Some(path), Some(existing)) = (&args.append_to, &existing) {
+        println!(
+            "➕ Appending to: {} ({} cues)",
+            path.display(),
+            existing.len()
+        );
+    }
     println!("🎯 Model: {}", args.model.as_str());
     println!("🌍 Language: {}", args.language);
     println!("🔧 Provider: {}\n", args.provider);
@@ -33,38 +122,145 @@ pub async fn execute(args: TranscribeArgs) -> Result<()> {
     );
     println!("(This may download the model on first run)");
 
+    let model_source = args
+        .model_url
+        .clone()
+        .or(config.whisper.model_base_url.clone());
+    let min_gap = args.min_gap.unwrap_or(config.min_gap_ms());
+    let min_duration = args.min_duration.or(config.subtitles.min_duration_ms);
+    let max_duration = args.max_duration.or(config.subtitles.max_duration_ms);
+
+    let mut generator = SubtitleGenerator::new()
+        .with_model(args.model)
+        .with_language(&args.language)
+        .with_translate(args.translate)
+        .with_trim_silence(args.trim_silence)
+        .with_continuous(args.continuous)
+        .with_sentence_splitting(!args.no_split);
+    if let Some(source) = &model_source {
+        generator = generator.with_model_source(source);
+    }
+    generator = generator
+        .with_min_gap(min_gap)
+        .with_duration_limits(min_duration, max_duration)
+        .with_temperature(args.temperature)
+        .with_no_speech_threshold(args.no_speech_threshold)
+        .with_logprob_threshold(args.logprob_threshold)
+        .with_write_options(config.srt_write_options())
+        .with_output_format(args.output_format)
+        .with_force_model(args.force);
+    generator.check_model()?;
+    if let Some(note) = generator.model_note() {
+        println!("⚠️  {}", note);
+    }
+
+    // Fetch the model up front so download progress is shown
     let (tx, rx) = mpsc::channel();
-    let generator = SubtitleGenerator::new();
-
-    let input_clone = args.input.clone();
-    let output_clone = output_path.clone();
-    std::thread::spawn(move || {
-        let _ = generator.generate(&input_clone, &output_clone, tx);
-    });
-
-    while let Ok(msg) = rx.recv() {
-        match msg {
-            ProgressMessage::Progress(p, m) => println!("  [{:3.0}%] {}", p * 100.0, m),
-            ProgressMessage::Complete => break,
-            ProgressMessage::Error(e) => {
-                anyhow::bail!("Subtitle generation failed: {}", e);
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            for msg in rx {
+                if let ProgressMessage::Progress(p, m) = msg {
+                    println!("  [{:3.0}%] {}", p * 100.0, m);
+                }
             }
+        });
+        let result = generator.ensure_model(&tx);
+        drop(tx);
+        result
+    })?;
+
+    // When appending, generate into the workspace and merge afterwards so a
+    // failed run leaves the existing file alone
+    let generated_path = if existing.is_some() {
+        if workspace.is_none() {
+            workspace = Some(TempWorkspace::new(config.paths.temp_dir.as_deref())?);
         }
+        workspace.as_ref().unwrap().file("generated.srt")
+    } else {
+        output_path.clone()
+    };
+
+    // Print cues as Whisper produces them
+    println!("\n📋 Transcript:");
+    let mut stream = generator.generate_stream(&input_path, &generated_path);
+    for cue in &mut stream {
+        println!(
+            "   │ {}  {}",
+            Subtitle::format_time(cue.start_time),
+            cue.text.replace('\n', " ")
+        );
+    }
+    let detected_language = stream.detected_language().cloned();
+    if let Some(language) = &detected_language {
+        println!("\n🌍 Detected: {}", language);
     }
+    let generated = stream.finish().context("Subtitle generation failed")?;
+    let new_cues = generated.len();
 
-    println!("\n✅ Subtitle generation complete!");
-    println!("   Output: {}", output_path.display());
+    let subtitles = match existing {
+        Some(existing) => {
+            let mut merged = timing::append_shifted(existing, generated, args.offset);
+            if !args.continuous {
+                timing::enforce_min_gap(&mut merged, min_gap);
+            }
+            format::save_subtitles(
+                &output_path,
+                &merged,
+                args.output_format,
+                &config.srt_write_options(),
+                &AssStyle::default(),
+            )?;
+            merged
+        }
+        None => generated,
+    };
 
-    // Show preview of generated subtitles
-    if let Ok(content) = std::fs::read_to_string(&output_path) {
-        let lines: Vec<&str> = content.lines().take(15).collect();
-        println!("\n📋 Preview (first few entries):");
-        for line in lines {
-            println!("   │ {}", line);
+    if new_cues == 0 {
+        if args.append_to.is_some() {
+            println!("\n⚠️  No speech detected; nothing was appended.");
+        } else {
+            println!("\n⚠️  No speech detected; the SRT is empty.");
+        }
+        println!("   Output: {}", output_path.display());
+        println!("   Check that the audio has speech, or lower --no-speech-threshold.");
+    } else {
+        println!("\n✅ Subtitle generation complete!");
+        println!("   Output: {}", output_path.display());
+        if args.append_to.is_some() {
+            println!("   Appended {} cues ({} total)", new_cues, subtitles.len());
         }
-        if content.lines().count() > 15 {
-            println!("   │ ...");
+    }
+
+    if args.metadata {
+        let metadata_path = RunMetadata::sidecar_path(&output_path);
+        RunMetadata {
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: 0,
+            command: "transcribe".to_string(),
+            input: args.input.clone(),
+            srt: Some(output_path.clone()),
+            output: None,
+            embedded_track: None,
+            post_hook: None,
+            model: args.model.as_str().to_string(),
+            model_source,
+            language: args.language.clone(),
+            detected_language: detected_language.map(|language| language.code),
+            translate: args.translate,
+            trim_silence: args.trim_silence,
+            continuous: args.continuous,
+            no_split: args.no_split,
+            temperature: args.temperature,
+            no_speech_threshold: args.no_speech_threshold,
+            logprob_threshold: args.logprob_threshold,
+            min_gap_ms: min_gap,
+            min_duration_ms: min_duration,
+            max_duration_ms: max_duration,
+            cue_count: subtitles.len(),
+            elapsed_ms: started.elapsed().as_millis() as u64,
         }
+        .save(&metadata_path)?;
+        println!("   Metadata: {}", metadata_path.display());
     }
 
     Ok(())
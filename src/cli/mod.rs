@@ -5,6 +5,11 @@ use anyhow::Result;
 use args::{Cli, Commands};
 use clap::Parser;
 
+use crate::app::App;
+use crate::config::Config;
+use crate::tui;
+use crate::utils::overwrite::OverwritePolicy;
+
 /// Execute the CLI with parsed arguments
 pub async fn run() -> Result<()> {
     let cli = Cli::parse();
@@ -12,67 +17,58 @@ pub async fn run() -> Result<()> {
     // Setup logging based on verbosity
     setup_logging(cli.verbose, cli.quiet);
 
+    // Config file values act as defaults underneath CLI arguments
+    let config = Config::load(cli.config.as_deref())?;
+    // With nobody to ask, keeping an existing file is the safe answer
+    let overwrite = OverwritePolicy::from_flags(
+        cli.yes,
+        cli.no_overwrite || (cli.non_interactive && !cli.yes),
+    );
+    if cli.non_interactive
+        && matches!(
+            cli.command,
+            None | Some(Commands::Edit(_)) | Some(Commands::Preview(_))
+        )
+    {
+        anyhow::bail!(
+            "--non-interactive only works with commands that run unattended (e.g. process, burn)"
+        );
+    }
+
     // Execute subcommand or launch TUI
     match cli.command {
-        Some(Commands::Process(args)) => commands::process::execute(args).await,
-        Some(Commands::Extract(args)) => commands::extract::execute(args).await,
-        Some(Commands::Transcribe(args)) => commands::transcribe::execute(args).await,
-        Some(Commands::Burn(args)) => commands::burn::execute(args).await,
-        Some(Commands::Edit(args)) => commands::edit::execute(args).await,
+        Some(Commands::Process(args)) => {
+            commands::process::execute(args, &config, overwrite, !cli.non_interactive).await
+        }
+        Some(Commands::Extract(args)) => commands::extract::execute(args, &config).await,
+        Some(Commands::Transcribe(args)) => commands::transcribe::execute(args, &config).await,
+        Some(Commands::Burn(args)) => commands::burn::execute(args, &config, overwrite).await,
+        Some(Commands::Overlay(args)) => commands::overlay::execute(args, &config, overwrite).await,
+        Some(Commands::Edit(args)) => {
+            commands::edit::execute(args, config, overwrite, !cli.no_altscreen).await
+        }
+        Some(Commands::Fix(args)) => commands::fix::execute(args, &config).await,
+        Some(Commands::Lint(args)) => commands::lint::execute(args, &config).await,
+        Some(Commands::Shift(args)) => commands::shift::execute(args, &config).await,
+        Some(Commands::Preview(args)) => commands::preview::execute(args).await,
+        Some(Commands::Models(args)) => {
+            commands::models::execute(args, cli.yes, !cli.non_interactive).await
+        }
+        Some(Commands::Benchmark(args)) => commands::benchmark::execute(args, &config).await,
         Some(Commands::Config(args)) => commands::config::execute(args).await,
         None => {
             // No subcommand provided - launch TUI mode
-            launch_tui().await
+            launch_tui(config, overwrite, !cli.no_altscreen).await
         }
     }
 }
 
 /// Launch the TUI interface
-async fn launch_tui() -> Result<()> {
-    use crate::app::App;
-    use crossterm::{
-        event::{DisableMouseCapture, EnableMouseCapture},
-        execute,
-        terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    };
-    use ratatui::prelude::*;
-    use std::io;
-    use std::panic;
-
-    // Setup panic hook to restore terminal
-    let original_hook = panic::take_hook();
-    panic::set_hook(Box::new(move |panic_info| {
-        let _ = restore_terminal();
-        original_hook(panic_info);
-    }));
-
-    // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-
-    // Run the application
-    let mut app = App::new();
-    let result = app.run(&mut terminal).await;
-
-    // Restore terminal
-    restore_terminal()?;
-
-    result
-}
-
-fn restore_terminal() -> Result<()> {
-    use crossterm::{
-        event::DisableMouseCapture,
-        execute,
-        terminal::{disable_raw_mode, LeaveAlternateScreen},
-    };
-
-    disable_raw_mode()?;
-    execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
-    Ok(())
+async fn launch_tui(config: Config, overwrite: OverwritePolicy, use_altscreen: bool) -> Result<()> {
+    let mut app = App::new()
+        .with_config(config)
+        .with_overwrite_policy(overwrite);
+    tui::run_app(&mut app, use_altscreen).await
 }
 
 fn setup_logging(verbose: u8, quiet: bool) {
@@ -0,0 +1,212 @@
+use crate::subtitle::srt::SrtWriteOptions;
+use crate::ui::keymap::{Action, KeyBinding, KeyMap, NudgeSteps};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Autosave while editing after this many seconds with unsaved changes
+pub const DEFAULT_AUTOSAVE_SECS: u64 = 30;
+/// ...or after this many edits, whichever comes first
+pub const DEFAULT_AUTOSAVE_EDITS: u32 = 20;
+/// Editor time nudges, in milliseconds: plain keys, and with Shift held
+pub const DEFAULT_NUDGE_MS: u64 = 100;
+pub const DEFAULT_LARGE_NUDGE_MS: u64 = 1000;
+
+/// Settings read from `config.toml`. Every section and key is optional;
+/// CLI arguments take precedence over anything set here.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub whisper: WhisperConfig,
+    pub subtitles: SubtitlesConfig,
+    pub video: VideoConfig,
+    pub editor: EditorConfig,
+    pub paths: PathsConfig,
+    pub behavior: BehaviorConfig,
+    /// Editor key bindings by action; unlisted actions keep their default keys
+    pub keys: HashMap<Action, KeyBinding>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct WhisperConfig {
+    /// Base URL or local directory that model files are fetched from
+    pub model_base_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SubtitlesConfig {
+    /// Minimum gap in milliseconds kept between adjacent cues by automated timing changes
+    pub min_gap_ms: Option<u64>,
+    /// Generated cues shorter than this are extended (default: off)
+    pub min_duration_ms: Option<u64>,
+    /// Generated cues longer than this are split at sentences (default: off)
+    pub max_duration_ms: Option<u64>,
+    /// Number written for the first cue in saved SRT files (default: 1)
+    pub index_base: Option<usize>,
+    /// Zero-pad cue indices to this many digits (default: no padding)
+    pub index_width: Option<usize>,
+    /// Write cue index lines at all (default: true)
+    pub write_index: Option<bool>,
+    /// Average glyph width as a fraction of the font size, for overflow warnings
+    pub char_width_factor: Option<f64>,
+}
+
+/// How subtitles are burned into the video
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BurnMethod {
+    /// Render a compact subtitle overlay video and merge it in
+    #[default]
+    Overlay,
+    /// Run FFmpeg's subtitles filter straight on the video (faster, no overlay geometry)
+    Direct,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct VideoConfig {
+    /// Default burn method when `--use-overlay` isn't given
+    pub burn_method: BurnMethod,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct EditorConfig {
+    /// Seconds between autosaves of unsaved edits (0 disables autosave)
+    pub autosave_secs: Option<u64>,
+    /// Autosave early once this many edits are unsaved (0: time only)
+    pub autosave_edits: Option<u32>,
+    /// Milliseconds the nudge and move keys shift a cue by
+    pub nudge_ms: Option<u64>,
+    /// ...and with Shift held
+    pub large_nudge_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PathsConfig {
+    /// Directory for intermediate files (default: the system temp directory)
+    pub temp_dir: Option<PathBuf>,
+    /// Directory for the live preview's mpv IPC socket (default: the system temp directory)
+    pub socket_dir: Option<PathBuf>,
+    /// How outputs are named when no path is given (see `--output-template`)
+    pub output_template: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BehaviorConfig {
+    /// Keep intermediate files next to the input instead of a temp directory
+    pub keep_files: bool,
+    /// Keep the extracted audio next to the input and reuse it on later runs
+    pub keep_audio: bool,
+}
+
+impl Config {
+    /// Minimum gap between cues, falling back to the built-in default
+    pub fn min_gap_ms(&self) -> u64 {
+        self.subtitles
+            .min_gap_ms
+            .unwrap_or(crate::subtitle::timing::DEFAULT_MIN_GAP_MS)
+    }
+
+    /// Whether to burn with the overlay method unless told otherwise
+    pub fn use_overlay(&self) -> bool {
+        self.video.burn_method == BurnMethod::Overlay
+    }
+
+    /// Glyph width estimate used to flag lines wider than the overlay
+    pub fn char_width_factor(&self) -> f64 {
+        self.subtitles
+            .char_width_factor
+            .unwrap_or(crate::subtitle::lint::DEFAULT_CHAR_WIDTH_FACTOR)
+    }
+
+    /// How long unsaved edits may sit before they are autosaved (None: never)
+    pub fn autosave_interval(&self) -> Option<Duration> {
+        match self.editor.autosave_secs.unwrap_or(DEFAULT_AUTOSAVE_SECS) {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        }
+    }
+
+    /// Unsaved edits that trigger an autosave before the interval is up (None: never)
+    pub fn autosave_edits(&self) -> Option<u32> {
+        match self.editor.autosave_edits.unwrap_or(DEFAULT_AUTOSAVE_EDITS) {
+            0 => None,
+            edits => Some(edits),
+        }
+    }
+
+    /// Editor nudge steps, plain and with Shift
+    pub fn nudge_steps(&self) -> NudgeSteps {
+        NudgeSteps {
+            small: self.editor.nudge_ms.unwrap_or(DEFAULT_NUDGE_MS).max(1),
+            large: self
+                .editor
+                .large_nudge_ms
+                .unwrap_or(DEFAULT_LARGE_NUDGE_MS)
+                .max(1),
+        }
+    }
+
+    /// How cue indices are written to SRT files
+    pub fn srt_write_options(&self) -> SrtWriteOptions {
+        let defaults = SrtWriteOptions::default();
+        SrtWriteOptions {
+            index_base: self.subtitles.index_base.unwrap_or(defaults.index_base),
+            index_width: self.subtitles.index_width.unwrap_or(defaults.index_width),
+            write_index: self.subtitles.write_index.unwrap_or(defaults.write_index),
+        }
+    }
+
+    /// Editor key bindings: the defaults with `[keys]` applied
+    pub fn keymap(&self) -> Result<KeyMap> {
+        KeyMap::with_overrides(&self.keys).context("Invalid [keys] section")
+    }
+
+    /// Default location of the configuration file
+    pub fn default_path() -> PathBuf {
+        if let Some(config_dir) = dirs::config_dir() {
+            let app_config_dir = config_dir.join("auto-subs-tui");
+            app_config_dir.join("config.toml")
+        } else {
+            PathBuf::from("config.toml")
+        }
+    }
+
+    /// Load the configuration from `path`, or from the default location when
+    /// no path is given. A missing default file yields the built-in defaults,
+    /// but an explicitly requested file must exist.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => {
+                if !path.exists() {
+                    anyhow::bail!("Config file not found: {}", path.display());
+                }
+                path.to_path_buf()
+            }
+            None => {
+                let path = Self::default_path();
+                if !path.exists() {
+                    return Ok(Self::default());
+                }
+                path
+            }
+        };
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let config: Self = toml::from_str(&content)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+        // Catch bad key bindings now rather than when the editor opens
+        config
+            .keymap()
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+        Ok(config)
+    }
+}
@@ -1,7 +1,9 @@
 mod app;
 mod audio;
 mod cli;
+mod config;
 mod subtitle;
+mod tui;
 mod ui;
 mod utils;
 
@@ -51,11 +53,7 @@ async fn run_legacy_cli(video_path: &str) -> Result<()> {
 
     let audio_path = video_path.with_extension("wav");
     let srt_path = video_path.with_extension("srt");
-    let output_path = video_path.with_file_name(format!(
-        "{}_subtitled.{}",
-        video_path.file_stem().unwrap().to_string_lossy(),
-        video_path.extension().unwrap().to_string_lossy()
-    ));
+    let output_path = SubtitleBurner::default_output_path(video_path);
 
     // Step 1: Extract audio
     println!("\n[1/3] Extracting audio...");
@@ -71,6 +69,9 @@ async fn run_legacy_cli(video_path: &str) -> Result<()> {
     while let Ok(msg) = rx.recv() {
         match msg {
             ProgressMessage::Progress(p, m) => println!("      [{:3.0}%] {}", p * 100.0, m),
+            ProgressMessage::Segment(_)
+            | ProgressMessage::Transcript(_)
+            | ProgressMessage::Language(_) => {}
             ProgressMessage::Complete => break,
             ProgressMessage::Error(e) => {
                 println!("      ❌ Error: {}", e);
@@ -95,6 +96,9 @@ async fn run_legacy_cli(video_path: &str) -> Result<()> {
     while let Ok(msg) = rx.recv() {
         match msg {
             ProgressMessage::Progress(p, m) => println!("      [{:3.0}%] {}", p * 100.0, m),
+            ProgressMessage::Segment(_)
+            | ProgressMessage::Transcript(_)
+            | ProgressMessage::Language(_) => {}
             ProgressMessage::Complete => break,
             ProgressMessage::Error(e) => {
                 println!("      ❌ Error: {}", e);
@@ -129,6 +133,9 @@ async fn run_legacy_cli(video_path: &str) -> Result<()> {
     while let Ok(msg) = rx.recv() {
         match msg {
             ProgressMessage::Progress(p, m) => println!("      [{:3.0}%] {}", p * 100.0, m),
+            ProgressMessage::Segment(_)
+            | ProgressMessage::Transcript(_)
+            | ProgressMessage::Language(_) => {}
             ProgressMessage::Complete => break,
             ProgressMessage::Error(e) => {
                 println!("      ❌ Error: {}", e);
@@ -0,0 +1,165 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::cli::args::{SubtitlePosition, TextAlign};
+use crate::subtitle::burner::SubtitleBurner;
+use crate::subtitle::srt::Subtitle;
+use crate::subtitle::style::ass_color;
+
+/// Font size libass gives SRT input in its 384x288 canvas
+pub const LIBASS_FONT_SIZE: u32 = 16;
+
+/// Frame height a style is resolved for when there is no video to measure
+pub const DEFAULT_FRAME_HEIGHT: u32 = 1080;
+
+/// The look of exported subtitles: an ASS script's `Default` style, and the
+/// placement WebVTT cue settings are written from. Sizes are in the script's
+/// 384x288 canvas, which players scale to the video.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssStyle {
+    pub font_size: u32,
+    /// Text and outline colors as RGB
+    pub primary_color: (u8, u8, u8),
+    pub outline_color: (u8, u8, u8),
+    /// Where cues without a position override sit
+    pub position: SubtitlePosition,
+    /// Where text sits across the frame
+    pub align: TextAlign,
+}
+
+impl Default for AssStyle {
+    /// What a burn with the default settings would look like on a 1080p frame
+    fn default() -> Self {
+        SubtitleBurner::new().ass_style(DEFAULT_FRAME_HEIGHT)
+    }
+}
+
+impl AssStyle {
+    /// Numpad alignment of the style, see `ass_alignment`
+    pub fn alignment(&self) -> u8 {
+        ass_alignment(self.position, self.align)
+    }
+}
+
+/// ASS `Alignment` (numpad layout: 1-3 bottom row, 4-6 middle, 7-9 top) for
+/// text at `position`, aligned `align` across it
+pub fn ass_alignment(position: SubtitlePosition, align: TextAlign) -> u8 {
+    let row = match position {
+        SubtitlePosition::Bottom => 0,
+        SubtitlePosition::Middle => 3,
+        SubtitlePosition::Top => 6,
+    };
+    let column = match align {
+        TextAlign::Left => 1,
+        TextAlign::Center => 2,
+        TextAlign::Right => 3,
+    };
+    row + column
+}
+
+/// SRT's `<i>`, `<b>` and `<u>` tags as ASS overrides (FFmpeg converts them
+/// for SRT input, but not in an ASS file)
+pub fn ass_markup(text: &str) -> String {
+    [
+        ("<i>", "{\\i1}"),
+        ("</i>", "{\\i0}"),
+        ("<b>", "{\\b1}"),
+        ("</b>", "{\\b0}"),
+        ("<u>", "{\\u1}"),
+        ("</u>", "{\\u0}"),
+    ]
+    .iter()
+    .fold(text.to_string(), |text, (tag, ass)| text.replace(tag, ass))
+}
+
+/// ASS timestamp: H:MM:SS.cc
+pub fn ass_time(ms: u64) -> String {
+    let cs = ms / 10;
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        cs / 360_000,
+        (cs / 6_000) % 60,
+        (cs / 100) % 60,
+        cs % 100
+    )
+}
+
+/// An ASS script of `subtitles` in `style`. A cue moved away from the
+/// style's position gets an `\an` override in the style's column.
+pub fn to_ass(subtitles: &[Subtitle], style: &AssStyle) -> String {
+    let mut ass = format!(
+        "[Script Info]
+ScriptType: v4.00+
+PlayResX: 384
+PlayResY: 288
+ScaledBorderAndShadow: yes
+YCbCr Matrix: None
+
+[V4+ Styles]
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding
+Style: Default,Arial,{size},{primary},{primary},{outline},&H00000000,0,0,0,0,100,100,0,0,1,1,0,{alignment},10,10,10,1
+
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+",
+        size = style.font_size,
+        primary = ass_color(style.primary_color),
+        outline = ass_color(style.outline_color),
+        alignment = style.alignment(),
+    );
+    for sub in subtitles {
+        let tag = match sub.position {
+            Some(position) if position != style.position => {
+                format!("{{\\an{}}}", ass_alignment(position, style.align))
+            }
+            _ => String::new(),
+        };
+        ass.push_str(&format!(
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}{}\n",
+            ass_time(sub.start_time),
+            ass_time(sub.end_time),
+            tag,
+            ass_markup(&sub.text.lines().collect::<Vec<_>>().join("\\N"))
+        ));
+    }
+    ass
+}
+
+/// Save subtitles as an ASS script in `style`
+pub fn save_ass(path: &Path, subtitles: &[Subtitle], style: &AssStyle) -> Result<()> {
+    fs::write(path, to_ass(subtitles, style)).context("Failed to write ASS file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_ass() {
+        let mut top = Subtitle::new(2, 3500, 5000, "General <i>Kenobi</i>".to_string());
+        top.position = Some(SubtitlePosition::Top);
+        let cues = vec![
+            Subtitle::new(1, 1000, 3500, "Hello\nthere".to_string()),
+            top,
+        ];
+        let style = AssStyle {
+            font_size: 20,
+            primary_color: (255, 255, 0),
+            outline_color: (0, 0, 0),
+            position: SubtitlePosition::Bottom,
+            align: TextAlign::Left,
+        };
+
+        let ass = to_ass(&cues, &style);
+        assert!(ass.contains(
+            "Style: Default,Arial,20,&H0000FFFF,&H0000FFFF,&H00000000,&H00000000,\
+             0,0,0,0,100,100,0,0,1,1,0,1,10,10,10,1\n"
+        ));
+        assert!(ass.ends_with(
+            "Dialogue: 0,0:00:01.00,0:00:03.50,Default,,0,0,0,,Hello\\Nthere\n\
+             Dialogue: 0,0:00:03.50,0:00:05.00,Default,,0,0,0,,{\\an7}General {\\i1}Kenobi{\\i0}\n"
+        ));
+        assert_eq!(ass_time(3_723_450), "1:02:03.45");
+    }
+}
@@ -1,10 +1,218 @@
 use anyhow::{Context, Result};
+use std::ffi::{OsStr, OsString};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
-use std::sync::mpsc::Sender;
+use std::sync::mpsc::{self, Sender};
+use std::time::Instant;
 
 use crate::app::ProgressMessage;
+use crate::cli::args::{SubtitlePosition, TextAlign};
+use crate::subtitle::ass::{ass_alignment, AssStyle, LIBASS_FONT_SIZE};
+use crate::subtitle::format;
+use crate::subtitle::srt::{self, SrtWriteOptions};
+use crate::subtitle::style::ass_color;
+use crate::subtitle::timing;
+use crate::subtitle::wrap::WrapFont;
+use crate::utils::cancel::CancelHandle;
+use crate::utils::ffmpeg;
+use crate::utils::temp::TempWorkspace;
+
+/// Default font size as a fraction of the overlay band height
+pub const DEFAULT_FONT_SCALE: f64 = 0.38;
+/// Default lower bound for the computed font size
+pub const DEFAULT_MIN_FONT_SIZE: u32 = 24;
+/// Container used for outputs when the input has no extension to copy
+pub const DEFAULT_CONTAINER: &str = "mp4";
+/// Script height libass assumes for SRT input (FFmpeg's default PlayResY):
+/// FontSize and MarginV are in these units, scaled to the frame rendered on
+const SRT_PLAY_RES_Y: f64 = 288.0;
+/// Script width libass assumes for SRT input (FFmpeg's default PlayResX)
+const SRT_PLAY_RES_X: f64 = 384.0;
+/// FFmpeg's default MarginL and MarginR for SRT input, in script units
+const SRT_MARGIN_LR: f64 = 10.0;
+/// Window height mpv's subtitle sizes and margins are given for
+const MPV_SUB_HEIGHT: f64 = 720.0;
+
+/// Where the subtitle band sits in a preview of the video, in video pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewBand {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    pub video_width: u32,
+    pub video_height: u32,
+    font_size: u32,
+    align: TextAlign,
+}
+
+impl PreviewBand {
+    /// `drawbox` filter outlining the band
+    pub fn drawbox(&self) -> String {
+        format!(
+            "drawbox=x={}:y={}:w={}:h={}:color=yellow@0.3:t=3",
+            self.x, self.y, self.width, self.height
+        )
+    }
+
+    /// mpv subtitle options that draw cues where an overlay burn puts them.
+    /// The burn renders them into a band-sized frame, so their size and
+    /// margin scale with the band; mpv then wants both in 720-line units.
+    /// mpv can only center cues on the video, so a band moved sideways
+    /// narrows where they wrap instead of moving them. Left- or
+    /// right-aligned cues keep the band's side margin from its edge.
+    pub fn sub_options(&self) -> Vec<(&'static str, String)> {
+        let to_mpv = MPV_SUB_HEIGHT / self.video_height.max(1) as f64;
+        let band_scale = self.height as f64 / SRT_PLAY_RES_Y;
+        let font_px = self.font_size as f64 * band_scale;
+        let margin_px = band_margin(self.height) as f64 * band_scale;
+        let below_band = self.video_height.saturating_sub(self.y + self.height) as f64;
+        let left_of_band = self.x as f64;
+        let right_of_band = self.video_width.saturating_sub(self.x + self.width) as f64;
+        let side_margin_px = SRT_MARGIN_LR * self.width as f64 / SRT_PLAY_RES_X;
+        let beside_band = match self.align {
+            TextAlign::Left => left_of_band + side_margin_px,
+            TextAlign::Center => left_of_band.max(right_of_band),
+            TextAlign::Right => right_of_band + side_margin_px,
+        };
+        vec![
+            ("sub-align-x", self.align.as_str().to_string()),
+            ("sub-font-size", format!("{:.0}", font_px * to_mpv)),
+            (
+                "sub-margin-y",
+                format!("{:.0}", ((below_band + margin_px) * to_mpv).min(600.0)),
+            ),
+            (
+                "sub-margin-x",
+                format!("{:.0}", (beside_band * to_mpv).min(300.0)),
+            ),
+        ]
+    }
+}
+
+/// Bottom margin of the cues inside a band (10% of its height)
+fn band_margin(height: u32) -> u32 {
+    (height as f64 * 0.1) as u32
+}
+
+/// One subtitle file and the band of the frame it is burned into
+#[derive(Debug, Clone)]
+pub struct SubtitleTrack {
+    pub srt_path: PathBuf,
+    pub position: SubtitlePosition,
+}
+
+impl SubtitleTrack {
+    pub fn new(srt_path: &Path, position: SubtitlePosition) -> Self {
+        Self {
+            srt_path: srt_path.to_path_buf(),
+            position,
+        }
+    }
+}
+
+/// The overlay band in video pixels, placed the way the burner places it:
+/// centered horizontally at the bottom of the frame (or the top or middle
+/// for other tracks), then moved by the offsets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverlayBand {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl OverlayBand {
+    pub fn new(video: (u32, u32), width: u32, height: u32, x_offset: i32, y_offset: i32) -> Self {
+        Self::at(
+            video,
+            width,
+            height,
+            SubtitlePosition::Bottom,
+            0,
+            (x_offset, y_offset),
+        )
+    }
 
+    /// The band of a track at `position`, clear of the `stacked` earlier
+    /// tracks there: bottom bands stack upwards, top and middle ones
+    /// downwards. Offsets can't push it past the top or left edge, and a
+    /// band larger than the frame starts at its corner.
+    pub fn at(
+        video: (u32, u32),
+        width: u32,
+        height: u32,
+        position: SubtitlePosition,
+        stacked: u32,
+        offset: (i32, i32),
+    ) -> Self {
+        let (x_range, y_range) = Self::offset_limits(video, width, height);
+        let y_bottom = -y_range.0;
+        let y_band = match position {
+            SubtitlePosition::Top => 0,
+            SubtitlePosition::Middle => y_bottom / 2,
+            SubtitlePosition::Bottom => y_bottom,
+        };
+        let y_stack = match position {
+            SubtitlePosition::Bottom => -(stacked as i32) * height as i32,
+            _ => stacked as i32 * height as i32,
+        };
+        Self {
+            x: (offset.0 - x_range.0).max(0),
+            y: (y_band + y_stack + offset.1).max(0),
+            width,
+            height,
+        }
+    }
+
+    /// Smallest and largest X and Y offsets that keep a band of this size
+    /// inside the frame
+    pub fn offset_limits(video: (u32, u32), width: u32, height: u32) -> ((i32, i32), (i32, i32)) {
+        let x_centered = (video.0.saturating_sub(width) / 2) as i32;
+        let x_free = video.0.saturating_sub(width) as i32;
+        let y_bottom = video.1.saturating_sub(height) as i32;
+        ((-x_centered, x_free - x_centered), (-y_bottom, 0))
+    }
+}
+
+/// Length of the test encode a `BurnEstimate` is scaled up from
+pub const ESTIMATE_SAMPLE_MS: u64 = 2000;
+
+/// What burning a whole video should cost, predicted from a short test encode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BurnEstimate {
+    /// Length of the video in milliseconds
+    pub duration_ms: u64,
+    pub width: u32,
+    pub height: u32,
+    /// Predicted size of the output in bytes
+    pub output_bytes: u64,
+    /// Predicted wall-clock encode time in seconds
+    pub encode_secs: f64,
+}
+
+impl BurnEstimate {
+    /// Scale a test encode of `sample_ms` of video, which wrote `sample_bytes`
+    /// in `sample_secs`, up to the whole `duration_ms`
+    fn from_sample(
+        sample_ms: u64,
+        sample_bytes: u64,
+        sample_secs: f64,
+        duration_ms: u64,
+        (width, height): (u32, u32),
+    ) -> Self {
+        let scale = duration_ms as f64 / sample_ms.max(1) as f64;
+        Self {
+            duration_ms,
+            width,
+            height,
+            output_bytes: (sample_bytes as f64 * scale).round() as u64,
+            encode_secs: sample_secs * scale,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct SubtitleBurner {
     pub use_overlay: bool,
     pub keep_overlay: bool,
@@ -12,6 +220,37 @@ pub struct SubtitleBurner {
     pub overlay_width: Option<u32>,
     pub overlay_x_offset: Option<i32>,
     pub overlay_y_offset: Option<i32>,
+    pub font_scale: Option<f64>,
+    pub min_font_size: Option<u32>,
+    pub temp_dir: Option<PathBuf>,
+    /// Only burn this window of the video: (start, duration) in milliseconds
+    pub time_range: Option<(u64, u64)>,
+    /// Text and outline colors as RGB (default: libass' white on black)
+    pub font_color: Option<(u8, u8, u8)>,
+    pub outline_color: Option<(u8, u8, u8)>,
+    /// Color karaoke `\k` runs fill words with as they are sung; the font
+    /// color is then the one they start in
+    pub karaoke_color: Option<(u8, u8, u8)>,
+    /// Outline thickness in pixels (default: libass' own)
+    pub outline: Option<f32>,
+    /// Shadow depth in pixels (default: libass' own)
+    pub shadow: Option<f32>,
+    /// Horizontal alignment of the text within its band (default: centered)
+    pub text_align: Option<TextAlign>,
+    /// Extra ASS style overrides appended to `force_style`
+    pub style: Option<String>,
+    /// Constant Rate Factor for the final encode (default: the encoder's own)
+    pub crf: Option<u32>,
+    /// Encoder speed preset for the final encode, e.g. "medium"
+    pub preset: Option<String>,
+    /// Target bitrate in bits/s for the final encode, used when no CRF is set
+    pub video_bitrate: Option<u64>,
+    /// Overlay bitrate in bits/s (default: scaled to the overlay's size and frame rate)
+    pub overlay_bitrate: Option<u64>,
+    /// Break cues at spaces to fit the band before burning them
+    pub wrap: Option<WrapFont>,
+    /// Kills the running FFmpeg process when the job is cancelled
+    cancel: CancelHandle,
 }
 
 impl SubtitleBurner {
@@ -23,6 +262,23 @@ impl SubtitleBurner {
             overlay_width: None,
             overlay_x_offset: None,
             overlay_y_offset: None,
+            font_scale: None,
+            min_font_size: None,
+            temp_dir: None,
+            time_range: None,
+            font_color: None,
+            outline_color: None,
+            karaoke_color: None,
+            outline: None,
+            shadow: None,
+            text_align: None,
+            style: None,
+            crf: None,
+            preset: None,
+            video_bitrate: None,
+            overlay_bitrate: None,
+            wrap: None,
+            cancel: CancelHandle::new(),
         }
     }
 
@@ -56,6 +312,276 @@ impl SubtitleBurner {
         self
     }
 
+    /// Font size as a fraction of the overlay band height (default 0.38)
+    pub fn with_font_scale(mut self, scale: f64) -> Self {
+        self.font_scale = Some(scale);
+        self
+    }
+
+    /// Smallest font size the scaling is allowed to produce (default 24)
+    pub fn with_min_font_size(mut self, size: u32) -> Self {
+        self.min_font_size = Some(size);
+        self
+    }
+
+    /// Burn only `duration_ms` of the video starting at `start_ms`, for quick
+    /// test renders. Subtitle timings are shifted to match the excerpt.
+    pub fn with_time_range(mut self, start_ms: u64, duration_ms: u64) -> Self {
+        self.time_range = Some((start_ms, duration_ms));
+        self
+    }
+
+    /// Text color, e.g. from `style::parse_hex_color`
+    pub fn with_font_color(mut self, rgb: (u8, u8, u8)) -> Self {
+        self.font_color = Some(rgb);
+        self
+    }
+
+    /// Outline color, e.g. from `style::parse_hex_color`
+    pub fn with_outline_color(mut self, rgb: (u8, u8, u8)) -> Self {
+        self.outline_color = Some(rgb);
+        self
+    }
+
+    /// Sung-word color for a karaoke ASS file, e.g. from `style::parse_hex_color`
+    pub fn with_karaoke_color(mut self, rgb: (u8, u8, u8)) -> Self {
+        self.karaoke_color = Some(rgb);
+        self
+    }
+
+    /// Outline thickness around the text in pixels
+    pub fn with_outline(mut self, px: f32) -> Self {
+        self.outline = Some(px);
+        self
+    }
+
+    /// Drop shadow depth in pixels
+    pub fn with_shadow(mut self, px: f32) -> Self {
+        self.shadow = Some(px);
+        self
+    }
+
+    /// Align the text to the left, center or right of its band
+    pub fn with_text_align(mut self, align: TextAlign) -> Self {
+        self.text_align = Some(align);
+        self
+    }
+
+    /// Extra ASS style overrides (e.g. "FontName=Arial,Outline=2"), applied
+    /// after the computed font size so they can override it
+    pub fn with_style(mut self, style: &str) -> Self {
+        if !style.is_empty() {
+            self.style = Some(style.to_string());
+        }
+        self
+    }
+
+    /// Encode the output at this Constant Rate Factor (lower = better quality)
+    pub fn with_crf(mut self, crf: u32) -> Self {
+        self.crf = Some(crf);
+        self
+    }
+
+    /// Encoder speed preset for the output (ultrafast ... veryslow)
+    pub fn with_preset(mut self, preset: &str) -> Self {
+        self.preset = Some(preset.to_string());
+        self
+    }
+
+    /// Encode the output at this bitrate (bits/s) instead of the encoder's
+    /// default quality; a CRF takes precedence
+    pub fn with_video_bitrate(mut self, bits_per_sec: u64) -> Self {
+        self.video_bitrate = Some(bits_per_sec);
+        self
+    }
+
+    /// Encode the subtitle overlay at this bitrate (bits/s)
+    pub fn with_overlay_bitrate(mut self, bits_per_sec: u64) -> Self {
+        self.overlay_bitrate = Some(bits_per_sec);
+        self
+    }
+
+    /// Run FFmpeg through `cancel`, so the job can be stopped from the UI
+    pub fn with_cancel(mut self, cancel: CancelHandle) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Directory for the intermediate overlay (default: the system temp directory)
+    pub fn with_temp_dir(mut self, dir: PathBuf) -> Self {
+        self.temp_dir = Some(dir);
+        self
+    }
+
+    /// Wrap the burned cues to the band's width at the resolved font size,
+    /// measured with `font`. The subtitle files themselves are left alone.
+    pub fn with_wrap(mut self, font: WrapFont) -> Self {
+        self.wrap = Some(font);
+        self
+    }
+
+    /// Default output for `video`: `<stem>_subtitled.<ext>` next to it, using
+    /// mp4 when the input has no extension
+    pub fn default_output_path(video_path: &Path) -> PathBuf {
+        let stem = video_path.file_stem().unwrap_or_default().to_string_lossy();
+        video_path.with_file_name(format!(
+            "{}_subtitled.{}",
+            stem,
+            Self::output_extension(video_path)
+        ))
+    }
+
+    /// Extension of the burned copy of `video`: the same as the input's, or
+    /// mp4 when it has none
+    pub fn output_extension(video_path: &Path) -> String {
+        video_path
+            .extension()
+            .map_or(DEFAULT_CONTAINER.to_string(), |e| {
+                e.to_string_lossy().to_string()
+            })
+    }
+
+    /// Output when burning only `start_ms..end_ms` of what would go to
+    /// `output_path`: `<stem>_<start>s-<end>s.<ext>`
+    pub fn clip_output_path(output_path: &Path, start_ms: u64, end_ms: u64) -> PathBuf {
+        let stem = output_path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy();
+        let extension = output_path
+            .extension()
+            .unwrap_or_default()
+            .to_string_lossy();
+        output_path.with_file_name(format!(
+            "{}_{}s-{}s.{}",
+            stem,
+            start_ms / 1000,
+            end_ms.div_ceil(1000),
+            extension
+        ))
+    }
+
+    /// Default standalone overlay for `video`: `<stem>_overlay.webm` next to it
+    pub fn overlay_output_path(video_path: &Path) -> PathBuf {
+        let stem = video_path.file_stem().unwrap_or_default().to_string_lossy();
+        video_path.with_file_name(format!("{}_overlay.webm", stem))
+    }
+
+    /// Where the overlay is saved next to `output_path` when it is kept
+    pub fn kept_overlay_path(output_path: &Path) -> PathBuf {
+        Self::kept_overlay_path_for_track(output_path, 0)
+    }
+
+    /// Kept overlay path for the track at `index`; extra tracks get a number
+    pub fn kept_overlay_path_for_track(output_path: &Path, index: usize) -> PathBuf {
+        let stem = output_path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy();
+        if index == 0 {
+            output_path.with_file_name(format!("{}_overlay.mp4", stem))
+        } else {
+            output_path.with_file_name(format!("{}_overlay_{}.mp4", stem, index + 1))
+        }
+    }
+
+    /// Resolve the subtitle font size for an overlay band of the given height
+    pub fn font_size_for_height(&self, height: u32) -> u32 {
+        let scale = self.font_scale.unwrap_or(DEFAULT_FONT_SCALE);
+        let min_size = self.min_font_size.unwrap_or(DEFAULT_MIN_FONT_SIZE);
+        (height as f64 * scale).max(min_size as f64) as u32
+    }
+
+    /// The burn's look as a style for exported subtitles, which players show
+    /// over a whole frame `video_height` tall: the band's font size scaled to
+    /// that frame (libass' own when a direct burn keeps it), the colors, and
+    /// the text alignment
+    pub fn ass_style(&self, video_height: u32) -> AssStyle {
+        let band_height = self.overlay_height.unwrap_or(200).min(video_height).max(1);
+        let font_size = if self.use_overlay {
+            // The band is rendered in a 384x288 canvas of its own
+            self.font_size_for_height(band_height) * band_height / video_height.max(1)
+        } else if self.font_scale.is_some() || self.min_font_size.is_some() {
+            self.font_size_for_height(band_height)
+        } else {
+            LIBASS_FONT_SIZE
+        };
+        AssStyle {
+            font_size: font_size.max(1),
+            primary_color: self.font_color.unwrap_or((255, 255, 255)),
+            outline_color: self.outline_color.unwrap_or((0, 0, 0)),
+            position: SubtitlePosition::Bottom,
+            align: self.text_align.unwrap_or_default(),
+        }
+    }
+
+    /// The font size cues are drawn at on a `video` frame, in pixels, and
+    /// the widest a line can be between the side margins: the band scaled
+    /// up from libass' 384x288 canvas, or the whole frame for a direct burn
+    pub fn text_box(&self, video: (u32, u32)) -> (f64, f64) {
+        let band_height = self.overlay_height.unwrap_or(200).min(video.1).max(1);
+        let (width, height, font_size) = if self.use_overlay {
+            (
+                self.overlay_width.unwrap_or(video.0),
+                band_height,
+                self.font_size_for_height(band_height),
+            )
+        } else if self.font_scale.is_some() || self.min_font_size.is_some() {
+            (video.0, video.1, self.font_size_for_height(band_height))
+        } else {
+            (video.0, video.1, LIBASS_FONT_SIZE)
+        };
+        let font_px = font_size as f64 * height as f64 / SRT_PLAY_RES_Y;
+        let side_margin_px = SRT_MARGIN_LR * width as f64 / SRT_PLAY_RES_X;
+        (font_px, width as f64 - 2.0 * side_margin_px)
+    }
+
+    /// `force_style` value for a band: font size, margin and alignment, then
+    /// any user style
+    fn force_style(&self, font_size: u32, margin_v: u32) -> String {
+        let mut style = vec![
+            format!("FontSize={}", font_size),
+            format!("MarginV={}", margin_v),
+        ];
+        if let Some(align) = self.text_align {
+            // Cues sit on the band's bottom row; MarginL/MarginR keep
+            // left- and right-aligned ones off its edges
+            style.push(format!(
+                "Alignment={}",
+                ass_alignment(SubtitlePosition::Bottom, align)
+            ));
+        }
+        style.extend(self.user_style());
+        style.join(",")
+    }
+
+    /// Colors, outline, shadow and `--style` overrides, in the order they apply
+    fn user_style(&self) -> Vec<String> {
+        let mut style = Vec::new();
+        if let Some(rgb) = self.karaoke_color {
+            // `\k` runs fill words from SecondaryColour to PrimaryColour
+            style.push(format!("PrimaryColour={}", ass_color(rgb)));
+            if let Some(rgb) = self.font_color {
+                style.push(format!("SecondaryColour={}", ass_color(rgb)));
+            }
+        } else if let Some(rgb) = self.font_color {
+            style.push(format!("PrimaryColour={}", ass_color(rgb)));
+        }
+        if let Some(rgb) = self.outline_color {
+            style.push(format!("OutlineColour={}", ass_color(rgb)));
+        }
+        if let Some(outline) = self.outline {
+            style.push(format!("Outline={}", outline));
+        }
+        if let Some(shadow) = self.shadow {
+            style.push(format!("Shadow={}", shadow));
+        }
+        if let Some(extra) = &self.style {
+            style.push(extra.clone());
+        }
+        style
+    }
+
     /// Preview video with overlay positioned (launches external player)
     pub fn preview_with_overlay(
         &self,
@@ -72,64 +598,48 @@ impl SubtitleBurner {
         let (video_width, video_height) = self.get_video_dimensions(video_path)?;
 
         // Calculate overlay dimensions and position
-        let overlay_height = self.overlay_height.unwrap_or(200);
-        let overlay_width = self.overlay_width.unwrap_or(video_width);
-
-        let x_offset = self.overlay_x_offset.unwrap_or(0);
-        let x_centered = ((video_width - overlay_width) / 2) as i32;
-        let x_position = (x_centered + x_offset).max(0);
-
-        let y_offset = self.overlay_y_offset.unwrap_or(0);
-        let y_bottom = (video_height - overlay_height) as i32;
-        let y_position = (y_bottom + y_offset).max(0);
-
-        // Escape the SRT path for FFmpeg filter
-        let srt_path_str = srt_path
-            .to_str()
-            .unwrap()
-            .replace("\\", "/")
-            .replace(":", "\\:");
+        let band = self.overlay_band(video_width, video_height);
 
         // Calculate font size based on overlay height
-        let font_size = (overlay_height as f64 * 0.38).max(24.0) as u32;
-        let margin_v = (overlay_height as f64 * 0.1) as u32;
+        let font_size = self.font_size_for_height(band.height);
+        let margin_v = band_margin(band.height);
 
         let _ = progress_tx.send(ProgressMessage::Progress(
             0.5,
             format!(
                 "Launching preview (Overlay: {}x{} at {},{})",
-                overlay_width, overlay_height, x_position, y_position
+                band.width, band.height, band.x, band.y
             ),
         ));
 
         // Create filter to overlay subtitles directly on video
         // This creates a transparent overlay and positions it
         let filter = format!(
-            "subtitles='{}':force_style='FontSize={},MarginV={}'",
-            srt_path_str, font_size, margin_v
+            "subtitles={}:force_style='{}'",
+            filter_path(srt_path),
+            self.force_style(font_size, margin_v)
         );
 
-        // Try ffplay first, then mpv as fallback
-        let player_result = self.try_launch_player(
-            video_path,
-            &filter,
-            "ffplay",
-            &[
-                "-i",
-                video_path.to_str().unwrap(),
-                "-vf",
+        // Prefer ffplay, fall back to mpv
+        if Self::player_available("ffplay") {
+            self.try_launch_player(
+                video_path,
                 &filter,
-                "-window_title",
-                "Subtitle Preview (Press Q to close)",
-                "-autoexit",
-            ],
-        );
-
-        if player_result.is_err() {
-            // Try mpv as fallback
+                "ffplay",
+                &[
+                    OsStr::new("-i"),
+                    video_path.as_os_str(),
+                    OsStr::new("-vf"),
+                    OsStr::new(&filter),
+                    OsStr::new("-window_title"),
+                    OsStr::new("Subtitle Preview (Press Q to close)"),
+                    OsStr::new("-autoexit"),
+                ],
+            )?;
+        } else if Self::player_available("mpv") {
             let _ = progress_tx.send(ProgressMessage::Progress(
                 0.6,
-                "ffplay not found, trying mpv...".to_string(),
+                "ffplay not found, using mpv...".to_string(),
             ));
 
             self.try_launch_player(
@@ -137,12 +647,16 @@ impl SubtitleBurner {
                 &filter,
                 "mpv",
                 &[
-                    video_path.to_str().unwrap(),
-                    &format!("--vf=lavfi=[{}]", filter),
-                    "--title=Subtitle Preview (Press Q to close)",
-                    "--keep-open=no",
+                    video_path.as_os_str(),
+                    OsStr::new(&format!("--vf=lavfi=[{}]", filter)),
+                    OsStr::new("--title=Subtitle Preview (Press Q to close)"),
+                    OsStr::new("--keep-open=no"),
                 ],
             )?;
+        } else {
+            anyhow::bail!(
+                "Neither ffplay nor mpv is installed. Install FFmpeg (which ships ffplay) or mpv to preview subtitles."
+            );
         }
 
         let _ = progress_tx.send(ProgressMessage::Progress(1.0, "Preview closed".to_string()));
@@ -158,62 +672,64 @@ impl SubtitleBurner {
         video_path: &Path,
         srt_path: &Path,
         socket_path: &Path,
-    ) -> Result<(Child, u32, u32)> {
-        // Get video dimensions
+    ) -> Result<(Child, PreviewBand)> {
         let (video_width, video_height) = self.get_video_dimensions(video_path)?;
-
-        // Calculate overlay dimensions and position
-        let overlay_height = self.overlay_height.unwrap_or(200);
-        let overlay_width = self.overlay_width.unwrap_or(video_width);
-
-        let x_offset = self.overlay_x_offset.unwrap_or(0);
-        let x_centered = if overlay_width > video_width {
-            0
-        } else {
-            ((video_width - overlay_width) / 2) as i32
-        };
-        let x_position = (x_centered + x_offset).max(0);
-
-        let y_offset = self.overlay_y_offset.unwrap_or(0);
-        let y_bottom = if overlay_height > video_height {
-            0
-        } else {
-            (video_height - overlay_height) as i32
-        };
-        let y_position = (y_bottom + y_offset).max(0);
-
-        // Calculate font size based on overlay height
-        let font_size = (overlay_height as f64 * 0.38).max(24.0) as u32;
-
-        // Create drawbox filter to show subtitle area
-        let drawbox_filter = format!(
-            "drawbox=x={}:y={}:w={}:h={}:color=yellow@0.3:t=3",
-            x_position, y_position, overlay_width, overlay_height
-        );
+        let band = self.preview_band(video_width, video_height);
 
         // Launch MPV with IPC socket
         // Use simple approach: subtitles via --sub-file, overlay via --vf
-        // DEBUG: Don't suppress stderr to see errors
+        // stderr is piped for error reporting; inheriting it would draw
+        // over the TUI's alternate screen
         let child = Command::new("mpv")
-            .arg(format!(
-                "--input-ipc-server={}",
-                socket_path.to_str().unwrap()
-            ))
+            .arg(prefixed_arg("--input-ipc-server=", socket_path))
             .arg("--loop-file=inf")
             .arg("--keep-open=yes")
             .arg(format!(
                 "--title=Preview - Adjust: h/H w/W x/X y/Y (p=stop)"
             ))
-            .arg(format!("--sub-file={}", srt_path.to_str().unwrap()))
-            .arg(format!("--sub-font-size={}", font_size))
-            .arg(format!("--vf={}", drawbox_filter))
-            .arg(video_path.to_str().unwrap())
-            // Temporarily show errors for debugging
-            .stderr(Stdio::inherit())
+            .arg(prefixed_arg("--sub-file=", srt_path))
+            .arg("--sub-align-y=bottom")
+            .args(
+                band.sub_options()
+                    .into_iter()
+                    .map(|(name, value)| format!("--{}={}", name, value)),
+            )
+            .arg(format!("--vf={}", band.drawbox()))
+            .arg(video_path)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
             .spawn()
             .context("Failed to launch MPV. Please install mpv.")?;
 
-        Ok((child, video_width, video_height))
+        Ok((child, band))
+    }
+
+    /// The band subtitles are burned into on a `video_width`×`video_height`
+    /// video, with the cues' font size, as the preview shows it
+    pub fn preview_band(&self, video_width: u32, video_height: u32) -> PreviewBand {
+        let band = self.overlay_band(video_width, video_height);
+        PreviewBand {
+            x: band.x as u32,
+            y: band.y as u32,
+            width: band.width,
+            height: band.height,
+            video_width,
+            video_height,
+            font_size: self.font_size_for_height(band.height),
+            align: self.text_align.unwrap_or_default(),
+        }
+    }
+
+    /// The bottom track's overlay band on a `video_width`×`video_height`
+    /// video, from the configured size and offsets
+    fn overlay_band(&self, video_width: u32, video_height: u32) -> OverlayBand {
+        OverlayBand::new(
+            (video_width, video_height),
+            self.overlay_width.unwrap_or(video_width),
+            self.overlay_height.unwrap_or(200),
+            self.overlay_x_offset.unwrap_or(0),
+            self.overlay_y_offset.unwrap_or(0),
+        )
     }
 
     /// Launch preview process without blocking - returns the Child process
@@ -222,50 +738,23 @@ impl SubtitleBurner {
         // Get video dimensions
         let (video_width, video_height) = self.get_video_dimensions(video_path)?;
 
-        // Calculate overlay dimensions and position
-        let overlay_height = self.overlay_height.unwrap_or(200);
-        let overlay_width = self.overlay_width.unwrap_or(video_width);
-
-        let x_offset = self.overlay_x_offset.unwrap_or(0);
-        // Prevent underflow if overlay_width > video_width
-        let x_centered = if overlay_width > video_width {
-            0
-        } else {
-            ((video_width - overlay_width) / 2) as i32
-        };
-        let _x_position = (x_centered + x_offset).max(0);
-
-        let y_offset = self.overlay_y_offset.unwrap_or(0);
-        // Prevent underflow if overlay_height > video_height
-        let y_bottom = if overlay_height > video_height {
-            0
-        } else {
-            (video_height - overlay_height) as i32
-        };
-        let _y_position = (y_bottom + y_offset).max(0);
-
-        // Escape the SRT path for FFmpeg filter
-        let srt_path_str = srt_path
-            .to_str()
-            .unwrap()
-            .replace("\\", "/")
-            .replace(":", "\\:");
-
         // Calculate font size based on overlay height
-        let font_size = (overlay_height as f64 * 0.38).max(24.0) as u32;
-        let margin_v = (overlay_height as f64 * 0.1) as u32;
+        let overlay_height = self.overlay_band(video_width, video_height).height;
+        let font_size = self.font_size_for_height(overlay_height);
+        let margin_v = band_margin(overlay_height);
 
         // Create filter to overlay subtitles directly on video
         let filter = format!(
-            "subtitles='{}':force_style='FontSize={},MarginV={}'",
-            srt_path_str, font_size, margin_v
+            "subtitles={}:force_style='{}'",
+            filter_path(srt_path),
+            self.force_style(font_size, margin_v)
         );
 
         // Try ffplay first
         let child = Command::new("ffplay")
+            .arg("-i")
+            .arg(video_path)
             .args([
-                "-i",
-                video_path.to_str().unwrap(),
                 "-vf",
                 &filter,
                 "-window_title",
@@ -281,8 +770,8 @@ impl SubtitleBurner {
             Err(_) => {
                 // Try mpv as fallback
                 Ok(Command::new("mpv")
+                    .arg(video_path)
                     .args([
-                        video_path.to_str().unwrap(),
                         &format!("--vf=lavfi=[{}]", filter),
                         "--title=Subtitle Preview (Press Q to close, or P in editor to stop)",
                         "--keep-open=no",
@@ -295,12 +784,46 @@ impl SubtitleBurner {
         }
     }
 
+    /// Open a video in ffplay (or mpv) without waiting for it to close
+    pub fn launch_player(video_path: &Path) -> Result<Child> {
+        let mut command = if Self::player_available("ffplay") {
+            let mut command = Command::new("ffplay");
+            command
+                .arg("-i")
+                .arg(video_path)
+                .args(["-window_title", "Test Burn", "-autoexit"]);
+            command
+        } else if Self::player_available("mpv") {
+            let mut command = Command::new("mpv");
+            command.arg(video_path).arg("--title=Test Burn");
+            command
+        } else {
+            anyhow::bail!("Neither ffplay nor mpv is installed to play the test burn");
+        };
+
+        command
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to launch player")
+    }
+
+    /// Check whether an external player binary can be executed
+    fn player_available(player: &str) -> bool {
+        Command::new(player)
+            .arg("-version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok()
+    }
+
     fn try_launch_player(
         &self,
         _video_path: &Path,
         _filter: &str,
         player: &str,
-        args: &[&str],
+        args: &[&OsStr],
     ) -> Result<()> {
         // Check if player exists
         Command::new(player)
@@ -332,13 +855,100 @@ impl SubtitleBurner {
         output_path: &Path,
         progress_tx: Sender<ProgressMessage>,
     ) -> Result<()> {
+        let tracks = [SubtitleTrack::new(srt_path, SubtitlePosition::Bottom)];
+        self.burn_tracks(video_path, &tracks, output_path, progress_tx)
+    }
+
+    /// Burn several subtitle files at once, each into its own band
+    /// (e.g. forced subtitles at the top and dialogue at the bottom)
+    pub fn burn_tracks(
+        &self,
+        video_path: &Path,
+        tracks: &[SubtitleTrack],
+        output_path: &Path,
+        progress_tx: Sender<ProgressMessage>,
+    ) -> Result<()> {
+        if tracks.is_empty() {
+            anyhow::bail!("No subtitle files to burn");
+        }
+
+        // For an excerpt, burn copies of the subtitles shifted to its start,
+        // and for a wrap, copies broken to fit the band
+        let workspace;
+        let prepared_tracks;
+        let tracks = if self.time_range.is_some() || self.wrap.is_some() {
+            let text_box = match self.wrap {
+                Some(_) => Some(self.text_box(self.get_video_dimensions(video_path)?)),
+                None => None,
+            };
+            workspace = TempWorkspace::new(self.temp_dir.as_deref())?;
+            prepared_tracks = tracks
+                .iter()
+                .enumerate()
+                .map(|(i, track)| {
+                    let mut subtitles = format::load_subtitles(&track.srt_path, None)?;
+                    if let Some((start_ms, duration_ms)) = self.time_range {
+                        subtitles = timing::clip_window(&subtitles, start_ms, duration_ms);
+                    }
+                    if let (Some(font), Some((font_px, max_px))) = (&self.wrap, text_box) {
+                        font.wrap_all(&mut subtitles, font_px, max_px);
+                    }
+                    let path = workspace.file(&format!("burn_{}.srt", i + 1));
+                    srt::save_srt(&path, &subtitles, &SrtWriteOptions::default())?;
+                    Ok(SubtitleTrack::new(&path, track.position))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            &prepared_tracks[..]
+        } else {
+            tracks
+        };
+
         if self.use_overlay {
-            self.burn_with_overlay(video_path, srt_path, output_path, progress_tx)
+            self.burn_with_overlay(video_path, tracks, output_path, progress_tx)
         } else {
-            self.burn_direct(video_path, srt_path, output_path, progress_tx)
+            self.burn_direct(video_path, tracks, output_path, progress_tx)
         }
     }
 
+    /// Predict the size and encode time of burning `tracks` into the whole
+    /// video, by burning `ESTIMATE_SAMPLE_MS` from its middle with these
+    /// settings into a temporary file. Only the test encode is written.
+    pub fn estimate(
+        &self,
+        video_path: &Path,
+        tracks: &[SubtitleTrack],
+        output_path: &Path,
+    ) -> Result<BurnEstimate> {
+        let duration_ms = self.get_video_duration_ms(video_path)?;
+        let dimensions = self.get_video_dimensions(video_path)?;
+        let sample_ms = ESTIMATE_SAMPLE_MS.min(duration_ms);
+        let start_ms = (duration_ms / 2).saturating_sub(sample_ms / 2);
+
+        let workspace = TempWorkspace::new(self.temp_dir.as_deref())?;
+        let sample_path =
+            workspace.file(&format!("sample.{}", Self::output_extension(output_path)));
+        let mut sampler = self.clone().with_time_range(start_ms, sample_ms);
+        sampler.keep_overlay = false;
+        // Nobody watches a test encode's progress
+        let (progress_tx, _progress_rx) = mpsc::channel();
+        let started = Instant::now();
+        sampler
+            .burn_tracks(video_path, tracks, &sample_path, progress_tx)
+            .context("Test encode failed")?;
+        let sample_secs = started.elapsed().as_secs_f64();
+        let sample_bytes = std::fs::metadata(&sample_path)
+            .context("Test encode wrote no output")?
+            .len();
+
+        Ok(BurnEstimate::from_sample(
+            sample_ms,
+            sample_bytes,
+            sample_secs,
+            duration_ms,
+            dimensions,
+        ))
+    }
+
     /// Extract subtitle overlay only without burning into video
     pub fn extract_overlay(
         &self,
@@ -347,6 +957,18 @@ impl SubtitleBurner {
         output_path: &Path,
         progress_tx: Sender<ProgressMessage>,
     ) -> Result<()> {
+        // MP4 and most other containers silently drop the alpha channel
+        let container = output_path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if !OVERLAY_CONTAINERS.contains(&container.as_str()) {
+            anyhow::bail!(
+                "Can't keep the overlay's transparency in .{}; use .webm, .mkv or .mov",
+                container
+            );
+        }
+
         let _ = progress_tx.send(ProgressMessage::Progress(
             0.05,
             "Creating subtitle overlay...".to_string(),
@@ -373,7 +995,7 @@ impl SubtitleBurner {
         ));
 
         // Create the subtitle overlay
-        self.create_subtitle_overlay(
+        let warnings = self.create_subtitle_overlay(
             video_path,
             srt_path,
             output_path,
@@ -384,7 +1006,7 @@ impl SubtitleBurner {
 
         let _ = progress_tx.send(ProgressMessage::Progress(
             1.0,
-            format!("Overlay saved to: {}", output_path.display()),
+            saved_message("Overlay", output_path, &warnings),
         ));
         let _ = progress_tx.send(ProgressMessage::Complete);
 
@@ -395,7 +1017,7 @@ impl SubtitleBurner {
     fn burn_with_overlay(
         &self,
         video_path: &Path,
-        srt_path: &Path,
+        tracks: &[SubtitleTrack],
         output_path: &Path,
         progress_tx: Sender<ProgressMessage>,
     ) -> Result<()> {
@@ -420,59 +1042,98 @@ impl SubtitleBurner {
             ),
         ));
 
-        // Create temporary overlay file path
-        let overlay_path = output_path.with_file_name(format!(
-            "{}_overlay.mp4",
-            output_path.file_stem().unwrap().to_string_lossy()
-        ));
-
-        // Step 1: Create compact overlay video with subtitles
-        let _ = progress_tx.send(ProgressMessage::Progress(
-            0.2,
-            "Step 1/2: Creating compact subtitle overlay...".to_string(),
-        ));
+        // The overlay only sits next to the output when the user keeps it;
+        // otherwise it lives in a temp workspace removed when this returns
+        let workspace = if self.keep_overlay {
+            None
+        } else {
+            Some(TempWorkspace::new(self.temp_dir.as_deref())?)
+        };
+        let overlay_paths: Vec<PathBuf> = (0..tracks.len())
+            .map(|i| match &workspace {
+                Some(workspace) => workspace.file(&format!("overlay_{}.mp4", i + 1)),
+                None => Self::kept_overlay_path_for_track(output_path, i),
+            })
+            .collect();
+
+        // Step 1: Create one compact overlay video per subtitle track
+        let mut warnings = Vec::new();
+        for (i, (track, overlay_path)) in tracks.iter().zip(&overlay_paths).enumerate() {
+            let _ = progress_tx.send(ProgressMessage::Progress(
+                0.2 + 0.4 * i as f32 / tracks.len() as f32,
+                if tracks.len() > 1 {
+                    format!(
+                        "Step 1/2: Creating subtitle overlay {}/{} ({})...",
+                        i + 1,
+                        tracks.len(),
+                        track.position.as_str()
+                    )
+                } else {
+                    "Step 1/2: Creating compact subtitle overlay...".to_string()
+                },
+            ));
 
-        self.create_subtitle_overlay(
-            video_path,
-            srt_path,
-            &overlay_path,
-            overlay_width,
-            overlay_height,
-            width, // Pass full width for proper font scaling
-        )?;
+            warnings.extend(self.create_subtitle_overlay(
+                video_path,
+                &track.srt_path,
+                overlay_path,
+                overlay_width,
+                overlay_height,
+                width, // Pass full width for proper font scaling
+            )?);
+        }
 
         let _ = progress_tx.send(ProgressMessage::Progress(
             0.6,
-            "Step 2/2: Merging overlay at bottom of video...".to_string(),
+            "Step 2/2: Merging overlays into video...".to_string(),
         ));
 
-        // Step 2: Position overlay at bottom of video
-        self.merge_overlay(video_path, &overlay_path, output_path, width, height)?;
+        // Step 2: Position each overlay in its band
+        let (output_args, warning) = self.output_codec_args(video_path, output_path);
+        if let Some(warning) = warning {
+            let _ = progress_tx.send(ProgressMessage::Progress(0.6, warning));
+        }
+        let overlays: Vec<(PathBuf, SubtitlePosition)> = overlay_paths
+            .iter()
+            .cloned()
+            .zip(tracks.iter().map(|t| t.position))
+            .collect();
+        warnings.extend(self.merge_overlay(
+            video_path,
+            &overlays,
+            output_path,
+            width,
+            height,
+            &output_args,
+        )?);
 
         // Cleanup temporary overlay file unless user wants to keep it
-        if !self.keep_overlay {
-            let _ = std::fs::remove_file(&overlay_path);
+        if let Some(workspace) = workspace {
+            drop(workspace);
             let _ = progress_tx.send(ProgressMessage::Progress(
                 0.95,
                 "Cleaned up temporary overlay file".to_string(),
             ));
         } else {
-            let _ = progress_tx.send(ProgressMessage::Progress(
-                0.95,
-                format!("Overlay saved to: {}", overlay_path.display()),
-            ));
+            for overlay_path in &overlay_paths {
+                let _ = progress_tx.send(ProgressMessage::Progress(
+                    0.95,
+                    format!("Overlay saved to: {}", overlay_path.display()),
+                ));
+            }
         }
 
         let _ = progress_tx.send(ProgressMessage::Progress(
             1.0,
-            format!("Output saved to: {}", output_path.display()),
+            saved_message("Output", output_path, &warnings),
         ));
         let _ = progress_tx.send(ProgressMessage::Complete);
 
         Ok(())
     }
 
-    /// Create a transparent overlay video with only subtitles
+    /// Create a transparent overlay video with only subtitles, returning
+    /// FFmpeg's warnings
     fn create_subtitle_overlay(
         &self,
         video_path: &Path,
@@ -481,119 +1142,176 @@ impl SubtitleBurner {
         width: u32,
         height: u32,
         original_width: u32, // For font size calculation
-    ) -> Result<()> {
-        // Get video duration and framerate
-        let duration = self.get_video_duration(video_path)?;
+    ) -> Result<Vec<String>> {
+        // Get video duration and framerate; an excerpt only needs its own length
+        let duration = match self.time_range {
+            Some((_, duration_ms)) => duration_ms as f64 / 1000.0,
+            None => self.get_video_duration(video_path)?,
+        };
         let fps = self.get_video_fps(video_path)?;
 
-        // Escape the SRT path for FFmpeg filter
-        let srt_path_str = srt_path
-            .to_str()
-            .unwrap()
-            .replace("\\", "/")
-            .replace(":", "\\:");
-
         // Calculate font size to fill the overlay space
-        // Use 35-40% of overlay height for good readability
-        // This ensures text uses the available space well
-        let font_size = (height as f64 * 0.38).max(24.0) as u32;
+        // Defaults to 38% of overlay height for good readability
+        let font_size = self.font_size_for_height(height);
 
         // Reduce margin to maximize space usage
-        let margin_v = (height as f64 * 0.1) as u32; // 10% margin
+        let margin_v = band_margin(height);
 
         // Create transparent video with subtitles using VP9 codec with alpha
         // Force subtitles to render at proper size to fill the overlay area
         let filter = format!(
-            "color=c=black@0.0:s={}x{}:d={},format=yuva420p,subtitles='{}':force_style='FontSize={},MarginV={}'",
-            width, height, duration, srt_path_str, font_size, margin_v
+            "color=c=black@0.0:s={}x{}:d={},format=yuva420p,subtitles={}:force_style='{}'",
+            width,
+            height,
+            duration,
+            filter_path(srt_path),
+            self.force_style(font_size, margin_v)
         );
 
-        let output = Command::new("ffmpeg")
-            .args([
-                "-f",
-                "lavfi",
-                "-i",
-                &filter,
-                "-r",
-                &fps.to_string(),
-                "-c:v",
-                "libvpx-vp9",
-                "-pix_fmt",
-                "yuva420p",
-                "-auto-alt-ref",
-                "0",
-                "-b:v",
-                "1M",
-                "-y",
-                overlay_path.to_str().unwrap(),
-            ])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+        let output = self
+            .cancel
+            .output(
+                Command::new("ffmpeg")
+                    .args(ffmpeg::LOG_ARGS)
+                    .args(["-f", "lavfi", "-i", &filter, "-r", &fps.to_string()])
+                    .args(self.overlay_codec_args(overlay_path, width, height, fps))
+                    .arg("-y")
+                    .arg(overlay_path),
+            )
             .context("Failed to create subtitle overlay")?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             anyhow::bail!("Failed to create overlay: {}", stderr);
         }
+        ffmpeg::check_output(overlay_path, "Overlay creation", &output.stderr)?;
 
-        Ok(())
+        Ok(ffmpeg::warnings(&output.stderr))
     }
 
-    /// Merge overlay video with original video
+    /// Codec arguments for a transparent overlay: ProRes 4444 in .mov, which
+    /// editing software imports with its alpha, otherwise VP9 with alpha
+    fn overlay_codec_args(
+        &self,
+        overlay_path: &Path,
+        width: u32,
+        height: u32,
+        fps: u32,
+    ) -> Vec<String> {
+        let is_mov = overlay_path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("mov"));
+        if is_mov {
+            [
+                "-c:v",
+                "prores_ks",
+                "-profile:v",
+                "4444",
+                "-pix_fmt",
+                "yuva444p10le",
+            ]
+            .map(String::from)
+            .to_vec()
+        } else {
+            let bitrate = self
+                .overlay_bitrate
+                .unwrap_or_else(|| overlay_bitrate_for(width, height, fps));
+            vec![
+                "-c:v".to_string(),
+                "libvpx-vp9".to_string(),
+                "-pix_fmt".to_string(),
+                "yuva420p".to_string(),
+                "-auto-alt-ref".to_string(),
+                "0".to_string(),
+                "-b:v".to_string(),
+                bitrate.to_string(),
+            ]
+        }
+    }
+
+    /// Merge overlay videos with original video, one band per position,
+    /// returning FFmpeg's warnings
     fn merge_overlay(
         &self,
         video_path: &Path,
-        overlay_path: &Path,
+        overlays: &[(PathBuf, SubtitlePosition)],
         output_path: &Path,
         video_width: u32,
         video_height: u32,
-    ) -> Result<()> {
-        // Get overlay dimensions to calculate position
-        let (overlay_width, overlay_height) = self.get_video_dimensions(overlay_path)?;
-
-        // Calculate X position (centered by default, or with offset)
-        let x_offset = self.overlay_x_offset.unwrap_or(0);
-        let x_centered = ((video_width - overlay_width) / 2) as i32;
-        let x_position = (x_centered + x_offset).max(0);
-
-        // Calculate Y position (at bottom by default, or with offset)
-        let y_offset = self.overlay_y_offset.unwrap_or(0);
-        let y_bottom = (video_height - overlay_height) as i32;
-        let y_position = (y_bottom + y_offset).max(0);
+        output_args: &[String],
+    ) -> Result<Vec<String>> {
+        let mut command = Command::new("ffmpeg");
+        command
+            .args(ffmpeg::LOG_ARGS)
+            .args(self.input_args(video_path));
+
+        let mut filters = Vec::new();
+        let mut previous = "0:v".to_string();
+        for (i, (overlay_path, position)) in overlays.iter().enumerate() {
+            command.arg("-i").arg(overlay_path);
+
+            // Get overlay dimensions to calculate position
+            let (overlay_width, overlay_height) = self.get_video_dimensions(overlay_path)?;
+
+            // Place the track's band, stacked so tracks sharing a position
+            // never cover each other
+            let stacked = overlays[..i].iter().filter(|(_, p)| p == position).count() as u32;
+            let band = OverlayBand::at(
+                (video_width, video_height),
+                overlay_width,
+                overlay_height,
+                *position,
+                stacked,
+                (
+                    self.overlay_x_offset.unwrap_or(0),
+                    self.overlay_y_offset.unwrap_or(0),
+                ),
+            );
+
+            let label = format!("v{}", i + 1);
+            filters.push(format!(
+                "[{}][{}:v]overlay={}:{}[{}]",
+                previous,
+                i + 1,
+                band.x,
+                band.y,
+                label
+            ));
+            previous = label;
+        }
 
-        // Use overlay filter to combine videos
-        let output = Command::new("ffmpeg")
+        // Use overlay filters to combine videos, keeping the original audio
+        command
             .args([
-                "-i",
-                video_path.to_str().unwrap(),
-                "-i",
-                overlay_path.to_str().unwrap(),
                 "-filter_complex",
-                &format!("[0:v][1:v]overlay={}:{}", x_position, y_position),
-                "-c:a",
-                "copy",
-                "-y",
-                output_path.to_str().unwrap(),
+                &filters.join(";"),
+                "-map",
+                &format!("[{}]", previous),
+                "-map",
+                "0:a?",
             ])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+            .args(output_args)
+            .arg("-y")
+            .arg(output_path);
+        let output = self
+            .cancel
+            .output(&mut command)
             .context("Failed to merge overlay with video")?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             anyhow::bail!("Failed to merge overlay: {}", stderr);
         }
+        ffmpeg::check_output(output_path, "Overlay merge", &output.stderr)?;
 
-        Ok(())
+        Ok(ffmpeg::warnings(&output.stderr))
     }
 
     /// Direct burn method (old approach) - kept for compatibility
     fn burn_direct(
         &self,
         video_path: &Path,
-        srt_path: &Path,
+        tracks: &[SubtitleTrack],
         output_path: &Path,
         progress_tx: Sender<ProgressMessage>,
     ) -> Result<()> {
@@ -602,47 +1320,180 @@ impl SubtitleBurner {
             "Using direct burn method...".to_string(),
         ));
 
-        let srt_path_str = srt_path
-            .to_str()
-            .unwrap()
-            .replace("\\", "/")
-            .replace(":", "\\:");
+        // One subtitles filter per track, chained
+        let filter = tracks
+            .iter()
+            .map(|track| {
+                let srt_path_str = filter_path(&track.srt_path);
+
+                // Only force a font size when scaling was explicitly requested,
+                // and an alignment for non-bottom tracks or a requested text
+                // alignment, so the default direct burn keeps libass' own styling
+                let mut style = Vec::new();
+                if self.font_scale.is_some() || self.min_font_size.is_some() {
+                    let band_height = self.overlay_height.unwrap_or(200);
+                    style.push(format!(
+                        "FontSize={}",
+                        self.font_size_for_height(band_height)
+                    ));
+                    style.push(format!("MarginV={}", (band_height as f64 * 0.1) as u32));
+                }
+                if track.position != SubtitlePosition::Bottom || self.text_align.is_some() {
+                    style.push(format!(
+                        "Alignment={}",
+                        ass_alignment(track.position, self.text_align.unwrap_or_default())
+                    ));
+                }
+                style.extend(self.user_style());
+
+                if style.is_empty() {
+                    format!("subtitles={}", srt_path_str)
+                } else {
+                    format!(
+                        "subtitles={}:force_style='{}'",
+                        srt_path_str,
+                        style.join(",")
+                    )
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let (output_args, warning) = self.output_codec_args(video_path, output_path);
+        if let Some(warning) = warning {
+            let _ = progress_tx.send(ProgressMessage::Progress(0.15, warning));
+        }
 
         let _ = progress_tx.send(ProgressMessage::Progress(
             0.2,
             "Running FFmpeg...".to_string(),
         ));
 
-        let output = Command::new("ffmpeg")
-            .args([
-                "-i",
-                video_path.to_str().unwrap(),
-                "-vf",
-                &format!("subtitles='{}'", srt_path_str),
-                "-c:a",
-                "copy",
-                "-y",
-                output_path.to_str().unwrap(),
-            ])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+        let output = self
+            .cancel
+            .output(
+                Command::new("ffmpeg")
+                    .args(ffmpeg::LOG_ARGS)
+                    .args(self.input_args(video_path))
+                    .args(["-vf", &filter])
+                    .args(&output_args)
+                    .arg("-y")
+                    .arg(output_path),
+            )
             .context("Failed to run FFmpeg")?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             anyhow::bail!("FFmpeg failed: {}", stderr);
         }
+        ffmpeg::check_output(output_path, "Burn", &output.stderr)?;
 
         let _ = progress_tx.send(ProgressMessage::Progress(
             1.0,
-            format!("Output saved to: {}", output_path.display()),
+            saved_message("Output", output_path, &ffmpeg::warnings(&output.stderr)),
         ));
         let _ = progress_tx.send(ProgressMessage::Complete);
 
         Ok(())
     }
 
+    /// FFmpeg input arguments for the source video, seeking to the excerpt
+    /// when a time range is set. Input seeking restarts timestamps at zero,
+    /// which is what the shifted excerpt subtitles expect.
+    fn input_args(&self, video_path: &Path) -> Vec<OsString> {
+        let mut args = Vec::new();
+        if let Some((start_ms, duration_ms)) = self.time_range {
+            args.extend([
+                "-ss".into(),
+                format!("{:.3}", start_ms as f64 / 1000.0).into(),
+                "-t".into(),
+                format!("{:.3}", duration_ms as f64 / 1000.0).into(),
+            ]);
+        }
+        args.extend(["-i".into(), video_path.into()]);
+        args
+    }
+
+    /// Codec and muxer arguments for the output container, chosen from the
+    /// output extension. Audio is copied when the container accepts the source
+    /// codec and re-encoded otherwise; the second value explains any re-encode.
+    fn output_codec_args(
+        &self,
+        video_path: &Path,
+        output_path: &Path,
+    ) -> (Vec<String>, Option<String>) {
+        let container = output_path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        let mut args = Vec::new();
+        let mut warning = None;
+
+        match self.get_audio_codec(video_path) {
+            Some(codec) if !audio_copy_compatible(&container, &codec) => {
+                let target = default_audio_codec(&container);
+                warning = Some(format!(
+                    "Audio codec {} can't be copied into .{}, re-encoding to {}",
+                    codec, container, target
+                ));
+                args.extend(["-c:a".to_string(), target.to_string()]);
+            }
+            Some(_) => args.extend(["-c:a".to_string(), "copy".to_string()]),
+            // No audio stream to carry over
+            None => {}
+        }
+
+        args.extend(self.video_quality_args());
+
+        // Move the index to the front so MP4 output can start playing while streaming
+        if matches!(container.as_str(), "mp4" | "m4v" | "mov") {
+            args.extend(["-movflags".to_string(), "+faststart".to_string()]);
+        }
+
+        (args, warning)
+    }
+
+    /// Quality settings for the final encode: the CRF when set, otherwise the
+    /// target bitrate when set, otherwise the encoder's defaults
+    fn video_quality_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(crf) = self.crf {
+            args.extend(["-crf".to_string(), crf.to_string()]);
+        } else if let Some(bitrate) = self.video_bitrate {
+            args.extend(["-b:v".to_string(), bitrate.to_string()]);
+        }
+        if let Some(preset) = &self.preset {
+            args.extend(["-preset".to_string(), preset.clone()]);
+        }
+        args
+    }
+
+    /// Get the codec name of the first audio stream, if there is one
+    fn get_audio_codec(&self, video_path: &Path) -> Option<String> {
+        let output = Command::new("ffprobe")
+            .args([
+                "-v",
+                "error",
+                "-select_streams",
+                "a:0",
+                "-show_entries",
+                "stream=codec_name",
+                "-of",
+                "default=noprint_wrappers=1:nokey=1",
+            ])
+            .arg(video_path)
+            .output()
+            .ok()?;
+
+        let codec = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if codec.is_empty() {
+            None
+        } else {
+            Some(codec)
+        }
+    }
+
     /// Get video framerate
     fn get_video_fps(&self, video_path: &Path) -> Result<u32> {
         let output = Command::new("ffprobe")
@@ -655,8 +1506,8 @@ impl SubtitleBurner {
                 "stream=r_frame_rate",
                 "-of",
                 "default=noprint_wrappers=1:nokey=1",
-                video_path.to_str().unwrap(),
             ])
+            .arg(video_path)
             .output()
             .context("Failed to get video fps")?;
 
@@ -679,7 +1530,7 @@ impl SubtitleBurner {
     }
 
     /// Get video dimensions using ffprobe
-    fn get_video_dimensions(&self, video_path: &Path) -> Result<(u32, u32)> {
+    pub fn get_video_dimensions(&self, video_path: &Path) -> Result<(u32, u32)> {
         let output = Command::new("ffprobe")
             .args([
                 "-v",
@@ -690,8 +1541,8 @@ impl SubtitleBurner {
                 "stream=width,height",
                 "-of",
                 "csv=s=x:p=0",
-                video_path.to_str().unwrap(),
             ])
+            .arg(video_path)
             .output()
             .context("Failed to run ffprobe")?;
 
@@ -708,6 +1559,11 @@ impl SubtitleBurner {
         Ok((width, height))
     }
 
+    /// Get video duration in milliseconds, for checking subtitle timing against it
+    pub fn get_video_duration_ms(&self, video_path: &Path) -> Result<u64> {
+        Ok((self.get_video_duration(video_path)? * 1000.0).round() as u64)
+    }
+
     /// Get video duration in seconds
     fn get_video_duration(&self, video_path: &Path) -> Result<f64> {
         let output = Command::new("ffprobe")
@@ -718,8 +1574,8 @@ impl SubtitleBurner {
                 "format=duration",
                 "-of",
                 "default=noprint_wrappers=1:nokey=1",
-                video_path.to_str().unwrap(),
             ])
+            .arg(video_path)
             .output()
             .context("Failed to get video duration")?;
 
@@ -730,3 +1586,334 @@ impl SubtitleBurner {
             .context("Invalid duration")
     }
 }
+
+/// Containers a standalone overlay can be written to with its alpha channel intact
+pub const OVERLAY_CONTAINERS: &[&str] = &["webm", "mkv", "mov"];
+
+/// Lowest overlay bitrate used when scaling it to the overlay size
+const MIN_OVERLAY_BITRATE: u64 = 500_000;
+
+/// Default overlay bitrate: 0.1 bits per pixel per frame, which keeps large
+/// text crisp without bloating small bands (about 1.2 Mbit/s for 1920x200 at 30fps)
+fn overlay_bitrate_for(width: u32, height: u32, fps: u32) -> u64 {
+    (width as u64 * height as u64 * fps as u64 / 10).max(MIN_OVERLAY_BITRATE)
+}
+
+/// Parse a bitrate like `2500k`, `4M` or `800000` into bits per second
+pub fn parse_bitrate(input: &str) -> Result<u64> {
+    let trimmed = input.trim();
+    let (number, multiplier) = match trimmed.chars().last() {
+        Some('k' | 'K') => (&trimmed[..trimmed.len() - 1], 1_000.0),
+        Some('m' | 'M') => (&trimmed[..trimmed.len() - 1], 1_000_000.0),
+        _ => (trimmed, 1.0),
+    };
+    match number.parse::<f64>() {
+        Ok(value) if value > 0.0 && value.is_finite() => Ok((value * multiplier).round() as u64),
+        _ => anyhow::bail!(
+            "Invalid bitrate '{}': expected a number of bits/s like 800000, 2500k or 4M",
+            input
+        ),
+    }
+}
+
+/// Final progress line for a saved file, noting FFmpeg's warnings (they often
+/// explain wrong-looking output, like a font that didn't apply). All of them
+/// are logged, which `--verbose` shows.
+fn saved_message(what: &str, path: &Path, warnings: &[String]) -> String {
+    for warning in warnings {
+        tracing::info!("FFmpeg: {}", warning);
+    }
+    match warnings {
+        [] => format!("{} saved to: {}", what, path.display()),
+        [warning] => format!(
+            "{} saved to: {} (FFmpeg warning: {})",
+            what,
+            path.display(),
+            warning
+        ),
+        [first, ..] => format!(
+            "{} saved to: {} (completed with {} FFmpeg warnings, first: {}; all with --verbose)",
+            what,
+            path.display(),
+            warnings.len(),
+            first
+        ),
+    }
+}
+
+/// Command-line argument made of a flag prefix and a path, without requiring
+/// the path to be valid UTF-8
+fn prefixed_arg(prefix: &str, path: &Path) -> OsString {
+    let mut arg = OsString::from(prefix);
+    arg.push(path);
+    arg
+}
+
+/// Quote a path for use as an option value inside an FFmpeg filtergraph
+/// (e.g. `subtitles=<path>`). Paths are escaped twice: once for the filter
+/// option parser (`\`, `'` and `:`), then quoted for the filtergraph parser so
+/// spaces, commas and brackets are kept literally. Non-UTF-8 bytes can't be
+/// represented in a filter string and are replaced.
+fn filter_path(path: &Path) -> String {
+    let value = path
+        .to_string_lossy()
+        .replace('\\', "/")
+        .replace('\'', "\\'")
+        .replace(':', "\\:");
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Whether an audio stream in `codec` can be stream-copied into `container`
+fn audio_copy_compatible(container: &str, codec: &str) -> bool {
+    match container {
+        "mp4" | "m4v" | "mov" => matches!(
+            codec,
+            "aac" | "mp3" | "ac3" | "eac3" | "alac" | "opus" | "flac"
+        ),
+        "webm" => matches!(codec, "opus" | "vorbis"),
+        "avi" => matches!(codec, "mp3" | "ac3" | "pcm_s16le" | "aac"),
+        // Matroska and unknown containers: let FFmpeg try to copy
+        _ => true,
+    }
+}
+
+/// Audio encoder to use when the source codec doesn't fit the container
+fn default_audio_codec(container: &str) -> &'static str {
+    match container {
+        "webm" => "libopus",
+        "avi" => "libmp3lame",
+        _ => "aac",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_path_escaping() {
+        assert_eq!(
+            filter_path(Path::new("/tmp/My Vidéos/clip 1.srt")),
+            "'/tmp/My Vidéos/clip 1.srt'"
+        );
+        assert_eq!(
+            filter_path(Path::new("C:\\subs\\it's.srt")),
+            "'C\\:/subs/it\\'\\''s.srt'"
+        );
+    }
+    #[test]
+    fn test_default_output_path() {
+        assert_eq!(
+            SubtitleBurner::default_output_path(Path::new("/videos/talk.mkv")),
+            Path::new("/videos/talk_subtitled.mkv")
+        );
+        assert_eq!(
+            SubtitleBurner::default_output_path(Path::new("/videos/recording")),
+            Path::new("/videos/recording_subtitled.mp4")
+        );
+        assert_eq!(
+            SubtitleBurner::clip_output_path(
+                &SubtitleBurner::default_output_path(Path::new("/videos/talk.mkv")),
+                12_500,
+                44_200
+            ),
+            Path::new("/videos/talk_subtitled_12s-45s.mkv")
+        );
+        assert_eq!(
+            SubtitleBurner::overlay_output_path(Path::new("/videos/talk.mp4")),
+            Path::new("/videos/talk_overlay.webm")
+        );
+    }
+
+    #[test]
+    fn test_text_box_scales_band_to_frame() {
+        let video = (1920, 1080);
+        // 76 in the band's 288-line canvas, 200px tall; 10 of 384 each side
+        let (font_px, max_px) = SubtitleBurner::new().text_box(video);
+        assert!((font_px - 76.0 * 200.0 / 288.0).abs() < 1e-9);
+        assert_eq!(max_px, 1820.0);
+        let (font_px, max_px) = SubtitleBurner::new()
+            .with_overlay_width(1280)
+            .text_box(video);
+        assert!((font_px - 76.0 * 200.0 / 288.0).abs() < 1e-9);
+        assert!((max_px - (1280.0 - 2.0 * 10.0 * 1280.0 / 384.0)).abs() < 1e-9);
+        // A direct burn draws libass' own size over the whole frame
+        let (font_px, _) = SubtitleBurner::new().with_overlay(false).text_box(video);
+        assert_eq!(font_px, 60.0);
+    }
+
+    #[test]
+    fn test_overlay_band_placement() {
+        let video = (1920, 1080);
+        assert_eq!(
+            OverlayBand::new(video, 1280, 200, 0, 0),
+            OverlayBand {
+                x: 320,
+                y: 880,
+                width: 1280,
+                height: 200,
+            }
+        );
+        assert_eq!(
+            OverlayBand::offset_limits(video, 1280, 200),
+            ((-320, 320), (-880, 0))
+        );
+        // Offsets past the frame edge are pinned to it, like the burner does
+        let band = OverlayBand::new(video, 1920, 2000, -50, -10);
+        assert_eq!((band.x, band.y), (0, 0));
+
+        // An overlay larger than the frame starts at its corner, whatever
+        // its position, and offsets still move it right and down
+        for position in [
+            SubtitlePosition::Top,
+            SubtitlePosition::Middle,
+            SubtitlePosition::Bottom,
+        ] {
+            let band = OverlayBand::at((1280, 720), 1920, 800, position, 0, (0, 0));
+            assert_eq!((band.x, band.y), (0, 0));
+        }
+        let band = OverlayBand::at(
+            (1280, 720),
+            1920,
+            800,
+            SubtitlePosition::Bottom,
+            0,
+            (40, 20),
+        );
+        assert_eq!((band.x, band.y), (40, 20));
+
+        // Tracks sharing a position stack away from the frame edge
+        let stacked = |position| OverlayBand::at(video, 1920, 200, position, 1, (0, 0)).y;
+        assert_eq!(stacked(SubtitlePosition::Bottom), 680);
+        assert_eq!(stacked(SubtitlePosition::Middle), 640);
+        assert_eq!(stacked(SubtitlePosition::Top), 200);
+    }
+
+    #[test]
+    fn test_bitrates() {
+        assert_eq!(parse_bitrate("4M").unwrap(), 4_000_000);
+        assert_eq!(parse_bitrate("2500k").unwrap(), 2_500_000);
+        assert_eq!(parse_bitrate("1.5m").unwrap(), 1_500_000);
+        assert_eq!(parse_bitrate("800000").unwrap(), 800_000);
+        assert!(parse_bitrate("fast").is_err());
+        assert!(parse_bitrate("-1M").is_err());
+
+        assert_eq!(overlay_bitrate_for(1920, 200, 30), 1_152_000);
+        assert_eq!(overlay_bitrate_for(640, 100, 24), MIN_OVERLAY_BITRATE);
+
+        let burner = SubtitleBurner::new()
+            .with_video_bitrate(4_000_000)
+            .with_preset("slow");
+        assert_eq!(
+            burner.video_quality_args(),
+            ["-b:v", "4000000", "-preset", "slow"]
+        );
+        // A CRF wins over the bitrate
+        assert_eq!(
+            burner.with_crf(20).video_quality_args()[..2],
+            ["-crf", "20"]
+        );
+    }
+
+    #[test]
+    fn test_estimate_scales_the_sample() {
+        let estimate = BurnEstimate::from_sample(2000, 500_000, 1.5, 60_000, (1920, 1080));
+        assert_eq!(estimate.output_bytes, 15_000_000);
+        assert_eq!(estimate.encode_secs, 45.0);
+        // A clip shorter than the sample is estimated from all of it
+        let short = BurnEstimate::from_sample(800, 100_000, 0.5, 800, (640, 360));
+        assert_eq!(short.output_bytes, 100_000);
+    }
+
+    #[test]
+    fn test_force_style_order() {
+        let burner = SubtitleBurner::new();
+        assert_eq!(burner.force_style(40, 20), "FontSize=40,MarginV=20");
+
+        let burner = SubtitleBurner::new()
+            .with_outline(3.0)
+            .with_shadow(0.5)
+            .with_style("Outline=1");
+        assert_eq!(
+            burner.force_style(40, 20),
+            "FontSize=40,MarginV=20,Outline=3,Shadow=0.5,Outline=1"
+        );
+    }
+
+    #[test]
+    fn test_preview_band_places_cues_in_band() {
+        let band = SubtitleBurner::new().preview_band(1920, 1080);
+        assert_eq!(
+            (band.x, band.y, band.width, band.height),
+            (0, 880, 1920, 200)
+        );
+        let option = |band: &PreviewBand, name: &str| {
+            band.sub_options()
+                .into_iter()
+                .find(|(option, _)| *option == name)
+                .map(|(_, value)| value)
+                .unwrap()
+        };
+        // 76 in a 200px band is ~53px, ~35 in mpv's 720-line units
+        assert_eq!(option(&band, "sub-font-size"), "35");
+        assert_eq!(option(&band, "sub-margin-y"), "9");
+
+        // Raised 100px and narrowed to 1200px, 60px right of center: the
+        // cues wrap within the wider side's margin
+        let band = SubtitleBurner::new()
+            .with_overlay_y_offset(-100)
+            .with_overlay_width(1200)
+            .with_overlay_x_offset(60)
+            .preview_band(1920, 1080);
+        assert_eq!(option(&band, "sub-margin-y"), "76");
+        assert_eq!(option(&band, "sub-margin-x"), "280");
+        assert!(band
+            .drawbox()
+            .starts_with("drawbox=x=420:y=780:w=1200:h=200"));
+    }
+
+    #[test]
+    fn test_ass_style_scales_band_font_to_frame() {
+        // 76-unit text in a 200px band is 14 units of a 1080p frame's canvas
+        let style = SubtitleBurner::new().ass_style(1080);
+        assert_eq!(style.font_size, 14);
+        assert_eq!(style.primary_color, (255, 255, 255));
+
+        let style = SubtitleBurner::new()
+            .with_overlay_height(300)
+            .with_font_color((255, 255, 0))
+            .with_text_align(TextAlign::Left)
+            .ass_style(720);
+        assert_eq!(style.font_size, 114 * 300 / 720);
+        assert_eq!((style.primary_color, style.alignment()), ((255, 255, 0), 1));
+
+        let direct = SubtitleBurner::new().with_overlay(false).ass_style(1080);
+        assert_eq!(direct.font_size, LIBASS_FONT_SIZE);
+    }
+
+    #[test]
+    fn test_text_align_within_band() {
+        assert_eq!(ass_alignment(SubtitlePosition::Bottom, TextAlign::Right), 3);
+        assert_eq!(ass_alignment(SubtitlePosition::Top, TextAlign::Left), 7);
+
+        // MarginR is left to FFmpeg's default (or --style) so right-aligned
+        // text keeps off the band's right edge
+        let burner = SubtitleBurner::new()
+            .with_text_align(TextAlign::Right)
+            .with_style("MarginR=20");
+        assert_eq!(
+            burner.force_style(40, 20),
+            "FontSize=40,MarginV=20,Alignment=3,MarginR=20"
+        );
+
+        // 1200px band 60px right of center: 300px to its right, plus the
+        // 10-unit side margin (~31px), in mpv's 720-line units
+        let band = burner
+            .with_overlay_width(1200)
+            .with_overlay_x_offset(60)
+            .preview_band(1920, 1080);
+        let options = band.sub_options();
+        assert!(options.contains(&("sub-align-x", "right".to_string())));
+        assert!(options.contains(&("sub-margin-x", "221".to_string())));
+    }
+}
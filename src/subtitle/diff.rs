@@ -0,0 +1,194 @@
+use std::fmt;
+
+use crate::subtitle::fix;
+use crate::subtitle::srt::Subtitle;
+
+/// Rough comparison of two versions of the same subtitles
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiffSummary {
+    /// Cues present in both versions with identical text
+    pub unchanged: usize,
+    /// Cues present in both versions whose text differs
+    pub changed: usize,
+    /// Cues in the new version with no counterpart in the old one
+    pub added: usize,
+    /// Cues in the old version with no counterpart in the new one
+    pub removed: usize,
+}
+
+impl DiffSummary {
+    /// Share of cues, out of everything either version has, that are unchanged
+    pub fn agreement(&self) -> f64 {
+        let total = self.unchanged + self.changed + self.added + self.removed;
+        if total == 0 {
+            return 1.0;
+        }
+        self.unchanged as f64 / total as f64
+    }
+}
+
+impl fmt::Display for DiffSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} cues changed, {} added, {} removed ({} unchanged)",
+            self.changed, self.added, self.removed, self.unchanged
+        )
+    }
+}
+
+/// Compare `old` and `new` by pairing each new cue with the unpaired old cue
+/// it overlaps most in time. This is approximate: split or merged cues show
+/// up as a change plus an addition or removal.
+pub fn diff_subtitles(old: &[Subtitle], new: &[Subtitle]) -> DiffSummary {
+    let mut paired = vec![false; old.len()];
+    let mut summary = DiffSummary::default();
+
+    for cue in new {
+        let best = old
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !paired[*i])
+            .map(|(i, other)| (i, overlap(cue, other)))
+            .filter(|(_, overlap)| *overlap > 0)
+            .max_by_key(|(_, overlap)| *overlap);
+
+        match best {
+            Some((i, _)) => {
+                paired[i] = true;
+                if normalize(&old[i].text) == normalize(&cue.text) {
+                    summary.unchanged += 1;
+                } else {
+                    summary.changed += 1;
+                }
+            }
+            None => summary.added += 1,
+        }
+    }
+
+    summary.removed = paired.iter().filter(|p| !**p).count();
+    summary
+}
+
+/// Carry the edits that turned `draft` into `edited` over to `fresh`, a
+/// cleaned-up version of the draft. Fresh cues overlapping a draft cue that
+/// was edited, retimed, locked or deleted give way to the edited cues, along
+/// with any untouched cue that loses its fresh version that way. The rest of
+/// `fresh` is kept as it is.
+pub fn carry_over_edits(
+    draft: &[Subtitle],
+    edited: &[Subtitle],
+    fresh: Vec<Subtitle>,
+) -> Vec<Subtitle> {
+    let same = |a: &Subtitle, b: &Subtitle| {
+        (a.start_time, a.end_time, &a.text, a.position, a.locked)
+            == (b.start_time, b.end_time, &b.text, b.position, b.locked)
+    };
+    let replaced: Vec<&Subtitle> = draft
+        .iter()
+        .filter(|old| !edited.iter().any(|new| same(old, new)))
+        .collect();
+
+    let (mut merged, dropped): (Vec<Subtitle>, Vec<Subtitle>) = fresh
+        .into_iter()
+        .partition(|sub| !replaced.iter().any(|old| overlap(old, sub) > 0));
+    let restored: Vec<Subtitle> = edited
+        .iter()
+        .filter(|new| {
+            let untouched = draft.iter().any(|old| same(old, new));
+            let covered = |cues: &[Subtitle]| cues.iter().any(|sub| overlap(sub, new) > 0);
+            !untouched || (covered(&dropped) && !covered(&merged))
+        })
+        .cloned()
+        .collect();
+    merged.extend(restored);
+    merged.sort_by_key(|sub| sub.start_time);
+    fix::reindex(&mut merged);
+    merged
+}
+
+fn overlap(a: &Subtitle, b: &Subtitle) -> u64 {
+    a.end_time
+        .min(b.end_time)
+        .saturating_sub(a.start_time.max(b.start_time))
+}
+
+/// Ignore line breaks and spacing, which re-wrapping changes freely
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(start: u64, end: u64, text: &str) -> Subtitle {
+        Subtitle::new(0, start, end, text.to_string())
+    }
+
+    #[test]
+    fn test_diff_counts() {
+        let old = vec![
+            cue(0, 1000, "Hello there"),
+            cue(1000, 2000, "General Kenobi"),
+            cue(5000, 6000, "Gone later"),
+        ];
+        let new = vec![
+            cue(0, 1100, "Hello\nthere"),
+            cue(1100, 2000, "General Kenobi!"),
+            cue(3000, 4000, "Brand new"),
+        ];
+
+        assert_eq!(
+            diff_subtitles(&old, &new),
+            DiffSummary {
+                unchanged: 1,
+                changed: 1,
+                added: 1,
+                removed: 1,
+            }
+        );
+        assert_eq!(diff_subtitles(&old, &new).agreement(), 0.25);
+    }
+
+    #[test]
+    fn test_carry_over_edits() {
+        let draft = vec![
+            cue(0, 1000, "Hello there"),
+            cue(1000, 9000, "Long one. Split later."),
+            cue(9000, 10000, "Repeat"),
+            cue(10000, 11000, "Repeat"),
+            cue(12000, 13000, "Deleted"),
+        ];
+        let mut edited = draft.clone();
+        edited[0].text = "Hello there!".to_string();
+        edited[2].locked = true;
+        edited.remove(4);
+        edited.push(cue(14000, 15000, "Added by hand"));
+        // Cleanup split the long cue, dropped a repeat and trimmed the gaps
+        let fresh = vec![
+            cue(0, 960, "Hello there"),
+            cue(1000, 4960, "Long one."),
+            cue(5000, 8960, "Split later."),
+            cue(9000, 11000, "Repeat"),
+            cue(12000, 13000, "Deleted"),
+        ];
+
+        let merged = carry_over_edits(&draft, &edited, fresh);
+        let texts: Vec<&str> = merged.iter().map(|sub| sub.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "Hello there!",
+                "Long one.",
+                "Split later.",
+                "Repeat",
+                "Repeat",
+                "Added by hand"
+            ]
+        );
+        assert!(merged[3].locked);
+        assert_eq!(merged[4].start_time, 10000);
+        assert_eq!(merged[5].index, 6);
+    }
+}
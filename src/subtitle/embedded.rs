@@ -0,0 +1,133 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Bitmap subtitle codecs; these are pictures of text and would need OCR
+const IMAGE_CODECS: &[&str] = &["hdmv_pgs_subtitle", "dvd_subtitle", "dvb_subtitle", "xsub"];
+
+/// A subtitle stream carried inside a video container
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedTrack {
+    /// Position among the file's subtitle streams (the N in `-map 0:s:N`)
+    pub index: usize,
+    pub codec: String,
+    pub language: Option<String>,
+    pub title: Option<String>,
+}
+
+impl EmbeddedTrack {
+    /// Whether the track is text and can be converted to SRT
+    pub fn is_text(&self) -> bool {
+        !IMAGE_CODECS.contains(&self.codec.as_str())
+    }
+
+    /// Short description, e.g. "#0 eng subrip (SDH)"
+    pub fn label(&self) -> String {
+        let mut label = format!("#{}", self.index);
+        if let Some(language) = &self.language {
+            label.push_str(&format!(" {}", language));
+        }
+        label.push_str(&format!(" {}", self.codec));
+        if let Some(title) = &self.title {
+            label.push_str(&format!(" ({})", title));
+        }
+        if !self.is_text() {
+            label.push_str(" [image]");
+        }
+        label
+    }
+}
+
+#[derive(Deserialize)]
+struct ProbeOutput {
+    #[serde(default)]
+    streams: Vec<ProbeStream>,
+}
+
+#[derive(Deserialize)]
+struct ProbeStream {
+    #[serde(default)]
+    codec_name: String,
+    #[serde(default)]
+    tags: ProbeTags,
+}
+
+#[derive(Deserialize, Default)]
+struct ProbeTags {
+    language: Option<String>,
+    title: Option<String>,
+}
+
+/// List the subtitle streams in `video_path`
+pub fn probe_tracks(video_path: &Path) -> Result<Vec<EmbeddedTrack>> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "s",
+            "-show_entries",
+            "stream=codec_name:stream_tags=language,title",
+            "-of",
+            "json",
+        ])
+        .arg(video_path)
+        .output()
+        .context("Failed to run ffprobe")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("ffprobe failed: {}", stderr.trim());
+    }
+
+    let probe: ProbeOutput =
+        serde_json::from_slice(&output.stdout).context("Failed to parse ffprobe output")?;
+    Ok(probe
+        .streams
+        .into_iter()
+        .enumerate()
+        .map(|(index, stream)| EmbeddedTrack {
+            index,
+            codec: stream.codec_name,
+            language: stream.tags.language,
+            title: stream.tags.title,
+        })
+        .collect())
+}
+
+/// Convert subtitle stream `index` of `video_path` to an SRT file
+pub fn extract_track(video_path: &Path, index: usize, srt_path: &Path) -> Result<()> {
+    let tracks = probe_tracks(video_path)?;
+    let track = tracks.get(index).with_context(|| {
+        format!(
+            "{} has no subtitle track #{} ({} subtitle tracks found)",
+            video_path.display(),
+            index,
+            tracks.len()
+        )
+    })?;
+    if !track.is_text() {
+        anyhow::bail!(
+            "Subtitle track {} is image-based and can't be converted to SRT without OCR",
+            track.label()
+        );
+    }
+
+    let output = Command::new("ffmpeg")
+        .arg("-i")
+        .arg(video_path)
+        .args(["-map", &format!("0:s:{}", index), "-c:s", "srt", "-y"])
+        .arg(srt_path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .context("Failed to run FFmpeg")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to extract subtitle track: {}", stderr.trim());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,423 @@
+use crate::subtitle::srt::Subtitle;
+use anyhow::{Context, Result};
+use regex::{NoExpand, Regex, RegexBuilder};
+
+/// Largest gap (ms) between two identical cues that still counts as a repeat
+pub const DEFAULT_DEDUPE_MAX_GAP_MS: u64 = 500;
+
+/// Normalize cue text for comparison: lowercase, alphanumerics only, single spaces
+fn normalize_for_compare(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Collapse runs of adjacent cues with identical (normalized) text into a single
+/// cue spanning the whole run. Cues separated by more than `max_gap_ms` are left
+/// alone so legitimately repeated dialogue survives. Returns the number of cues removed.
+pub fn dedupe_consecutive(subtitles: &mut Vec<Subtitle>, max_gap_ms: u64) -> usize {
+    let before = subtitles.len();
+    let mut result: Vec<Subtitle> = Vec::with_capacity(before);
+
+    for sub in subtitles.drain(..) {
+        if let Some(prev) = result.last_mut() {
+            let gap = sub.start_time.saturating_sub(prev.end_time);
+            let text = normalize_for_compare(&sub.text);
+            if gap <= max_gap_ms && !text.is_empty() && text == normalize_for_compare(&prev.text) {
+                prev.end_time = prev.end_time.max(sub.end_time);
+                continue;
+            }
+        }
+        result.push(sub);
+    }
+
+    *subtitles = result;
+    reindex(subtitles);
+    before - subtitles.len()
+}
+
+/// Words ending in a period that don't end a sentence
+const ABBREVIATIONS: &[&str] = &[
+    "mr.", "mrs.", "ms.", "dr.", "prof.", "st.", "jr.", "sr.", "vs.", "etc.", "e.g.", "i.e.",
+    "approx.", "no.",
+];
+
+/// Capitalize the first letter of each sentence and the pronoun "I". Other
+/// letters are left alone, so names and acronyms survive.
+pub fn sentence_case(text: &str) -> String {
+    sentence_case_from(text, true).0
+}
+
+/// Like `sentence_case`, starting mid-sentence unless `sentence_start`.
+/// Also returns whether the text ends a sentence, to carry into the next cue.
+fn sentence_case_from(text: &str, sentence_start: bool) -> (String, bool) {
+    let mut result = String::with_capacity(text.len());
+    let mut capitalize = sentence_start;
+    for word in text.split_inclusive(char::is_whitespace) {
+        // Dashes, ellipses and the like don't start or end anything
+        let core = word.trim_end();
+        if !core.chars().any(char::is_alphanumeric) {
+            result.push_str(word);
+            continue;
+        }
+
+        let lower = core.to_lowercase();
+        let bare = lower.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'');
+        let pronoun = bare == "i" || bare.starts_with("i'");
+        if capitalize || pronoun {
+            let mut done = false;
+            for c in word.chars() {
+                if !done && c.is_alphabetic() {
+                    result.extend(c.to_uppercase());
+                    done = true;
+                } else {
+                    result.push(c);
+                }
+            }
+        } else {
+            result.push_str(word);
+        }
+
+        capitalize = ends_sentence(core);
+    }
+    (result, capitalize)
+}
+
+/// Whether `word` ends a sentence: it ends in `.`, `!` or `?` (before any
+/// closing quote or bracket) and isn't a common abbreviation. A trailing
+/// ellipsis usually means the sentence carries on.
+fn ends_sentence(word: &str) -> bool {
+    let lower = word.to_lowercase();
+    let ending = lower
+        .trim_start_matches(|c: char| !c.is_alphanumeric())
+        .trim_end_matches(['"', '\'', ')', ']']);
+    ending.ends_with(['.', '!', '?']) && !ending.ends_with("..") && !ABBREVIATIONS.contains(&ending)
+}
+
+/// Split text into sentences, after each word that ends one
+pub fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+    for word in text.split_inclusive(char::is_whitespace) {
+        offset += word.len();
+        if ends_sentence(word.trim_end()) {
+            sentences.push(text[start..offset].trim());
+            start = offset;
+        }
+    }
+    sentences.push(text[start..].trim());
+    sentences.retain(|s| !s.is_empty());
+    sentences
+}
+
+/// Next step in the editor's case cycle: lowercase -> Sentence case -> UPPERCASE
+pub fn cycle_case(text: &str) -> String {
+    let has_letters = text.chars().any(char::is_alphabetic);
+    if has_letters && text == text.to_uppercase() {
+        text.to_lowercase()
+    } else if text == text.to_lowercase() {
+        sentence_case(text)
+    } else {
+        text.to_uppercase()
+    }
+}
+
+/// Sentence-case every cue, treating a cue as continuing the previous one
+/// unless that one ended a sentence. Returns the number of cues changed.
+pub fn sentence_case_all(subtitles: &mut [Subtitle]) -> usize {
+    let mut sentence_start = true;
+    let mut changed = 0;
+    for sub in subtitles.iter_mut() {
+        let (text, ends_sentence) = sentence_case_from(&sub.text, sentence_start);
+        if text != sub.text {
+            sub.text = text;
+            changed += 1;
+        }
+        sentence_start = ends_sentence;
+    }
+    changed
+}
+
+/// Punctuation that attaches to the word before it
+const CLOSING_PUNCTUATION: &[char] = &[',', '.', '!', '?', ':', ';', ')', ']'];
+
+/// Punctuation French typography sets off with a space before it
+const FRENCH_SPACED: &[char] = &['?', '!', ':', ';'];
+
+/// Tidy the spacing in cue text: collapse runs of whitespace and trim each
+/// line, drop spaces before punctuation (" ," -> ","), and add the missing
+/// space after a comma or sentence end ("end.Next" -> "end. Next"). With
+/// `french`, `? ! : ;` get a single space before them instead.
+pub fn normalize_whitespace(text: &str, french: bool) -> String {
+    text.lines()
+        .map(|line| normalize_line(line, french))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn normalize_line(line: &str, french: bool) -> String {
+    let spaced_before = |c: char| french && FRENCH_SPACED.contains(&c);
+
+    let mut joined = String::with_capacity(line.len());
+    for word in line.split_whitespace() {
+        let first = word.chars().next().unwrap_or(' ');
+        let attach = CLOSING_PUNCTUATION.contains(&first) && !spaced_before(first);
+        if !joined.is_empty() && !attach {
+            joined.push(' ');
+        }
+        joined.push_str(word);
+    }
+
+    let chars: Vec<char> = joined.chars().collect();
+    let mut result = String::with_capacity(joined.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|j| chars[j]);
+        let next = chars.get(i + 1).copied();
+
+        // "Quoi?" -> "Quoi ?", but leave "10:30" and "http://" alone
+        if spaced_before(c)
+            && prev.is_some_and(char::is_alphanumeric)
+            && next.is_none_or(|n| n.is_whitespace() || CLOSING_PUNCTUATION.contains(&n))
+        {
+            result.push(' ');
+        }
+        result.push(c);
+
+        // Only between letters, and only at a plausible sentence end for
+        // periods, so "1,000", "e.g." and "U.S." are untouched
+        let missing_space = match c {
+            ',' => prev.is_some_and(char::is_alphabetic) && next.is_some_and(char::is_alphabetic),
+            '.' | '!' | '?' => {
+                prev.is_some_and(char::is_lowercase) && next.is_some_and(char::is_uppercase)
+            }
+            _ => false,
+        };
+        if missing_space {
+            result.push(' ');
+        }
+    }
+    result
+}
+
+/// Normalize the spacing of every cue. Returns the number of cues changed.
+pub fn normalize_whitespace_all(subtitles: &mut [Subtitle], french: bool) -> usize {
+    let mut changed = 0;
+    for sub in subtitles.iter_mut() {
+        let text = normalize_whitespace(&sub.text, french);
+        if text != sub.text {
+            sub.text = text;
+            changed += 1;
+        }
+    }
+    changed
+}
+
+/// Replace `from` with `to` in every cue, as plain text. Returns the number
+/// of occurrences replaced.
+pub fn replace_all(
+    subtitles: &mut [Subtitle],
+    from: &str,
+    to: &str,
+    case_sensitive: bool,
+) -> usize {
+    Replacement::literal(from, to, case_sensitive).map_or(0, |r| r.apply(subtitles))
+}
+
+/// A find-and-replace over cue text, either plain text or a regular expression
+#[derive(Debug, Clone)]
+pub struct Replacement {
+    pattern: Regex,
+    to: String,
+    /// Expand `$1` / `${name}` in `to` (regex mode only)
+    expand: bool,
+}
+
+impl Replacement {
+    /// Replace `from` as plain text
+    pub fn literal(from: &str, to: &str, case_sensitive: bool) -> Result<Self> {
+        if from.is_empty() {
+            anyhow::bail!("Nothing to find");
+        }
+        let pattern = RegexBuilder::new(&regex::escape(from))
+            .case_insensitive(!case_sensitive)
+            .build()?;
+        Ok(Self {
+            pattern,
+            to: to.to_string(),
+            expand: false,
+        })
+    }
+
+    /// Replace matches of a regular expression; `to` may refer to groups as
+    /// `$1` or `${name}`
+    pub fn regex(pattern: &str, to: &str, case_sensitive: bool) -> Result<Self> {
+        let pattern = RegexBuilder::new(pattern)
+            .case_insensitive(!case_sensitive)
+            .build()
+            .context("Invalid regular expression")?;
+        // A pattern that matches nothing at all would insert `to` everywhere
+        if pattern.is_match("") {
+            anyhow::bail!("The pattern matches empty text");
+        }
+        Ok(Self {
+            pattern,
+            to: to.to_string(),
+            expand: true,
+        })
+    }
+
+    /// How many occurrences would be replaced, and in how many cues
+    pub fn count(&self, subtitles: &[Subtitle]) -> (usize, usize) {
+        subtitles
+            .iter()
+            .map(|sub| self.pattern.find_iter(&sub.text).count())
+            .filter(|&n| n > 0)
+            .fold((0, 0), |(total, cues), n| (total + n, cues + 1))
+    }
+
+    /// Replace in every cue. Changed cues lose their confidence, as with a
+    /// manual edit. Returns the number of occurrences replaced.
+    pub fn apply(&self, subtitles: &mut [Subtitle]) -> usize {
+        let mut replaced = 0;
+        for sub in subtitles.iter_mut() {
+            let found = self.pattern.find_iter(&sub.text).count();
+            if found == 0 {
+                continue;
+            }
+            let text = if self.expand {
+                self.pattern.replace_all(&sub.text, self.to.as_str())
+            } else {
+                self.pattern.replace_all(&sub.text, NoExpand(&self.to))
+            };
+            if text != sub.text {
+                sub.text = text.into_owned();
+                sub.confidence = None;
+            }
+            replaced += found;
+        }
+        replaced
+    }
+}
+
+/// Renumber subtitles sequentially starting at 1
+pub fn reindex(subtitles: &mut [Subtitle]) {
+    for (i, sub) in subtitles.iter_mut().enumerate() {
+        sub.index = i + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(start: u64, end: u64, text: &str) -> Subtitle {
+        Subtitle::new(0, start, end, text.to_string())
+    }
+
+    #[test]
+    fn test_dedupe_collapses_adjacent_repeats() {
+        let mut subs = vec![
+            cue(0, 1000, "Thank you."),
+            cue(1000, 2000, "thank you"),
+            cue(2100, 3000, "Thank you!"),
+            cue(3000, 4000, "Something else"),
+        ];
+        assert_eq!(dedupe_consecutive(&mut subs, DEFAULT_DEDUPE_MAX_GAP_MS), 2);
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0].end_time, 3000);
+        assert_eq!(subs[1].index, 2);
+    }
+
+    #[test]
+    fn test_sentence_case() {
+        assert_eq!(
+            sentence_case("hello there. i'm dr. smith from NASA! ok?"),
+            "Hello there. I'm dr. smith from NASA! Ok?"
+        );
+        assert_eq!(
+            sentence_case("- well... (e.g. this) ok"),
+            "- Well... (e.g. this) ok"
+        );
+    }
+
+    #[test]
+    fn test_cycle_case() {
+        assert_eq!(cycle_case("hello world. bye"), "Hello world. Bye");
+        assert_eq!(cycle_case("Hello world. Bye"), "HELLO WORLD. BYE");
+        assert_eq!(cycle_case("HELLO WORLD. BYE"), "hello world. bye");
+    }
+
+    #[test]
+    fn test_sentence_case_all_continues_across_cues() {
+        let mut subs = vec![
+            cue(0, 1000, "so we went"),
+            cue(1000, 2000, "to the shop."),
+            cue(2000, 3000, "then home"),
+        ];
+        assert_eq!(sentence_case_all(&mut subs), 2);
+        assert_eq!(subs[1].text, "to the shop.");
+        assert_eq!(subs[2].text, "Then home");
+    }
+
+    #[test]
+    fn test_normalize_whitespace() {
+        assert_eq!(
+            normalize_whitespace("  Well ,   I  think so .It's fine\n  ok  ", false),
+            "Well, I think so. It's fine\nok"
+        );
+        assert_eq!(
+            normalize_whitespace("Wait ! Is it 10:30 , or 1,000 people?Yes", false),
+            "Wait! Is it 10:30, or 1,000 people? Yes"
+        );
+        assert_eq!(
+            normalize_whitespace("e.g. the U.S. , at example.com", false),
+            "e.g. the U.S., at example.com"
+        );
+    }
+
+    #[test]
+    fn test_normalize_whitespace_french() {
+        assert_eq!(
+            normalize_whitespace("Quoi?! Il est 10:30 ; viens  , vite !", true),
+            "Quoi ?! Il est 10:30 ; viens, vite !"
+        );
+        assert_eq!(
+            normalize_whitespace("Voir http://a.fr : oui", true),
+            "Voir http://a.fr : oui"
+        );
+    }
+
+    #[test]
+    fn test_replace_all() {
+        let mut subs = vec![
+            cue(0, 1000, "Jon said hi to jon."),
+            cue(1000, 2000, "Nobody here."),
+            cue(2000, 3000, "JON? $5"),
+        ];
+        let replacement = Replacement::literal("jon", "John", false).unwrap();
+        assert_eq!(replacement.count(&subs), (3, 2));
+        assert_eq!(replace_all(&mut subs, "$5", "$1", true), 1);
+        assert_eq!(subs[2].text, "JON? $1");
+        assert_eq!(replace_all(&mut subs, "jon", "John", true), 1);
+        assert_eq!(subs[0].text, "Jon said hi to John.");
+
+        let regex = Replacement::regex(r"(\w+) said", "$1 asked", false).unwrap();
+        assert_eq!(regex.apply(&mut subs), 1);
+        assert_eq!(subs[0].text, "Jon asked hi to John.");
+        assert!(Replacement::regex("x*", "y", false).is_err());
+        assert!(Replacement::regex("(", "y", false).is_err());
+    }
+
+    #[test]
+    fn test_dedupe_keeps_distant_repeats() {
+        let mut subs = vec![cue(0, 1000, "Yes."), cue(5000, 6000, "Yes.")];
+        assert_eq!(dedupe_consecutive(&mut subs, DEFAULT_DEDUPE_MAX_GAP_MS), 0);
+        assert_eq!(subs.len(), 2);
+    }
+}
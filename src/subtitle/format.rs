@@ -0,0 +1,217 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::subtitle::ass::{self, AssStyle};
+use crate::subtitle::srt::{self, SrtWriteOptions, Subtitle};
+use crate::subtitle::vtt;
+
+/// Subtitle file formats. Normally picked by file extension; the
+/// `--input-format` / `--output-format` flags override that.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum SubtitleFormat {
+    Srt,
+    /// WebVTT
+    Vtt,
+    /// Advanced SubStation Alpha (also accepts `ssa`)
+    #[value(alias = "ssa")]
+    Ass,
+    /// YouTube's legacy SubViewer-style captions
+    Sbv,
+    /// Synced lyrics, one timestamped line per cue
+    Lrc,
+}
+
+impl SubtitleFormat {
+    /// Format named by `path`'s extension, if it is one we know
+    pub fn detect(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| ext.parse().ok())
+    }
+
+    /// Format for `path`'s extension; anything unrecognised is treated as SRT
+    pub fn from_path(path: &Path) -> Self {
+        Self::detect(path).unwrap_or(SubtitleFormat::Srt)
+    }
+
+    /// The explicit `format` if there is one, otherwise `path`'s
+    pub fn resolve(path: &Path, format: Option<Self>) -> Self {
+        format.unwrap_or_else(|| Self::from_path(path))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::Vtt => "vtt",
+            SubtitleFormat::Ass => "ass",
+            SubtitleFormat::Sbv => "sbv",
+            SubtitleFormat::Lrc => "lrc",
+        }
+    }
+}
+
+impl FromStr for SubtitleFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        <Self as ValueEnum>::from_str(s, true).map_err(|_| {
+            anyhow::anyhow!(
+                "Unknown subtitle format '{}' (expected srt, vtt, ass, sbv or lrc)",
+                s
+            )
+        })
+    }
+}
+
+impl fmt::Display for SubtitleFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Read subtitles in `format`, or the one `path`'s extension names
+pub fn load_subtitles(path: &Path, format: Option<SubtitleFormat>) -> Result<Vec<Subtitle>> {
+    match SubtitleFormat::resolve(path, format) {
+        SubtitleFormat::Srt => srt::parse_srt(path),
+        SubtitleFormat::Vtt => vtt::parse_vtt(path),
+        other => anyhow::bail!(
+            "Reading {} subtitles isn't supported; convert {} to SRT or WebVTT first",
+            other.as_str().to_uppercase(),
+            path.display()
+        ),
+    }
+}
+
+/// Write subtitles in `format`, or the one `path`'s extension names. The SRT
+/// options only apply to SRT, which alone has cue numbers, and the style
+/// only to ASS and WebVTT.
+pub fn save_subtitles(
+    path: &Path,
+    subtitles: &[Subtitle],
+    format: Option<SubtitleFormat>,
+    options: &SrtWriteOptions,
+    style: &AssStyle,
+) -> Result<()> {
+    match SubtitleFormat::resolve(path, format) {
+        SubtitleFormat::Srt => srt::save_srt(path, subtitles, options),
+        SubtitleFormat::Vtt => vtt::save_vtt(path, subtitles, style),
+        SubtitleFormat::Ass => ass::save_ass(path, subtitles, style),
+        SubtitleFormat::Sbv => save_sbv(path, subtitles),
+        SubtitleFormat::Lrc => save_lrc(path, subtitles),
+    }
+}
+
+/// Save subtitles as SBV
+pub fn save_sbv(path: &Path, subtitles: &[Subtitle]) -> Result<()> {
+    fs::write(path, to_sbv(subtitles)).context("Failed to write SBV file")
+}
+
+/// Save subtitles as LRC
+pub fn save_lrc(path: &Path, subtitles: &[Subtitle]) -> Result<()> {
+    fs::write(path, to_lrc(subtitles)).context("Failed to write LRC file")
+}
+
+/// `H:MM:SS.mmm,H:MM:SS.mmm` on one line, then the text, blank line between cues
+fn to_sbv(subtitles: &[Subtitle]) -> String {
+    subtitles
+        .iter()
+        .map(|sub| {
+            format!(
+                "{},{}\n{}\n",
+                sbv_time(sub.start_time),
+                sbv_time(sub.end_time),
+                sub.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// One `[mm:ss.xx]text` line per cue. LRC has no end times, so a bare
+/// timestamp clears the line wherever a cue ends before the next begins.
+fn to_lrc(subtitles: &[Subtitle]) -> String {
+    let mut content = String::new();
+    for (i, sub) in subtitles.iter().enumerate() {
+        content.push_str(&format!(
+            "[{}]{}\n",
+            lrc_time(sub.start_time),
+            sub.text.replace('\n', " ")
+        ));
+        let next_start = subtitles.get(i + 1).map(|next| next.start_time);
+        if next_start.is_none_or(|start| start > sub.end_time) {
+            content.push_str(&format!("[{}]\n", lrc_time(sub.end_time)));
+        }
+    }
+    content
+}
+
+fn sbv_time(ms: u64) -> String {
+    format!(
+        "{}:{:02}:{:02}.{:03}",
+        ms / 3_600_000,
+        (ms % 3_600_000) / 60_000,
+        (ms % 60_000) / 1_000,
+        ms % 1_000
+    )
+}
+
+/// `mm:ss.xx` in hundredths; minutes keep counting past an hour
+fn lrc_time(ms: u64) -> String {
+    format!(
+        "{:02}:{:02}.{:02}",
+        ms / 60_000,
+        (ms % 60_000) / 1_000,
+        (ms % 1_000) / 10
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cues() -> Vec<Subtitle> {
+        vec![
+            Subtitle::new(1, 1_000, 3_500, "Hello\nthere".to_string()),
+            Subtitle::new(2, 3_500, 5_000, "General Kenobi".to_string()),
+            Subtitle::new(3, 3_725_120, 3_726_000, "Later".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_to_sbv() {
+        assert_eq!(
+            to_sbv(&cues()),
+            "0:00:01.000,0:00:03.500\nHello\nthere\n\n\
+             0:00:03.500,0:00:05.000\nGeneral Kenobi\n\n\
+             1:02:05.120,1:02:06.000\nLater\n"
+        );
+    }
+
+    #[test]
+    fn test_to_lrc() {
+        assert_eq!(
+            to_lrc(&cues()),
+            "[00:01.00]Hello there\n\
+             [00:03.50]General Kenobi\n[00:05.00]\n\
+             [62:05.12]Later\n[62:06.00]\n"
+        );
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        use SubtitleFormat::*;
+        assert_eq!(SubtitleFormat::from_path(Path::new("a.SBV")), Sbv);
+        assert_eq!(SubtitleFormat::from_path(Path::new("a.lrc")), Lrc);
+        assert_eq!(SubtitleFormat::from_path(Path::new("a.ssa")), Ass);
+        assert_eq!(SubtitleFormat::from_path(Path::new("a.srt")), Srt);
+        assert_eq!(SubtitleFormat::from_path(Path::new("a")), Srt);
+        assert_eq!(SubtitleFormat::detect(Path::new("a.txt")), None);
+        assert_eq!(SubtitleFormat::resolve(Path::new("a.txt"), Some(Vtt)), Vtt);
+        assert_eq!("VTT".parse::<SubtitleFormat>().unwrap(), Vtt);
+        assert!("docx".parse::<SubtitleFormat>().is_err());
+    }
+}
@@ -1,10 +1,24 @@
 use anyhow::{Context, Result};
+use std::fmt;
+use std::io::{Read, Write};
 use std::path::Path;
-use std::sync::mpsc::Sender;
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+use whisper_rs::{
+    FullParams, SamplingStrategy, SegmentCallbackData, WhisperContext, WhisperContextParameters,
+    WhisperState, WhisperToken,
+};
 
 use crate::app::ProgressMessage;
-use crate::subtitle::srt::Subtitle;
+use crate::audio::silence::is_loud;
+use crate::audio::wav::WavLayout;
+use crate::cli::args::WhisperModel;
+use crate::subtitle::ass::AssStyle;
+use crate::subtitle::fix;
+use crate::subtitle::format::{self, SubtitleFormat};
+use crate::subtitle::srt::{SrtWriteOptions, Subtitle, WordConfidence};
+use crate::subtitle::timing;
+use crate::utils::cancel::CancelHandle;
 
 /// Temporarily redirect stderr to suppress Whisper's verbose output
 #[cfg(unix)]
@@ -46,25 +60,299 @@ where
     f()
 }
 
+/// The language Whisper settled on when asked to detect it (`auto`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedLanguage {
+    /// Whisper's code for it, e.g. "fr"
+    pub code: String,
+    /// English name, e.g. "French"
+    pub name: String,
+    /// How sure the detection was (0.0 - 1.0), when it could be measured
+    pub probability: Option<f32>,
+}
+
+impl fmt::Display for DetectedLanguage {
+    /// "French (0.87)"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.probability {
+            Some(probability) => write!(f, "{} ({:.2})", self.name, probability),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
 pub struct SubtitleGenerator {
     model_path: std::path::PathBuf,
+    model: WhisperModel,
+    /// Language code to transcribe; unset or "auto" lets Whisper detect it
+    language: Option<String>,
+    /// Keep an English-only model even for another language
+    force_model: bool,
+    /// Have Whisper write English whatever the spoken language
+    translate: bool,
+    trim_silence: bool,
+    continuous: bool,
+    /// Split Whisper segments into one cue per sentence
+    split_sentences: bool,
+    model_source: String,
+    min_gap_ms: u64,
+    min_duration_ms: Option<u64>,
+    max_duration_ms: Option<u64>,
+    temperature: f32,
+    no_speech_threshold: f32,
+    logprob_threshold: f32,
+    write_options: SrtWriteOptions,
+    /// Output format, when it shouldn't be taken from the output's extension
+    output_format: Option<SubtitleFormat>,
+    /// Checked between Whisper decoding steps to stop a cancelled job
+    cancel: CancelHandle,
 }
 
+/// Decoding defaults, matching whisper.cpp
+pub const DEFAULT_TEMPERATURE: f32 = 0.0;
+pub const DEFAULT_NO_SPEECH_THRESHOLD: f32 = 0.6;
+pub const DEFAULT_LOGPROB_THRESHOLD: f32 = -1.0;
+
+/// Reported instead of a cue count when Whisper finds nothing to transcribe
+const NO_SPEECH_MESSAGE: &str = "No speech detected";
+
+/// Where models are downloaded from unless a mirror is configured
+pub const DEFAULT_MODEL_SOURCE: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+/// Sample rate Whisper expects for its input audio
+const WHISPER_SAMPLE_RATE: usize = 16000;
+
+/// Window used when measuring audio energy for silence detection (20ms)
+const SILENCE_WINDOW: usize = WHISPER_SAMPLE_RATE / 50;
+
+/// Audio kept on either side of detected speech so word onsets aren't clipped
+const SILENCE_PADDING: usize = WHISPER_SAMPLE_RATE / 4;
+
 impl SubtitleGenerator {
     pub fn new() -> Self {
-        // Use cache directory for model storage
-        let cache_dir = dirs::cache_dir()
+        Self {
+            // No language means detection, which base.en can't do
+            model_path: Self::model_cache_dir().join(WhisperModel::Base.multilingual_filename()),
+            model: WhisperModel::Base,
+            language: None,
+            force_model: false,
+            translate: false,
+            trim_silence: false,
+            continuous: false,
+            split_sentences: true,
+            model_source: DEFAULT_MODEL_SOURCE.to_string(),
+            min_gap_ms: timing::DEFAULT_MIN_GAP_MS,
+            min_duration_ms: None,
+            max_duration_ms: None,
+            temperature: DEFAULT_TEMPERATURE,
+            no_speech_threshold: DEFAULT_NO_SPEECH_THRESHOLD,
+            logprob_threshold: DEFAULT_LOGPROB_THRESHOLD,
+            write_options: SrtWriteOptions::default(),
+            output_format: None,
+            cancel: CancelHandle::new(),
+        }
+    }
+
+    /// Directory where downloaded Whisper models are cached
+    pub fn model_cache_dir() -> std::path::PathBuf {
+        dirs::cache_dir()
             .unwrap_or_else(|| std::path::PathBuf::from("."))
             .join("auto-subs-tui")
-            .join("models");
+            .join("models")
+    }
 
-        Self {
-            model_path: cache_dir.join("ggml-base.en.bin"),
+    /// Use a specific Whisper model instead of the default base
+    pub fn with_model(mut self, model: WhisperModel) -> Self {
+        self.model = model;
+        self.resolve_model_path();
+        self
+    }
+
+    /// Keep an English-only model even when another language is requested
+    pub fn with_force_model(mut self, force: bool) -> Self {
+        self.force_model = force;
+        self.resolve_model_path();
+        self
+    }
+
+    /// `.en` models only know English: asked for French they write English
+    /// or nonsense. For another language, or to detect one, load the
+    /// multilingual model of the same size instead, unless forced.
+    fn resolve_model_path(&mut self) {
+        let filename = if self.needs_multilingual() && !self.force_model {
+            self.model.multilingual_filename()
+        } else {
+            self.model.filename()
+        };
+        self.model_path = Self::model_cache_dir().join(filename);
+    }
+
+    /// Whether a language other than English, its detection, or a
+    /// translation was requested of an English-only model
+    fn needs_multilingual(&self) -> bool {
+        self.model.is_english_only() && (self.translate || self.language.as_deref() != Some("en"))
+    }
+
+    /// Fail when the settings can't work together: translating needs a
+    /// multilingual model, and `with_force_model` kept an English-only one
+    pub fn check_model(&self) -> Result<()> {
+        if self.translate && self.force_model && self.model.is_english_only() {
+            anyhow::bail!(
+                "--translate needs a multilingual model, but {} is English-only; drop --force to use {}",
+                self.model.filename(),
+                self.model.multilingual_filename()
+            );
         }
+        Ok(())
+    }
+
+    /// Describes the model switch made for the requested language, or the
+    /// mismatch kept by `with_force_model`, for the caller to report
+    pub fn model_note(&self) -> Option<String> {
+        if !self.needs_multilingual() {
+            return None;
+        }
+        let language = self.language.as_deref().unwrap_or_default();
+        Some(if self.force_model && self.wants_detection() {
+            format!(
+                "{} is English-only; transcribing English with it instead of detecting the language",
+                self.model.filename()
+            )
+        } else if self.force_model {
+            format!(
+                "{} is English-only; transcribing '{}' with it anyway",
+                self.model.filename(),
+                language
+            )
+        } else if self.translate {
+            format!(
+                "{} is English-only; using {} to translate",
+                self.model.filename(),
+                self.model.multilingual_filename()
+            )
+        } else if self.wants_detection() {
+            format!(
+                "{} is English-only; using {} to detect the language (--force keeps it)",
+                self.model.filename(),
+                self.model.multilingual_filename()
+            )
+        } else {
+            format!(
+                "{} is English-only; using {} for '{}' (--force keeps it)",
+                self.model.filename(),
+                self.model.multilingual_filename(),
+                language
+            )
+        })
+    }
+
+    /// Transcription language code (e.g. "en", "fr"), or "auto" to let Whisper detect it
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_string());
+        self.resolve_model_path();
+        self
+    }
+
+    /// Have Whisper translate the speech into English as it transcribes
+    pub fn with_translate(mut self, translate: bool) -> Self {
+        self.translate = translate;
+        self.resolve_model_path();
+        self
+    }
+
+    /// Whether no language, or "auto", was asked for
+    fn wants_detection(&self) -> bool {
+        matches!(self.language.as_deref(), None | Some("auto"))
+    }
+
+    /// Whether Whisper is left to detect the spoken language. An English-only
+    /// model kept by `with_force_model` can't, and transcribes English.
+    fn detects_language(&self) -> bool {
+        self.wants_detection() && !(self.force_model && self.model.is_english_only())
+    }
+
+    /// Skip leading and trailing silence instead of sending it to Whisper
+    pub fn with_trim_silence(mut self, trim: bool) -> Self {
+        self.trim_silence = trim;
+        self
+    }
+
+    /// Keep each cue up until the next starts, and the last to the end of
+    /// the audio, instead of leaving a minimum gap between cues
+    pub fn with_continuous(mut self, continuous: bool) -> Self {
+        self.continuous = continuous;
+        self
+    }
+
+    /// Split each Whisper segment into one cue per sentence (the default), or
+    /// keep segments whole, which suits lyrics and other loosely punctuated text
+    pub fn with_sentence_splitting(mut self, split: bool) -> Self {
+        self.split_sentences = split;
+        self
+    }
+
+    /// Fetch models from a mirror instead of Hugging Face. Accepts a base URL
+    /// or a local directory; either must host the ggml files under the names
+    /// returned by `WhisperModel::filename()` (and `multilingual_filename()`
+    /// for languages other than English).
+    pub fn with_model_source(mut self, source: &str) -> Self {
+        self.model_source = source.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Minimum gap kept between generated cues so they never touch
+    pub fn with_min_gap(mut self, min_gap_ms: u64) -> Self {
+        self.min_gap_ms = min_gap_ms;
+        self
+    }
+
+    /// Extend cues shorter than `min_ms` and split ones longer than `max_ms`
+    pub fn with_duration_limits(mut self, min_ms: Option<u64>, max_ms: Option<u64>) -> Self {
+        self.min_duration_ms = min_ms;
+        self.max_duration_ms = max_ms;
+        self
+    }
+
+    /// How cue indices are written to the output SRT
+    pub fn with_write_options(mut self, options: SrtWriteOptions) -> Self {
+        self.write_options = options;
+        self
+    }
+
+    /// Write this format regardless of the output file's extension
+    pub fn with_output_format(mut self, format: Option<SubtitleFormat>) -> Self {
+        self.output_format = format;
+        self
+    }
+
+    /// Stop transcribing once `cancel` is triggered from the UI
+    pub fn with_cancel(mut self, cancel: CancelHandle) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Sampling temperature; 0.0 is deterministic, higher values add randomness
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Probability above which a segment is treated as silence. Only applies
+    /// when the segment also decodes with low confidence (see the logprob threshold).
+    pub fn with_no_speech_threshold(mut self, threshold: f32) -> Self {
+        self.no_speech_threshold = threshold;
+        self
     }
 
-    /// Download the Whisper model if not present
-    fn ensure_model(&self, progress_tx: &Sender<ProgressMessage>) -> Result<()> {
+    /// Average log probability below which a decode counts as failed
+    pub fn with_logprob_threshold(mut self, threshold: f32) -> Self {
+        self.logprob_threshold = threshold;
+        self
+    }
+
+    /// Download the Whisper model if not present. `generate` calls this too,
+    /// but it can be run ahead of time to overlap the download with other work.
+    pub fn ensure_model(&self, progress_tx: &Sender<ProgressMessage>) -> Result<()> {
         if self.model_path.exists() {
             let _ = progress_tx.send(ProgressMessage::Progress(
                 0.1,
@@ -78,23 +366,82 @@ impl SubtitleGenerator {
             std::fs::create_dir_all(parent).context("Failed to create model directory")?;
         }
 
+        let filename = self
+            .model_path
+            .file_name()
+            .context("Invalid model path")?
+            .to_string_lossy()
+            .to_string();
+
+        // Local mirror: copy the model out of the directory
+        if !self.model_source.starts_with("http://") && !self.model_source.starts_with("https://") {
+            let mirror_dir = self
+                .model_source
+                .strip_prefix("file://")
+                .unwrap_or(&self.model_source);
+            let source_path = Path::new(mirror_dir).join(&filename);
+
+            let _ = progress_tx.send(ProgressMessage::Progress(
+                0.05,
+                format!("Copying Whisper model from {}...", source_path.display()),
+            ));
+
+            std::fs::copy(&source_path, &self.model_path)
+                .with_context(|| format!("Failed to copy model from {}", source_path.display()))?;
+
+            let _ = progress_tx.send(ProgressMessage::Progress(
+                0.1,
+                "Model copied successfully!".to_string(),
+            ));
+            return Ok(());
+        }
+
         let _ = progress_tx.send(ProgressMessage::Progress(
             0.05,
-            "Downloading Whisper model (~150MB)...".to_string(),
+            format!("Downloading Whisper model {}...", filename),
         ));
 
-        // Download the base.en model from Hugging Face
-        let model_url =
-            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.en.bin";
+        let model_url = format!("{}/{}", self.model_source, filename);
 
-        let response = ureq::get(model_url)
+        let response = ureq::get(&model_url)
             .call()
-            .context("Failed to download model")?;
+            .with_context(|| format!("Failed to download model from {}", model_url))?;
 
+        // Chunked responses carry no length, so only byte counts can be shown
+        let total_bytes = response
+            .header("Content-Length")
+            .and_then(|len| len.parse::<u64>().ok());
+
+        // Download to a partial file so an interrupted transfer isn't mistaken
+        // for a complete model on the next run
+        let partial_path = self.model_path.with_extension("bin.part");
         let mut file =
-            std::fs::File::create(&self.model_path).context("Failed to create model file")?;
+            std::fs::File::create(&partial_path).context("Failed to create model file")?;
+
+        let mut reader = response.into_reader();
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut downloaded: u64 = 0;
+        let mut last_reported: u64 = 0;
+        loop {
+            let read = reader
+                .read(&mut buffer)
+                .context("Failed to download model")?;
+            if read == 0 {
+                break;
+            }
+            file.write_all(&buffer[..read])
+                .context("Failed to save model")?;
+            downloaded += read as u64;
 
-        std::io::copy(&mut response.into_reader(), &mut file).context("Failed to save model")?;
+            // Report roughly every megabyte
+            if downloaded - last_reported >= 1024 * 1024 {
+                last_reported = downloaded;
+                let _ = progress_tx.send(download_progress(downloaded, total_bytes));
+            }
+        }
+        drop(file);
+
+        std::fs::rename(&partial_path, &self.model_path).context("Failed to save model")?;
 
         let _ = progress_tx.send(ProgressMessage::Progress(
             0.1,
@@ -115,6 +462,27 @@ impl SubtitleGenerator {
         with_suppressed_stderr(|| self.generate_internal(audio_path, output_path, progress_tx))
     }
 
+    /// Transcribe on a background thread and hand back cues as Whisper
+    /// produces them. Iterating yields each cue once; `finish` waits for the
+    /// run and returns the final list, which is what gets written to
+    /// `output_path`.
+    ///
+    /// Streamed cues are raw: repeat removal and duration limits are only
+    /// applied to the list `finish` returns, so it can have fewer or
+    /// re-timed cues. There is no backpressure: Whisper never waits for the
+    /// consumer, and cues queue in memory until they are read.
+    pub fn generate_stream(self, audio_path: &Path, output_path: &Path) -> SubtitleStream {
+        let (tx, rx) = mpsc::channel();
+        let audio_path = audio_path.to_path_buf();
+        let output_path = output_path.to_path_buf();
+        let handle = std::thread::spawn(move || self.generate(&audio_path, &output_path, tx));
+        SubtitleStream {
+            rx,
+            handle,
+            language: None,
+        }
+    }
+
     /// Internal generation function
     fn generate_internal(
         &self,
@@ -122,6 +490,7 @@ impl SubtitleGenerator {
         output_path: &Path,
         progress_tx: Sender<ProgressMessage>,
     ) -> Result<Vec<Subtitle>> {
+        self.check_model()?;
         // Ensure model is available
         self.ensure_model(&progress_tx)?;
 
@@ -134,7 +503,11 @@ impl SubtitleGenerator {
         let mut ctx_params = WhisperContextParameters::default();
         ctx_params.use_gpu(false);
 
-        let ctx = WhisperContext::new_with_params(self.model_path.to_str().unwrap(), ctx_params)
+        let model_path = self
+            .model_path
+            .to_str()
+            .context("Model path is not valid UTF-8")?;
+        let ctx = WhisperContext::new_with_params(model_path, ctx_params)
             .context("Failed to load Whisper model")?;
 
         let _ = progress_tx.send(ProgressMessage::Progress(
@@ -143,7 +516,28 @@ impl SubtitleGenerator {
         ));
 
         // Read audio file
-        let audio_data = self.read_audio(audio_path)?;
+        let mut audio_data = self.read_audio(audio_path, &progress_tx)?;
+        let media_end_ms = (audio_data.len() * 1000 / WHISPER_SAMPLE_RATE) as u64;
+
+        // Timestamps from Whisper are relative to the trimmed audio, so every
+        // cue is shifted by the removed lead-in to line up with the video
+        let mut offset_ms = 0;
+        if self.trim_silence {
+            let (start, end) = speech_bounds(&audio_data);
+            if end > start {
+                offset_ms = (start * 1000 / WHISPER_SAMPLE_RATE) as u64;
+                let _ = progress_tx.send(ProgressMessage::Progress(
+                    0.22,
+                    format!(
+                        "Trimmed {:.1}s of leading and {:.1}s of trailing silence",
+                        start as f64 / WHISPER_SAMPLE_RATE as f64,
+                        (audio_data.len() - end) as f64 / WHISPER_SAMPLE_RATE as f64
+                    ),
+                ));
+                audio_data.truncate(end);
+                audio_data.drain(..start);
+            }
+        }
 
         let _ = progress_tx.send(ProgressMessage::Progress(
             0.25,
@@ -156,17 +550,59 @@ impl SubtitleGenerator {
         params.set_print_progress(false);
         params.set_print_realtime(false);
         params.set_print_timestamps(false);
-        params.set_language(Some("en"));
+        // No language asks Whisper to detect the spoken one
+        if self.detects_language() {
+            params.set_language(None);
+        } else if self.wants_detection() {
+            params.set_language(Some("en"));
+        } else {
+            params.set_language(self.language.as_deref());
+        }
+        params.set_translate(self.translate);
         params.set_token_timestamps(true);
+        params.set_temperature(self.temperature);
+        params.set_no_speech_thold(self.no_speech_threshold);
+        params.set_logprob_thold(self.logprob_threshold);
+
+        // Stream each finished segment to the UI as soon as Whisper produces it
+        let segment_tx = progress_tx.clone();
+        let mut next_index = 1;
+        let min_gap_ms = self.min_gap_ms;
+        let split_sentences = self.split_sentences;
+        // Whisper leaves stray spaces around punctuation; French keeps some
+        let french = self.language.as_deref() == Some("fr");
+        params.set_segment_callback_safe(move |data: SegmentCallbackData| {
+            let start = data.start_timestamp.max(0) as u64 * 10 + offset_ms;
+            let end = data.end_timestamp.max(0) as u64 * 10 + offset_ms;
+            let text = fix::normalize_whitespace(&data.text, french);
+            for subtitle in
+                segment_to_subtitles(next_index, start, end, &text, min_gap_ms, split_sentences)
+            {
+                next_index += 1;
+                let _ = segment_tx.send(ProgressMessage::Segment(subtitle));
+            }
+        });
+
+        // Whisper polls this between decoding steps and gives up once it returns true
+        let cancel = self.cancel.clone();
+        params.set_abort_callback_safe(move || cancel.is_cancelled());
 
         // Create state and run transcription
         let mut state = ctx
             .create_state()
             .context("Failed to create Whisper state")?;
 
-        state
-            .full(params, &audio_data)
-            .context("Transcription failed")?;
+        let result = state.full(params, &audio_data);
+        if self.cancel.is_cancelled() {
+            anyhow::bail!("Transcription cancelled");
+        }
+        result.context("Transcription failed")?;
+
+        if self.detects_language() {
+            if let Some(language) = detected_language(&state) {
+                let _ = progress_tx.send(ProgressMessage::Language(language));
+            }
+        }
 
         let _ = progress_tx.send(ProgressMessage::Progress(
             0.9,
@@ -183,67 +619,106 @@ impl SubtitleGenerator {
             let start = state
                 .full_get_segment_t0(i)
                 .context("Failed to get start time")? as u64
-                * 10; // Convert to ms
+                * 10 // Convert to ms
+                + offset_ms;
             let end = state
                 .full_get_segment_t1(i)
                 .context("Failed to get end time")? as u64
-                * 10;
+                * 10
+                + offset_ms;
             let text = state
                 .full_get_segment_text(i)
                 .context("Failed to get text")?;
 
-            let text = text.trim().to_string();
-            if !text.is_empty() {
-                // Split text into sentences for more detailed subtitles
-                let sentences = self.split_into_sentences(&text);
-
-                if sentences.len() == 1 {
-                    // Single sentence or short text - keep as is
-                    subtitles.push(Subtitle::new(subtitles.len() + 1, start, end, text));
-                } else {
-                    // Multiple sentences - distribute time proportionally
-                    let total_duration = end - start;
-                    let total_chars: usize = sentences.iter().map(|s| s.len()).sum();
-
-                    let mut current_time = start;
-                    for sentence in sentences {
-                        if sentence.is_empty() {
-                            continue;
-                        }
-
-                        // Calculate duration based on sentence length
-                        let sentence_duration = (total_duration as f64 * sentence.len() as f64
-                            / total_chars as f64)
-                            as u64;
-                        let sentence_end = (current_time + sentence_duration).min(end);
-
-                        subtitles.push(Subtitle::new(
-                            subtitles.len() + 1,
-                            current_time,
-                            sentence_end,
-                            sentence.to_string(),
-                        ));
-
-                        current_time = sentence_end;
-                    }
-                }
-            }
+            // Normalized the same way as the streamed cues, so they still match
+            let mut cues = segment_to_subtitles(
+                subtitles.len() + 1,
+                start,
+                end,
+                &fix::normalize_whitespace(&text, french),
+                self.min_gap_ms,
+                self.split_sentences,
+            );
+            attach_confidence(
+                &mut cues,
+                segment_words(&state, ctx.token_eot(), i, offset_ms)?,
+            );
+            subtitles.extend(cues);
         }
 
-        // Save to file
-        crate::subtitle::srt::save_srt(output_path, &subtitles)?;
+        // Whisper tends to repeat the same line over silence or music
+        let removed = fix::dedupe_consecutive(&mut subtitles, fix::DEFAULT_DEDUPE_MAX_GAP_MS);
+        timing::clamp_durations(
+            &mut subtitles,
+            self.min_duration_ms,
+            self.max_duration_ms,
+            self.min_gap_ms,
+        );
+        if self.continuous {
+            timing::make_continuous(&mut subtitles, Some(media_end_ms));
+        } else {
+            timing::enforce_min_gap(&mut subtitles, self.min_gap_ms);
+        }
 
-        let _ = progress_tx.send(ProgressMessage::Progress(
-            1.0,
-            format!("Generated {} subtitles!", subtitles.len()),
-        ));
+        // The streamed cues had neither this cleanup nor token data; hand over
+        // the final ones, as saved
+        let _ = progress_tx.send(ProgressMessage::Transcript(subtitles.clone()));
+
+        // Save to file, in the format the output's extension asks for
+        format::save_subtitles(
+            output_path,
+            &subtitles,
+            self.output_format,
+            &self.write_options,
+            &AssStyle::default(),
+        )?;
+
+        // Silent or music-only audio; say so instead of "Generated 0 subtitles!"
+        let summary = if subtitles.is_empty() {
+            NO_SPEECH_MESSAGE.to_string()
+        } else if removed > 0 {
+            format!(
+                "Generated {} subtitles! (removed {} repeated cues)",
+                subtitles.len(),
+                removed
+            )
+        } else {
+            format!("Generated {} subtitles!", subtitles.len())
+        };
+        let _ = progress_tx.send(ProgressMessage::Progress(1.0, summary));
         let _ = progress_tx.send(ProgressMessage::Complete);
 
         Ok(subtitles)
     }
 
-    /// Read and convert audio file to f32 samples
-    fn read_audio(&self, path: &Path) -> Result<Vec<f32>> {
+    /// Read and convert audio file to f32 samples. A file cut off by an
+    /// interrupted extraction is read up to where it ends, with a warning.
+    fn read_audio(&self, path: &Path, progress_tx: &Sender<ProgressMessage>) -> Result<Vec<f32>> {
+        let layout = WavLayout::read(path).with_context(|| {
+            format!(
+                "{} is not a usable WAV file; delete it and extract the audio again",
+                path.display()
+            )
+        })?;
+        if let Some(problem) = layout.problem() {
+            if layout.available_secs() < 1.0 {
+                anyhow::bail!(
+                    "{} is incomplete ({}); delete it and extract the audio again",
+                    path.display(),
+                    problem
+                );
+            }
+            let action = if layout.is_truncated() {
+                "transcribing what is there (re-extract the audio for the rest)"
+            } else {
+                "transcribing it anyway"
+            };
+            let _ = progress_tx.send(ProgressMessage::Progress(
+                0.2,
+                format!("⚠️  {}: {}; {}", path.display(), problem, action),
+            ));
+        }
+
         let reader = hound::WavReader::open(path).context("Failed to open WAV file")?;
         let spec = reader.spec();
 
@@ -258,66 +733,324 @@ impl SubtitleGenerator {
         let samples: Vec<f32> = match spec.sample_format {
             hound::SampleFormat::Int => {
                 let max_value = (1 << (spec.bits_per_sample - 1)) as f32;
+                // Stop at the first unreadable sample: past the end of a cut-off file
                 reader
                     .into_samples::<i32>()
-                    .filter_map(|s| s.ok())
+                    .map_while(Result::ok)
                     .map(|s| s as f32 / max_value)
                     .collect()
             }
-            hound::SampleFormat::Float => reader
-                .into_samples::<f32>()
-                .filter_map(|s| s.ok())
-                .collect(),
+            hound::SampleFormat::Float => {
+                reader.into_samples::<f32>().map_while(Result::ok).collect()
+            }
         };
 
         Ok(samples)
     }
+}
 
-    /// Split text into sentences for more detailed subtitles
-    fn split_into_sentences<'a>(&self, text: &'a str) -> Vec<&'a str> {
-        let mut sentences = Vec::new();
-        let mut start = 0;
-        let chars: Vec<char> = text.chars().collect();
-
-        for (i, ch) in chars.iter().enumerate() {
-            // Check for sentence endings: . ! ?
-            if matches!(ch, '.' | '!' | '?') {
-                // Look ahead to see if there's a space or end of string
-                let is_sentence_end = if i + 1 < chars.len() {
-                    // Next char should be space, quote, or another punctuation
-                    matches!(chars[i + 1], ' ' | '"' | '\'' | ')' | ']')
-                } else {
-                    true // End of string
-                };
-
-                if is_sentence_end {
-                    let end = text
-                        .char_indices()
-                        .nth(i + 1)
-                        .map(|(pos, _)| pos)
-                        .unwrap_or(text.len());
-                    let sentence = text[start..end].trim();
-                    if !sentence.is_empty() {
-                        sentences.push(sentence);
-                    }
-                    start = end;
-                }
+/// Progress message for a model download, mapped into the 0.05-0.1 slice of
+/// the overall bar that `ensure_model` owns
+fn download_progress(downloaded: u64, total: Option<u64>) -> ProgressMessage {
+    const MB: f64 = 1024.0 * 1024.0;
+    match total {
+        Some(total) if total > 0 => {
+            let fraction = (downloaded as f64 / total as f64).min(1.0) as f32;
+            ProgressMessage::Progress(
+                0.05 + 0.05 * fraction,
+                format!(
+                    "Downloaded {:.1}/{:.1} MB",
+                    downloaded as f64 / MB,
+                    total as f64 / MB
+                ),
+            )
+        }
+        _ => {
+            ProgressMessage::Progress(0.05, format!("Downloaded {:.1} MB", downloaded as f64 / MB))
+        }
+    }
+}
+
+/// Find the sample range that contains speech, padded slightly on both sides.
+/// Returns `(0, 0)` when the whole clip is below the silence threshold.
+fn speech_bounds(samples: &[f32]) -> (usize, usize) {
+    let windows: Vec<&[f32]> = samples.chunks(SILENCE_WINDOW).collect();
+    let first = match windows.iter().position(|w| is_loud(w)) {
+        Some(i) => i,
+        None => return (0, 0),
+    };
+    let last = windows.iter().rposition(|w| is_loud(w)).unwrap_or(first);
+
+    let start = (first * SILENCE_WINDOW).saturating_sub(SILENCE_PADDING);
+    let end = ((last + 1) * SILENCE_WINDOW + SILENCE_PADDING).min(samples.len());
+    (start, end)
+}
+
+/// The language the last `full` run detected. Whisper doesn't keep the
+/// probabilities, so detection runs again on the spectrogram it left behind.
+fn detected_language(state: &WhisperState) -> Option<DetectedLanguage> {
+    let id = state.full_lang_id_from_state().ok()?;
+    let code = whisper_rs::get_lang_str(id)?;
+    let name = whisper_rs::get_lang_str_full(id)?;
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get().min(4));
+    let probability = state
+        .lang_detect(0, threads)
+        .ok()
+        .and_then(|(_, probabilities)| probabilities.get(id as usize).copied());
+
+    let mut chars = name.chars();
+    let name = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => code.to_string(),
+    };
+    Some(DetectedLanguage {
+        code: code.to_string(),
+        name,
+        probability,
+    })
+}
+
+/// Words of segment `segment`, each scored by its least likely token and
+/// timed from its tokens' timestamps (shifted by `offset_ms`)
+fn segment_words(
+    state: &WhisperState,
+    eot: WhisperToken,
+    segment: i32,
+    offset_ms: u64,
+) -> Result<Vec<WordConfidence>> {
+    let mut words: Vec<WordConfidence> = Vec::new();
+    for token in 0..state.full_n_tokens(segment)? {
+        // Timestamps and other special tokens sort after end-of-text
+        if state.full_get_token_id(segment, token)? >= eot {
+            continue;
+        }
+        // Lossy: a multi-byte character can be split across tokens
+        let text = state.full_get_token_text_lossy(segment, token)?;
+        let probability = state.full_get_token_prob(segment, token)?;
+        let data = state.full_get_token_data(segment, token)?;
+        let start_time = data.t0.max(0) as u64 * 10 + offset_ms;
+        let end_time = (data.t1.max(0) as u64 * 10 + offset_ms).max(start_time);
+        match words.last_mut() {
+            Some(word) if !text.starts_with(' ') => {
+                word.text.push_str(&text);
+                word.probability = word.probability.min(probability);
+                word.end_time = word.end_time.max(end_time);
             }
+            _ => words.push(WordConfidence {
+                text: text.trim_start().to_string(),
+                probability,
+                start_time,
+                end_time,
+            }),
         }
+    }
+    Ok(words)
+}
+
+/// Give each cue split from a segment its share of the segment's words.
+/// Left unset if the words don't line up with the cue text.
+fn attach_confidence(cues: &mut [Subtitle], words: Vec<WordConfidence>) {
+    let count = |cue: &Subtitle| cue.text.split_whitespace().count();
+    if cues.iter().map(count).sum::<usize>() != words.len() {
+        return;
+    }
+    let mut words = words.into_iter();
+    for cue in cues {
+        cue.confidence = Some(words.by_ref().take(count(cue)).collect());
+    }
+}
 
-        // Add remaining text if any
-        if start < text.len() {
-            let sentence = text[start..].trim();
-            if !sentence.is_empty() {
-                sentences.push(sentence);
+/// Cues from a running `generate_stream`, in the order they are transcribed
+pub struct SubtitleStream {
+    rx: Receiver<ProgressMessage>,
+    handle: JoinHandle<Result<Vec<Subtitle>>>,
+    language: Option<DetectedLanguage>,
+}
+
+impl SubtitleStream {
+    /// The language Whisper detected for `auto`, once the stream has ended
+    pub fn detected_language(&self) -> Option<&DetectedLanguage> {
+        self.language.as_ref()
+    }
+
+    /// Wait for transcription to end and return the finished subtitles.
+    /// Cues not yet read from the stream are dropped.
+    pub fn finish(self) -> Result<Vec<Subtitle>> {
+        self.handle
+            .join()
+            .map_err(|_| anyhow::anyhow!("Transcription thread panicked"))?
+    }
+}
+
+impl Iterator for SubtitleStream {
+    type Item = Subtitle;
+
+    fn next(&mut self) -> Option<Subtitle> {
+        // Ends at Complete, or when the worker stops without sending it (an error)
+        loop {
+            match self.rx.recv().ok()? {
+                ProgressMessage::Segment(subtitle) => return Some(subtitle),
+                ProgressMessage::Language(language) => self.language = Some(language),
+                ProgressMessage::Progress(..) | ProgressMessage::Transcript(_) => continue,
+                ProgressMessage::Complete | ProgressMessage::Error(_) => return None,
             }
         }
+    }
+}
+
+/// Convert a single Whisper segment into one or more subtitles, splitting on
+/// sentence boundaries (when `split`) and distributing the segment's time
+/// proportionally
+fn segment_to_subtitles(
+    first_index: usize,
+    start: u64,
+    end: u64,
+    text: &str,
+    min_gap_ms: u64,
+    split: bool,
+) -> Vec<Subtitle> {
+    let mut subtitles = Vec::new();
+    if text.is_empty() {
+        return subtitles;
+    }
+
+    // Split text into sentences for more detailed subtitles
+    let sentences = if split {
+        fix::split_sentences(text)
+    } else {
+        vec![text]
+    };
+
+    if sentences.len() == 1 {
+        // Single sentence or short text - keep as is
+        subtitles.push(Subtitle::new(first_index, start, end, text.to_string()));
+    } else {
+        // Multiple sentences - distribute time proportionally
+        let total_duration = end.saturating_sub(start);
+        let total_chars: usize = sentences.iter().map(|s| s.len()).sum();
+
+        let mut current_time = start;
+        for sentence in sentences {
+            if sentence.is_empty() {
+                continue;
+            }
 
-        // If no sentences were found, return the whole text
-        if sentences.is_empty() {
-            sentences.push(text.trim());
+            // Calculate duration based on sentence length
+            let sentence_duration =
+                (total_duration as f64 * sentence.len() as f64 / total_chars as f64) as u64;
+            let sentence_end = (current_time + sentence_duration).min(end);
+
+            subtitles.push(Subtitle::new(
+                first_index + subtitles.len(),
+                current_time,
+                sentence_end,
+                sentence.to_string(),
+            ));
+
+            current_time = sentence_end;
         }
 
-        sentences
+        // Split sentences share boundaries; pull them apart
+        timing::enforce_min_gap(&mut subtitles, min_gap_ms);
+    }
+
+    subtitles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attach_confidence_splits_words_across_cues() {
+        let word = |text: &str, probability| WordConfidence {
+            text: text.to_string(),
+            probability,
+            start_time: 0,
+            end_time: 0,
+        };
+        let mut cues = vec![
+            Subtitle::new(1, 0, 1000, "Hello there.".to_string()),
+            Subtitle::new(2, 1000, 2000, "Bye.".to_string()),
+        ];
+        attach_confidence(
+            &mut cues,
+            vec![word("Hello", 0.9), word("there.", 0.3), word("Bye.", 0.8)],
+        );
+        assert_eq!(cues[0].confidence.as_ref().unwrap().len(), 2);
+        assert_eq!(cues[1].confidence.as_ref().unwrap()[0].text, "Bye.");
+
+        let mut cues = vec![Subtitle::new(1, 0, 1000, "Hello there.".to_string())];
+        attach_confidence(&mut cues, vec![word("Hello", 0.9)]);
+        assert!(cues[0].confidence.is_none());
+    }
+
+    #[test]
+    fn test_other_languages_use_multilingual_model() {
+        let file = |generator: &SubtitleGenerator| {
+            generator
+                .model_path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string()
+        };
+        let english = SubtitleGenerator::new()
+            .with_language("en")
+            .with_model(WhisperModel::Small);
+        assert_eq!(file(&english), "ggml-small.en.bin");
+        assert!(english.model_note().is_none());
+
+        let french = english.with_language("fr");
+        assert_eq!(file(&french), "ggml-small.bin");
+        assert!(french.model_note().unwrap().contains("ggml-small.bin"));
+
+        let forced = french.with_force_model(true);
+        assert_eq!(file(&forced), "ggml-small.en.bin");
+        assert!(forced.model_note().unwrap().contains("anyway"));
+        assert!(forced.check_model().is_ok());
+
+        // The defaults, base and auto, detect the language with base.bin;
+        // kept on base.en, it transcribes English without detecting
+        let default = SubtitleGenerator::new().with_model(WhisperModel::Base);
+        assert_eq!(file(&default), "ggml-base.bin");
+        assert!(default.detects_language());
+        let auto = default.with_language("auto");
+        assert_eq!(file(&auto), "ggml-base.bin");
+        assert!(auto.model_note().unwrap().contains("detect"));
+        let forced = auto.with_force_model(true);
+        assert_eq!(file(&forced), "ggml-base.en.bin");
+        assert!(!forced.detects_language());
+        assert!(forced
+            .model_note()
+            .unwrap()
+            .contains("instead of detecting"));
+
+        // Translating needs the multilingual model too, and can't be forced off it
+        let translating = SubtitleGenerator::new()
+            .with_model(WhisperModel::Small)
+            .with_language("auto")
+            .with_translate(true);
+        assert_eq!(file(&translating), "ggml-small.bin");
+        assert!(translating.check_model().is_ok());
+        let forced = translating.with_force_model(true);
+        assert!(forced
+            .check_model()
+            .unwrap_err()
+            .to_string()
+            .contains("English-only"));
+    }
+
+    #[test]
+    fn test_speech_bounds_trims_silence() {
+        let second = WHISPER_SAMPLE_RATE;
+        let mut samples = vec![0.0; 2 * second];
+        samples.extend(vec![0.5; second]);
+        samples.extend(vec![0.0; 3 * second]);
+
+        let (start, end) = speech_bounds(&samples);
+        assert_eq!(start, 2 * second - SILENCE_PADDING);
+        assert_eq!(end, 3 * second + SILENCE_PADDING);
+        assert_eq!(speech_bounds(&vec![0.0; second]), (0, 0));
     }
 }
@@ -0,0 +1,159 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::cli::args::SubtitlePosition;
+use crate::subtitle::ass::{ass_markup, ass_time};
+use crate::subtitle::srt::Subtitle;
+
+/// The script header FFmpeg gives SRT input, so a karaoke burn gets the same
+/// font size and margins from the burner's `force_style` as an SRT one. `\k`
+/// runs fill each word from SecondaryColour (still to be sung) to
+/// PrimaryColour (sung); the burner's colors replace these defaults.
+const ASS_HEADER: &str = "[Script Info]
+ScriptType: v4.00+
+PlayResX: 384
+PlayResY: 288
+ScaledBorderAndShadow: yes
+YCbCr Matrix: None
+
+[V4+ Styles]
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding
+Style: Default,Arial,16,&H0000FFFF,&H00FFFFFF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,1,0,2,10,10,10,1
+
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+";
+
+/// Give `cues` the word timings of the `transcript` cues with the same text
+/// that overlap them. Cues whose text was edited since (or that Whisper
+/// didn't time) are left without. Returns how many cues got timings.
+pub fn attach_word_timings(cues: &mut [Subtitle], transcript: &[Subtitle]) -> usize {
+    let mut timed = 0;
+    for cue in cues.iter_mut() {
+        let words = transcript
+            .iter()
+            .filter(|t| t.start_time < cue.end_time && cue.start_time < t.end_time)
+            .find(|t| t.text == cue.text)
+            .and_then(|t| t.confidence.clone());
+        if let Some(words) = words {
+            cue.confidence = Some(words);
+            timed += 1;
+        }
+    }
+    timed
+}
+
+/// ASS text of a cue with a `{\k<cs>}` run before each word, timed from its
+/// word timings (clamped into the cue); `None` when it has none that match
+/// its text
+pub fn karaoke_text(sub: &Subtitle) -> Option<String> {
+    let words = sub.confidence.as_ref()?;
+    if words.is_empty() || words.len() != sub.text.split_whitespace().count() {
+        return None;
+    }
+
+    // Centiseconds from the cue start, rounded, so the runs add up exactly
+    let at = |ms: u64| (ms.clamp(sub.start_time, sub.end_time) - sub.start_time + 5) / 10;
+    let mut text = String::new();
+    let mut elapsed = at(words[0].start_time);
+    if elapsed > 0 {
+        // Silence before the first word
+        text.push_str(&format!("{{\\k{}}}", elapsed));
+    }
+    let mut index = 0;
+    for (line_index, line) in sub.text.lines().enumerate() {
+        if line_index > 0 {
+            text.push_str("\\N");
+        }
+        for (word_index, word) in line.split_whitespace().enumerate() {
+            if word_index > 0 {
+                text.push(' ');
+            }
+            // A word lasts until the next one starts, the last until it ends
+            let until = match words.get(index + 1) {
+                Some(next) => at(next.start_time),
+                None => at(words[index].end_time),
+            }
+            .max(elapsed);
+            text.push_str(&format!("{{\\k{}}}{}", until - elapsed, ass_markup(word)));
+            elapsed = until;
+            index += 1;
+        }
+    }
+    Some(text)
+}
+
+/// A karaoke ASS script of `subtitles`. Cues without word timings are shown
+/// whole, in the sung color.
+pub fn to_karaoke_ass(subtitles: &[Subtitle]) -> String {
+    let mut ass = ASS_HEADER.to_string();
+    for sub in subtitles {
+        let tag = match sub.position {
+            Some(SubtitlePosition::Bottom) => "{\\an2}",
+            Some(SubtitlePosition::Middle) => "{\\an5}",
+            Some(SubtitlePosition::Top) => "{\\an8}",
+            None => "",
+        };
+        let text = karaoke_text(sub)
+            .unwrap_or_else(|| ass_markup(&sub.text.lines().collect::<Vec<_>>().join("\\N")));
+        ass.push_str(&format!(
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}{}\n",
+            ass_time(sub.start_time),
+            ass_time(sub.end_time),
+            tag,
+            text
+        ));
+    }
+    ass
+}
+
+/// Save a karaoke ASS script of `subtitles` for burning
+pub fn save_karaoke(path: &Path, subtitles: &[Subtitle]) -> Result<()> {
+    fs::write(path, to_karaoke_ass(subtitles)).context("Failed to write karaoke ASS file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subtitle::srt::WordConfidence;
+
+    fn word(text: &str, start_time: u64, end_time: u64) -> WordConfidence {
+        WordConfidence {
+            text: text.to_string(),
+            probability: 1.0,
+            start_time,
+            end_time,
+        }
+    }
+
+    #[test]
+    fn test_karaoke_runs_follow_word_timings() {
+        let mut sub = Subtitle::new(1, 1000, 3000, "Hello <i>big</i>\nworld".to_string());
+        sub.confidence = Some(vec![
+            word("Hello", 1200, 1500),
+            word("big", 1600, 2000),
+            word("world", 2100, 3500),
+        ]);
+        assert_eq!(
+            karaoke_text(&sub).unwrap(),
+            "{\\k20}{\\k40}Hello {\\k50}{\\i1}big{\\i0}\\N{\\k90}world"
+        );
+
+        let ass = to_karaoke_ass(&[sub.clone()]);
+        assert!(ass.contains("Dialogue: 0,0:00:01.00,0:00:03.00,Default,,0,0,0,,{\\k20}"));
+
+        // Edited since transcription: shown whole
+        sub.text = "Hello world".to_string();
+        assert_eq!(karaoke_text(&sub), None);
+
+        let transcript = vec![sub.clone()];
+        let mut cues = vec![
+            Subtitle::new(1, 1000, 2800, "Hello world".to_string()),
+            Subtitle::new(2, 5000, 6000, "Hello world".to_string()),
+        ];
+        assert_eq!(attach_word_timings(&mut cues, &transcript), 1);
+        assert!(cues[1].confidence.is_none());
+        assert_eq!(ass_time(3_723_450), "1:02:03.45");
+    }
+}
@@ -0,0 +1,307 @@
+use crate::subtitle::burner::DEFAULT_MIN_FONT_SIZE;
+use crate::subtitle::srt::Subtitle;
+
+/// Reading speed above which a cue is flagged as too fast (characters per second)
+pub const DEFAULT_MAX_CPS: f64 = 20.0;
+/// Longest a single cue should stay on screen
+pub const DEFAULT_MAX_DURATION_MS: u64 = 7000;
+/// Shortest a cue should stay on screen to be readable
+pub const DEFAULT_MIN_DURATION_MS: u64 = 700;
+/// Average glyph width as a fraction of the font size, used to estimate how
+/// wide a line renders. Rough, but close enough for typical sans-serif fonts.
+pub const DEFAULT_CHAR_WIDTH_FACTOR: f64 = 0.6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// A problem found in one cue
+#[derive(Debug, Clone)]
+pub struct LintIssue {
+    /// Position of the cue in the subtitle list (0-based)
+    pub position: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Thresholds used by `lint`
+#[derive(Debug, Clone)]
+pub struct LintRules {
+    pub max_cps: f64,
+    pub max_duration_ms: u64,
+    pub min_duration_ms: u64,
+    /// Width of the area subtitles are rendered into; `None` skips the overflow check
+    pub overlay_width: Option<u32>,
+    /// Font size the subtitles are rendered at
+    pub font_size: u32,
+    pub char_width_factor: f64,
+    /// End of the video in milliseconds; `None` skips the past-the-end check
+    pub media_end_ms: Option<u64>,
+}
+
+impl Default for LintRules {
+    fn default() -> Self {
+        Self {
+            max_cps: DEFAULT_MAX_CPS,
+            max_duration_ms: DEFAULT_MAX_DURATION_MS,
+            min_duration_ms: DEFAULT_MIN_DURATION_MS,
+            overlay_width: None,
+            font_size: DEFAULT_MIN_FONT_SIZE,
+            char_width_factor: DEFAULT_CHAR_WIDTH_FACTOR,
+            media_end_ms: None,
+        }
+    }
+}
+
+/// Check every cue for timing and readability problems, in list order
+pub fn lint(subtitles: &[Subtitle], rules: &LintRules) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    for (position, sub) in subtitles.iter().enumerate() {
+        let mut issue = |severity, message: String| {
+            issues.push(LintIssue {
+                position,
+                severity,
+                message,
+            })
+        };
+
+        if sub.end_time <= sub.start_time {
+            issue(Severity::Error, "ends before it starts".to_string());
+            continue;
+        }
+
+        if let Some(next) = subtitles.get(position + 1) {
+            if sub.end_time > next.start_time {
+                issue(
+                    Severity::Error,
+                    format!(
+                        "overlaps the next cue by {}ms",
+                        sub.end_time - next.start_time
+                    ),
+                );
+            }
+        }
+
+        if let Some(media_end) = rules.media_end_ms {
+            if sub.start_time >= media_end {
+                issue(
+                    Severity::Warning,
+                    format!(
+                        "starts after the video ends ({}), so it is never shown",
+                        Subtitle::format_time(media_end)
+                    ),
+                );
+            } else if sub.end_time > media_end {
+                issue(
+                    Severity::Warning,
+                    format!(
+                        "runs {}ms past the end of the video ({})",
+                        sub.end_time - media_end,
+                        Subtitle::format_time(media_end)
+                    ),
+                );
+            }
+        }
+
+        let text = sub.text.trim();
+        if text.is_empty() {
+            issue(Severity::Warning, "has no text".to_string());
+            continue;
+        }
+
+        let duration = sub.end_time - sub.start_time;
+        let cps = reading_chars(text) as f64 * 1000.0 / duration as f64;
+        if cps > rules.max_cps {
+            issue(
+                Severity::Warning,
+                format!("too fast to read ({:.1} chars/s)", cps),
+            );
+        }
+        if duration > rules.max_duration_ms {
+            issue(
+                Severity::Warning,
+                format!("on screen too long ({:.1}s)", duration as f64 / 1000.0),
+            );
+        } else if duration < rules.min_duration_ms {
+            issue(
+                Severity::Warning,
+                format!("too short to read ({}ms)", duration),
+            );
+        }
+
+        if let Some(overlay_width) = rules.overlay_width {
+            for (line_no, line) in text.lines().enumerate() {
+                let width = estimated_width(line, rules);
+                if width > overlay_width as f64 {
+                    issue(
+                        Severity::Warning,
+                        format!(
+                            "line {} may be cut off (~{:.0}px wide, overlay is {}px); consider wrapping it",
+                            line_no + 1,
+                            width,
+                            overlay_width
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+/// Overall figures for a subtitle file, summarising what `lint` checks cue by cue
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    /// Time with at least one cue on screen (overlaps counted once)
+    pub shown_ms: u64,
+    /// `shown_ms` as a fraction of the media's length, when known
+    pub coverage: Option<f64>,
+    /// Characters per second over all cues together
+    pub average_cps: f64,
+    /// Reading speed of the fastest cue
+    pub max_cps: f64,
+    /// Position (0-based) and duration of the longest cue
+    pub longest: Option<(usize, u64)>,
+}
+
+/// Compute `Stats` for `subtitles`; `media_end_ms` enables coverage
+pub fn stats(subtitles: &[Subtitle], media_end_ms: Option<u64>) -> Stats {
+    let mut stats = Stats::default();
+    let mut total_chars = 0;
+    let mut total_ms = 0;
+    for (position, sub) in subtitles.iter().enumerate() {
+        let duration = sub.end_time.saturating_sub(sub.start_time);
+        if duration == 0 {
+            continue;
+        }
+        let chars = reading_chars(&sub.text);
+        total_chars += chars;
+        total_ms += duration;
+        stats.max_cps = stats.max_cps.max(chars as f64 * 1000.0 / duration as f64);
+        if stats.longest.is_none_or(|(_, longest)| duration > longest) {
+            stats.longest = Some((position, duration));
+        }
+    }
+    if total_ms > 0 {
+        stats.average_cps = total_chars as f64 * 1000.0 / total_ms as f64;
+    }
+
+    // Union of the cue intervals, so overlapping cues aren't counted twice
+    let mut spans: Vec<(u64, u64)> = subtitles
+        .iter()
+        .filter(|sub| sub.end_time > sub.start_time)
+        .map(|sub| (sub.start_time, sub.end_time))
+        .collect();
+    spans.sort_unstable();
+    let mut covered_until = 0;
+    for (start, end) in spans {
+        let start = start.max(covered_until);
+        if end > start {
+            stats.shown_ms += end - start;
+            covered_until = end;
+        }
+    }
+
+    stats.coverage = media_end_ms
+        .filter(|&end| end > 0)
+        .map(|end| stats.shown_ms as f64 / end as f64);
+    stats
+}
+
+/// Characters that count towards reading speed (whitespace doesn't)
+fn reading_chars(text: &str) -> usize {
+    text.chars().filter(|c| !c.is_whitespace()).count()
+}
+
+/// Approximate rendered width of a line in pixels
+fn estimated_width(line: &str, rules: &LintRules) -> f64 {
+    line.trim().chars().count() as f64 * rules.font_size as f64 * rules.char_width_factor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_count_overlaps_once() {
+        let subs = vec![
+            Subtitle::new(1, 0, 2000, "Hello there".to_string()),
+            Subtitle::new(2, 1000, 3000, "Overlapping".to_string()),
+            Subtitle::new(3, 5000, 10000, "Slow".to_string()),
+        ];
+        let stats = stats(&subs, Some(20000));
+        assert_eq!(stats.shown_ms, 8000);
+        assert_eq!(stats.coverage, Some(0.4));
+        assert_eq!(stats.longest, Some((2, 5000)));
+        assert!((stats.max_cps - 5.5).abs() < 1e-9);
+        assert!((stats.average_cps - 25.0 / 9.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_lint_flags_overlap_and_speed() {
+        let subs = vec![
+            Subtitle::new(1, 0, 1200, "Hello there".to_string()),
+            Subtitle::new(
+                2,
+                1000,
+                2000,
+                "This line is far too long to read in a second".to_string(),
+            ),
+            Subtitle::new(3, 3000, 5000, "Fine".to_string()),
+        ];
+        let issues = lint(&subs, &LintRules::default());
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].position, 0);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[1].position, 1);
+        assert!(issues[1].message.contains("too fast"));
+    }
+
+    #[test]
+    fn test_lint_flags_overflowing_line() {
+        let subs = vec![Subtitle::new(
+            1,
+            0,
+            4000,
+            "Short line\nThis second line is long enough to overflow".to_string(),
+        )];
+        let rules = LintRules {
+            overlay_width: Some(640),
+            font_size: 30,
+            ..LintRules::default()
+        };
+        let issues = lint(&subs, &rules);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.starts_with("line 2 may be cut off"));
+    }
+
+    #[test]
+    fn test_lint_flags_cues_past_media_end() {
+        let subs = vec![
+            Subtitle::new(1, 0, 2000, "Inside".to_string()),
+            Subtitle::new(2, 3000, 5500, "Crosses the end".to_string()),
+            Subtitle::new(3, 6000, 8000, "After the end".to_string()),
+        ];
+        let rules = LintRules {
+            media_end_ms: Some(5000),
+            ..LintRules::default()
+        };
+        let issues = lint(&subs, &rules);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.starts_with("runs 500ms past the end"));
+        assert!(issues[1].message.starts_with("starts after the video ends"));
+    }
+}
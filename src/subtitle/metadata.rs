@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Settings a subtitle file was produced with, written as a JSON sidecar so
+/// results can be audited and reproduced
+#[derive(Debug, Clone, Serialize)]
+pub struct RunMetadata {
+    pub tool_version: String,
+    /// Unix timestamp (seconds) of when the run finished
+    pub created_at: u64,
+    pub command: String,
+    pub input: PathBuf,
+    /// Generated SRT, unless it was a temporary file
+    pub srt: Option<PathBuf>,
+    /// Burned video, for `process`
+    pub output: Option<PathBuf>,
+    /// Embedded subtitle track used instead of transcription; the Whisper
+    /// settings below don't apply when set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedded_track: Option<usize>,
+    /// Command run on the SRT before burning
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_hook: Option<String>,
+    pub model: String,
+    pub model_source: Option<String>,
+    pub language: String,
+    /// What Whisper detected the language as, for `auto`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detected_language: Option<String>,
+    pub translate: bool,
+    pub trim_silence: bool,
+    pub continuous: bool,
+    /// Whisper segments were kept whole instead of split at sentence ends
+    pub no_split: bool,
+    pub temperature: f32,
+    pub no_speech_threshold: f32,
+    pub logprob_threshold: f32,
+    pub min_gap_ms: u64,
+    pub min_duration_ms: Option<u64>,
+    pub max_duration_ms: Option<u64>,
+    pub cue_count: usize,
+    /// Wall-clock time the whole run took
+    pub elapsed_ms: u64,
+}
+
+impl RunMetadata {
+    /// Sidecar path for an output file: `<output>.json`
+    pub fn sidecar_path(output_path: &Path) -> PathBuf {
+        let mut name = output_path.as_os_str().to_os_string();
+        name.push(".json");
+        PathBuf::from(name)
+    }
+
+    /// Stamp the creation time and write the sidecar as pretty-printed JSON
+    pub fn save(mut self, path: &Path) -> Result<()> {
+        self.created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let json = serde_json::to_string_pretty(&self).context("Failed to serialize metadata")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write metadata file {}", path.display()))
+    }
+}
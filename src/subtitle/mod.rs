@@ -1,3 +1,17 @@
+pub mod ass;
 pub mod burner;
+pub mod diff;
+pub mod embedded;
+pub mod fix;
+pub mod format;
 pub mod generator;
+pub mod karaoke;
+pub mod lint;
+pub mod metadata;
+pub mod resegment;
+pub mod split;
 pub mod srt;
+pub mod style;
+pub mod timing;
+pub mod vtt;
+pub mod wrap;
@@ -0,0 +1,205 @@
+use crate::cli::args::SubtitlePosition;
+use crate::subtitle::srt::Subtitle;
+use crate::subtitle::timing;
+
+/// Pauses at least this long are taken as natural cue boundaries
+pub const MIN_PAUSE_MS: u64 = 300;
+
+/// Longest cue re-segmenting leaves unsplit, unless the config sets one
+pub const DEFAULT_MAX_CUE_MS: u64 = 7000;
+
+/// Speech shorter than this is merged into a neighbour rather than flashed alone
+const MIN_CUE_MS: u64 = 1000;
+
+/// A word of the re-segmented text and when it is thought to be spoken
+#[derive(Debug, Clone)]
+struct TimedWord<'a> {
+    text: &'a str,
+    start: u64,
+    end: u64,
+    position: Option<SubtitlePosition>,
+}
+
+/// The words of `cues` with their times: Whisper's word timings where a cue
+/// still has them, otherwise the cue's span shared out by word length
+fn timed_words(cues: &[Subtitle]) -> Vec<TimedWord<'_>> {
+    let mut words = Vec::new();
+    for sub in cues {
+        let texts: Vec<&str> = sub.text.split_whitespace().collect();
+        let timings = sub
+            .confidence
+            .as_ref()
+            .filter(|timings| timings.len() == texts.len());
+        if let Some(timings) = timings {
+            words.extend(texts.iter().zip(timings).map(|(text, word)| TimedWord {
+                text,
+                start: word.start_time,
+                end: word.end_time,
+                position: sub.position,
+            }));
+            continue;
+        }
+
+        let duration = sub.end_time.saturating_sub(sub.start_time);
+        let total: u64 = texts.iter().map(|t| t.chars().count() as u64).sum();
+        let mut before = 0;
+        for text in texts {
+            let length = text.chars().count() as u64;
+            words.push(TimedWord {
+                text,
+                start: sub.start_time + duration * before / total.max(1),
+                end: sub.start_time + duration * (before + length) / total.max(1),
+                position: sub.position,
+            });
+            before += length;
+        }
+    }
+    words
+}
+
+/// Re-cut `cues` at the pauses in `silences` (start and end in ms, in order):
+/// their words are pooled and regrouped into one cue per stretch of speech,
+/// timed to where the speech starts and stops. Stretches shorter than a
+/// second are merged into the neighbour across the shorter pause, and cues
+/// longer than `max_duration_ms` are split at sentence ends. Locked cues are
+/// kept as they are, and the cues between them re-cut separately.
+pub fn resegment(
+    cues: &[Subtitle],
+    silences: &[(u64, u64)],
+    max_duration_ms: u64,
+) -> Vec<Subtitle> {
+    let mut result = Vec::new();
+    let mut run_start = 0;
+    for (i, sub) in cues.iter().enumerate() {
+        if sub.locked {
+            result.extend(resegment_run(
+                &cues[run_start..i],
+                silences,
+                max_duration_ms,
+            ));
+            result.push(sub.clone());
+            run_start = i + 1;
+        }
+    }
+    result.extend(resegment_run(&cues[run_start..], silences, max_duration_ms));
+    result
+}
+
+fn resegment_run(run: &[Subtitle], silences: &[(u64, u64)], max_duration_ms: u64) -> Vec<Subtitle> {
+    let words = timed_words(run);
+    let (Some(mut start), Some(mut end)) = (
+        run.iter().map(|sub| sub.start_time).min(),
+        run.iter().map(|sub| sub.end_time).max(),
+    ) else {
+        return Vec::new();
+    };
+    if words.is_empty() {
+        return run.to_vec();
+    }
+
+    // Silence the cues already start or end in is trimmed off
+    for &(silence_start, silence_end) in silences {
+        if silence_start <= start && start < silence_end && silence_end < end {
+            start = silence_end;
+        }
+        if silence_start < end && end <= silence_end && start < silence_start {
+            end = silence_start;
+        }
+    }
+    let pauses: Vec<(u64, u64)> = silences
+        .iter()
+        .copied()
+        .filter(|&(silence_start, silence_end)| start < silence_start && silence_end < end)
+        .collect();
+
+    // Stretches of speech between the pauses, each taking the words whose
+    // middle falls nearer to it than to the next
+    let mut bounds = vec![start];
+    for &(pause_start, pause_end) in &pauses {
+        bounds.push(pause_start);
+        bounds.push(pause_end);
+    }
+    bounds.push(end);
+    let mut stretches: Vec<(u64, u64, Vec<TimedWord>)> = bounds
+        .chunks(2)
+        .map(|pair| (pair[0], pair[1], Vec::new()))
+        .collect();
+    for word in words {
+        let middle = (word.start + word.end) / 2;
+        let stretch = pauses
+            .iter()
+            .filter(|(pause_start, pause_end)| (pause_start + pause_end) / 2 <= middle)
+            .count();
+        stretches[stretch].2.push(word);
+    }
+    let mut groups: Vec<(u64, u64, Vec<TimedWord>)> = stretches
+        .into_iter()
+        .filter(|(_, _, words)| !words.is_empty())
+        .collect();
+
+    // Merge stretches too short to read into the neighbour across the shorter pause
+    while let Some(i) = groups
+        .iter()
+        .position(|(start, end, _)| end - start < MIN_CUE_MS)
+        .filter(|_| groups.len() > 1)
+    {
+        let pause_before = i.checked_sub(1).map(|p| groups[i].0 - groups[p].1);
+        let pause_after = groups.get(i + 1).map(|next| next.0 - groups[i].1);
+        let into = match (pause_before, pause_after) {
+            (Some(before), Some(after)) if after < before => i + 1,
+            (Some(_), _) => i - 1,
+            _ => i + 1,
+        };
+        let (first, second) = (i.min(into), i.max(into));
+        let (_, second_end, second_words) = groups.remove(second);
+        groups[first].1 = second_end;
+        groups[first].2.extend(second_words);
+    }
+
+    let mut cues: Vec<Subtitle> = groups
+        .into_iter()
+        .map(|(start, end, words)| {
+            let text = words.iter().map(|w| w.text).collect::<Vec<_>>().join(" ");
+            Subtitle {
+                position: words[0].position,
+                ..Subtitle::new(0, start, end, text)
+            }
+        })
+        .collect();
+    timing::split_long_cues(&mut cues, max_duration_ms);
+    cues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cuts_at_pauses_and_keeps_locked_cues() {
+        let mut locked = Subtitle::new(3, 6000, 7000, "Keep me".to_string());
+        locked.locked = true;
+        let cues = vec![
+            Subtitle::new(1, 1000, 3000, "Hello there, my".to_string()),
+            Subtitle::new(2, 3000, 5000, "friend. How are you?".to_string()),
+            locked.clone(),
+        ];
+        // A pause after "friend.", one before the first cue, and one too
+        // brief to leave a readable cue on its own
+        let silences = [(0, 1200), (3300, 3800), (4700, 4800)];
+
+        let result = resegment(&cues, &silences, DEFAULT_MAX_CUE_MS);
+        let summary: Vec<(u64, u64, &str)> = result
+            .iter()
+            .map(|sub| (sub.start_time, sub.end_time, sub.text.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1200, 3300, "Hello there, my friend."),
+                (3800, 5000, "How are you?"),
+                (6000, 7000, "Keep me"),
+            ]
+        );
+        assert!(result[2].locked);
+    }
+}
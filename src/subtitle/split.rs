@@ -0,0 +1,206 @@
+use crate::subtitle::srt::{self, SrtWriteOptions, Subtitle};
+use crate::subtitle::timing;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Where to cut a video into parts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SplitMode {
+    /// Parts of at most this many minutes
+    Minutes(u64),
+    /// One part per chapter
+    Chapters,
+}
+
+impl SplitMode {
+    /// The mode chosen by `--split-minutes` / `--split-chapters`, if any
+    pub fn from_args(minutes: Option<u64>, chapters: bool) -> Result<Option<Self>> {
+        match (minutes, chapters) {
+            (Some(0), _) => anyhow::bail!("--split-minutes must be at least 1"),
+            (Some(minutes), _) => Ok(Some(SplitMode::Minutes(minutes))),
+            (None, true) => Ok(Some(SplitMode::Chapters)),
+            (None, false) => Ok(None),
+        }
+    }
+}
+
+/// One part of a split video, with its own subtitles starting at zero
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub video: PathBuf,
+    pub srt: PathBuf,
+    /// Where the part starts and ends in the original video
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub cues: usize,
+}
+
+/// Path of part `number` (from 1) of `output`: `video.mp4` gives `video_part01.mp4`
+pub fn part_path(output: &Path, number: usize) -> PathBuf {
+    output.with_file_name(part_name(output, &format!("{:02}", number)))
+}
+
+/// File name of a part of `output`, numbered `number`
+fn part_name(output: &Path, number: &str) -> String {
+    let stem = output.file_stem().unwrap_or_default().to_string_lossy();
+    match output.extension() {
+        Some(ext) => format!("{}_part{}.{}", stem, number, ext.to_string_lossy()),
+        None => format!("{}_part{}", stem, number),
+    }
+}
+
+#[derive(Deserialize)]
+struct ProbeChapters {
+    #[serde(default)]
+    chapters: Vec<ProbeChapter>,
+}
+
+#[derive(Deserialize)]
+struct ProbeChapter {
+    start_time: String,
+}
+
+/// Start times of the chapters in `video_path`, in milliseconds
+pub fn chapter_starts(video_path: &Path) -> Result<Vec<u64>> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "error", "-show_chapters", "-of", "json"])
+        .arg(video_path)
+        .output()
+        .context("Failed to run ffprobe")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("ffprobe failed: {}", stderr.trim());
+    }
+
+    let probe: ProbeChapters =
+        serde_json::from_slice(&output.stdout).context("Failed to parse ffprobe output")?;
+    Ok(probe
+        .chapters
+        .iter()
+        .filter_map(|chapter| chapter.start_time.parse::<f64>().ok())
+        .map(|secs| (secs * 1000.0).round() as u64)
+        .collect())
+}
+
+/// Cut `video_path` into parts next to it (`<name>_part01.<ext>`, ...) and
+/// write each part's slice of `subtitles` beside it as `<part>.srt`, shifted
+/// to start at zero. Streams are copied, so cuts land on the keyframe at or
+/// after each requested time; the subtitles follow the actual cut points.
+pub fn split(
+    video_path: &Path,
+    subtitles: &[Subtitle],
+    mode: SplitMode,
+    options: &SrtWriteOptions,
+) -> Result<Vec<Part>> {
+    let segment_args = match mode {
+        SplitMode::Minutes(minutes) => {
+            vec!["-segment_time".to_string(), (minutes * 60).to_string()]
+        }
+        SplitMode::Chapters => {
+            let starts: Vec<String> = chapter_starts(video_path)?
+                .into_iter()
+                .filter(|&ms| ms > 0)
+                .map(|ms| format!("{:.3}", ms as f64 / 1000.0))
+                .collect();
+            if starts.is_empty() {
+                anyhow::bail!("{} has no chapter marks to split at", video_path.display());
+            }
+            vec!["-segment_times".to_string(), starts.join(",")]
+        }
+    };
+
+    // The segment muxer numbers parts through a printf pattern, so a literal
+    // % in the file name has to be doubled
+    let escaped = PathBuf::from(video_path.to_string_lossy().replace('%', "%%"));
+    let pattern = escaped.with_file_name(part_name(&escaped, "%02d"));
+
+    let output = Command::new("ffmpeg")
+        .args(["-loglevel", "error", "-i"])
+        .arg(video_path)
+        .args(["-map", "0", "-c", "copy", "-f", "segment"])
+        .args(&segment_args)
+        .args(["-reset_timestamps", "1", "-segment_start_number", "1"])
+        // Lists each part with where it really starts and ends
+        .args(["-segment_list", "pipe:1", "-segment_list_type", "csv"])
+        .arg("-y")
+        .arg(&pattern)
+        .output()
+        .context("Failed to run FFmpeg")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "Failed to split {}: {}",
+            video_path.display(),
+            stderr.trim()
+        );
+    }
+
+    let entries = parse_segment_list(&String::from_utf8_lossy(&output.stdout));
+    if entries.is_empty() {
+        anyhow::bail!(
+            "FFmpeg did not report any parts of {}",
+            video_path.display()
+        );
+    }
+
+    let mut parts = Vec::with_capacity(entries.len());
+    for (number, (start_ms, end_ms)) in entries.into_iter().enumerate() {
+        let video = part_path(video_path, number + 1);
+        let srt = video.with_extension("srt");
+        let slice = timing::clip_window(subtitles, start_ms, end_ms.saturating_sub(start_ms));
+        srt::save_srt(&srt, &slice, options)?;
+        parts.push(Part {
+            video,
+            srt,
+            start_ms,
+            end_ms,
+            cues: slice.len(),
+        });
+    }
+    Ok(parts)
+}
+
+/// Start and end times of each part in the segment muxer's CSV list
+/// (`name,start,end` per line; the name may be quoted and contain commas)
+fn parse_segment_list(csv: &str) -> Vec<(u64, u64)> {
+    csv.lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().rsplitn(3, ',');
+            let end: f64 = fields.next()?.parse().ok()?;
+            let start: f64 = fields.next()?.parse().ok()?;
+            fields.next()?;
+            Some((
+                (start * 1000.0).round() as u64,
+                (end * 1000.0).round() as u64,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_paths_and_segment_list() {
+        assert_eq!(
+            part_path(Path::new("out/talk.mp4"), 3),
+            PathBuf::from("out/talk_part03.mp4")
+        );
+        assert_eq!(
+            parse_segment_list(
+                "talk_part01.mp4,0.000000,600.400000\n\"a,b_part02.mp4\",600.400000,731.25\n"
+            ),
+            vec![(0, 600_400), (600_400, 731_250)]
+        );
+        assert_eq!(
+            SplitMode::from_args(None, true).unwrap(),
+            Some(SplitMode::Chapters)
+        );
+        assert!(SplitMode::from_args(Some(0), false).is_err());
+    }
+}
@@ -2,13 +2,60 @@ use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
 
+use crate::cli::args::SubtitlePosition;
+
+/// How cue indices are written by `save_srt`. Cues are always numbered from 1
+/// in memory; these options only change what ends up on disk.
+#[derive(Debug, Clone, Copy)]
+pub struct SrtWriteOptions {
+    /// Number written for the first cue
+    pub index_base: usize,
+    /// Zero-pad indices to this many digits (0 disables padding)
+    pub index_width: usize,
+    /// Write the index line at all; without it the output is VTT-like
+    pub write_index: bool,
+}
+
+impl Default for SrtWriteOptions {
+    fn default() -> Self {
+        Self {
+            index_base: 1,
+            index_width: 0,
+            write_index: true,
+        }
+    }
+}
+
+/// Words Whisper was less sure of than this are flagged in the editor
+pub const LOW_CONFIDENCE: f32 = 0.5;
+
+/// How sure Whisper was of one word of a cue, and when it was spoken
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordConfidence {
+    pub text: String,
+    /// Lowest probability among the word's tokens (0.0 - 1.0)
+    pub probability: f32,
+    /// Start of the word's first token and end of its last, in milliseconds
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
 /// A single subtitle entry
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Subtitle {
     pub index: usize,
     pub start_time: u64, // milliseconds
     pub end_time: u64,   // milliseconds
     pub text: String,
+    /// Placement override for this cue, stored in the file as a leading
+    /// `{\anN}` tag (None: wherever the track is burned)
+    pub position: Option<SubtitlePosition>,
+    /// Per-word confidence from transcription, one entry per whitespace-separated
+    /// word of `text`. Not saved to the file, and dropped when the text is edited.
+    pub confidence: Option<Vec<WordConfidence>>,
+    /// Held in place when a ripple edit shifts the cues around it. Editor-only,
+    /// not saved to the file.
+    pub locked: bool,
 }
 
 impl Subtitle {
@@ -18,9 +65,38 @@ impl Subtitle {
             start_time,
             end_time,
             text,
+            position: None,
+            confidence: None,
+            locked: false,
+        }
+    }
+
+    /// Split a leading `{\an2}`, `{\an5}` or `{\an8}` tag off the text
+    fn take_position_tag(text: &str) -> (Option<SubtitlePosition>, &str) {
+        let position = match text.get(..5) {
+            Some("{\\an2") => SubtitlePosition::Bottom,
+            Some("{\\an5") => SubtitlePosition::Middle,
+            Some("{\\an8") => SubtitlePosition::Top,
+            _ => return (None, text),
+        };
+        match text[5..].strip_prefix('}') {
+            Some(rest) => (Some(position), rest),
+            None => (None, text),
         }
     }
 
+    /// Cue text as written to SRT, with the position override as an ASS tag
+    /// (honoured by libass when burning, and by most players)
+    fn tagged_text(&self) -> String {
+        let tag = match self.position {
+            Some(SubtitlePosition::Bottom) => "{\\an2}",
+            Some(SubtitlePosition::Middle) => "{\\an5}",
+            Some(SubtitlePosition::Top) => "{\\an8}",
+            None => "",
+        };
+        format!("{}{}", tag, self.text)
+    }
+
     /// Format time in SRT format: HH:MM:SS,mmm
     pub fn format_time(ms: u64) -> String {
         let hours = ms / 3_600_000;
@@ -46,13 +122,23 @@ impl Subtitle {
     }
 
     /// Convert to SRT format string
-    pub fn to_srt(&self) -> String {
-        format!(
-            "{}\n{} --> {}\n{}\n",
-            self.index,
+    pub fn to_srt(&self, options: &SrtWriteOptions) -> String {
+        let timing = format!(
+            "{} --> {}",
             Self::format_time(self.start_time),
-            Self::format_time(self.end_time),
-            self.text
+            Self::format_time(self.end_time)
+        );
+        if !options.write_index {
+            return format!("{}\n{}\n", timing, self.tagged_text());
+        }
+
+        let index = self.index.saturating_sub(1) + options.index_base;
+        format!(
+            "{:0width$}\n{}\n{}\n",
+            index,
+            timing,
+            self.tagged_text(),
+            width = options.index_width
         )
     }
 }
@@ -63,7 +149,8 @@ pub fn parse_srt(path: &Path) -> Result<Vec<Subtitle>> {
     parse_srt_string(&content)
 }
 
-/// Parse SRT content from a string
+/// Parse SRT content from a string. Index lines are optional and cues are
+/// renumbered from 1, whatever base the file used.
 pub fn parse_srt_string(content: &str) -> Result<Vec<Subtitle>> {
     let mut subtitles = Vec::new();
     let mut lines = content.lines().peekable();
@@ -74,19 +161,23 @@ pub fn parse_srt_string(content: &str) -> Result<Vec<Subtitle>> {
             lines.next();
         }
 
-        // Parse index
-        let index_line = match lines.next() {
+        // Parse index, unless the entry starts directly with its time range
+        let first_line = match lines.next() {
             Some(l) if !l.trim().is_empty() => l,
             _ => break,
         };
 
-        let index: usize = index_line
-            .trim()
-            .parse()
-            .context("Invalid subtitle index")?;
+        let time_line = if first_line.contains(" --> ") {
+            first_line
+        } else {
+            first_line
+                .trim()
+                .parse::<usize>()
+                .context("Invalid subtitle index")?;
+            lines.next().context("Expected time range")?
+        };
 
         // Parse time range
-        let time_line = lines.next().context("Expected time range")?;
         let parts: Vec<&str> = time_line.split(" --> ").collect();
         if parts.len() != 2 {
             anyhow::bail!("Invalid time range: {}", time_line);
@@ -104,18 +195,22 @@ pub fn parse_srt_string(content: &str) -> Result<Vec<Subtitle>> {
             text_lines.push(lines.next().unwrap());
         }
         let text = text_lines.join("\n");
+        let (position, text) = Subtitle::take_position_tag(&text);
 
-        subtitles.push(Subtitle::new(index, start_time, end_time, text));
+        subtitles.push(Subtitle {
+            position,
+            ..Subtitle::new(subtitles.len() + 1, start_time, end_time, text.to_string())
+        });
     }
 
     Ok(subtitles)
 }
 
 /// Save subtitles to an SRT file
-pub fn save_srt(path: &Path, subtitles: &[Subtitle]) -> Result<()> {
+pub fn save_srt(path: &Path, subtitles: &[Subtitle], options: &SrtWriteOptions) -> Result<()> {
     let content: String = subtitles
         .iter()
-        .map(|s| s.to_srt())
+        .map(|s| s.to_srt(options))
         .collect::<Vec<_>>()
         .join("\n");
 
@@ -141,4 +236,74 @@ mod tests {
         assert_eq!(Subtitle::parse_time("00:00:01,500").unwrap(), 1500);
         assert_eq!(Subtitle::parse_time("00:01:05,000").unwrap(), 65000);
     }
+
+    fn render(subtitles: &[Subtitle], options: &SrtWriteOptions) -> String {
+        subtitles
+            .iter()
+            .map(|s| s.to_srt(options))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_zero_based_round_trip() {
+        let subs = vec![
+            Subtitle::new(1, 0, 1000, "First".to_string()),
+            Subtitle::new(2, 1500, 2500, "Second".to_string()),
+        ];
+        let options = SrtWriteOptions {
+            index_base: 0,
+            index_width: 3,
+            write_index: true,
+        };
+
+        let content = render(&subs, &options);
+        assert!(content.starts_with("000\n00:00:00,000 --> "));
+        assert!(content.contains("\n001\n00:00:01,500 --> "));
+
+        let parsed = parse_srt_string(&content).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].index, 1);
+        assert_eq!(parsed[1].start_time, 1500);
+        assert_eq!(parsed[1].text, "Second");
+    }
+
+    #[test]
+    fn test_round_trip_without_index() {
+        let subs = vec![
+            Subtitle::new(1, 0, 1000, "First".to_string()),
+            Subtitle::new(2, 1500, 2500, "Second\nline".to_string()),
+        ];
+        let options = SrtWriteOptions {
+            write_index: false,
+            ..SrtWriteOptions::default()
+        };
+
+        let content = render(&subs, &options);
+        assert!(content.starts_with("00:00:00,000 --> 00:00:01,000\nFirst\n"));
+
+        let parsed = parse_srt_string(&content).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].index, 2);
+        assert_eq!(parsed[1].text, "Second\nline");
+    }
+
+    #[test]
+    fn test_position_tag_round_trip() {
+        let parsed =
+            parse_srt_string("1\n00:00:00,000 --> 00:00:01,000\n{\\an8}Sign text\n").unwrap();
+        assert_eq!(parsed[0].position, Some(SubtitlePosition::Top));
+        assert_eq!(parsed[0].text, "Sign text");
+
+        let content = parsed[0].to_srt(&SrtWriteOptions::default());
+        assert_eq!(
+            content,
+            "1\n00:00:00,000 --> 00:00:01,000\n{\\an8}Sign text\n"
+        );
+
+        // Other alignments are left in the text untouched
+        let parsed = parse_srt_string("1\n00:00:00,000 --> 00:00:01,000\n{\\an7}Corner\n").unwrap();
+        assert_eq!(parsed[0].position, None);
+        assert_eq!(parsed[0].text, "{\\an7}Corner");
+    }
 }
@@ -0,0 +1,200 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+
+/// A named subtitle look, turned into an ffmpeg `force_style` string.
+/// Unset fields keep libass' (or the burner's) defaults.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct StylePreset {
+    pub font: Option<String>,
+    pub font_size: Option<u32>,
+    /// Text color as RRGGBB hex
+    pub color: Option<String>,
+    /// Outline color as RRGGBB hex
+    pub outline_color: Option<String>,
+    /// Shadow / box color as RRGGBB hex
+    pub back_color: Option<String>,
+    pub bold: Option<bool>,
+    /// Outline thickness in pixels
+    pub outline: Option<f32>,
+    /// Shadow depth in pixels
+    pub shadow: Option<f32>,
+    /// Draw an opaque box behind the text instead of an outline
+    pub boxed: Option<bool>,
+    /// ASS numpad alignment (2 = bottom center, 8 = top center)
+    pub alignment: Option<u8>,
+}
+
+/// Presets available without a presets file
+const BUILTIN_PRESETS: &str = r#"
+[netflix]
+font = "Arial"
+color = "FFFFFF"
+outline_color = "000000"
+outline = 2
+shadow = 1
+
+[youtube]
+font = "Roboto"
+color = "FFFFFF"
+back_color = "000000"
+boxed = true
+outline = 0
+shadow = 0
+
+[bold-yellow]
+font = "Arial"
+bold = true
+color = "FFFF00"
+outline_color = "000000"
+outline = 3
+shadow = 0
+"#;
+
+impl StylePreset {
+    /// Render as comma-separated ASS style overrides for `force_style`
+    pub fn to_force_style(&self) -> Result<String> {
+        let mut parts = Vec::new();
+        if let Some(font) = &self.font {
+            parts.push(format!("FontName={}", font));
+        }
+        if let Some(size) = self.font_size {
+            parts.push(format!("FontSize={}", size));
+        }
+        if let Some(color) = &self.color {
+            parts.push(format!(
+                "PrimaryColour={}",
+                ass_color(parse_hex_color(color)?)
+            ));
+        }
+        if let Some(color) = &self.outline_color {
+            parts.push(format!(
+                "OutlineColour={}",
+                ass_color(parse_hex_color(color)?)
+            ));
+        }
+        if let Some(color) = &self.back_color {
+            parts.push(format!("BackColour={}", ass_color(parse_hex_color(color)?)));
+        }
+        if let Some(bold) = self.bold {
+            parts.push(format!("Bold={}", if bold { -1 } else { 0 }));
+        }
+        if let Some(outline) = self.outline {
+            parts.push(format!("Outline={}", outline));
+        }
+        if let Some(shadow) = self.shadow {
+            parts.push(format!("Shadow={}", shadow));
+        }
+        if let Some(boxed) = self.boxed {
+            parts.push(format!("BorderStyle={}", if boxed { 3 } else { 1 }));
+        }
+        if let Some(alignment) = self.alignment {
+            parts.push(format!("Alignment={}", alignment));
+        }
+        Ok(parts.join(","))
+    }
+}
+
+/// User presets file, next to the config file
+pub fn presets_path() -> PathBuf {
+    Config::default_path().with_file_name("styles.toml")
+}
+
+/// Look up a preset by name. Presets in the user's file take precedence
+/// over the built-in ones of the same name.
+pub fn resolve_preset(name: &str) -> Result<StylePreset> {
+    let mut presets = parse_presets(BUILTIN_PRESETS).expect("built-in presets are valid");
+    let path = presets_path();
+    if path.exists() {
+        presets.extend(load_presets(&path)?);
+    }
+
+    let available = presets.keys().cloned().collect::<Vec<_>>().join(", ");
+    presets.remove(name).with_context(|| {
+        format!(
+            "Unknown style preset '{}' (available: {}; add your own in {})",
+            name,
+            available,
+            path.display()
+        )
+    })
+}
+
+/// Combine a named preset with a raw `--style` string; the raw overrides
+/// come last so they win over the preset
+pub fn build_style(preset: Option<&str>, custom: Option<&str>) -> Result<String> {
+    let mut parts = Vec::new();
+    if let Some(name) = preset {
+        parts.push(resolve_preset(name)?.to_force_style()?);
+    }
+    if let Some(custom) = custom {
+        parts.push(custom.trim().to_string());
+    }
+    parts.retain(|p| !p.is_empty());
+    Ok(parts.join(","))
+}
+
+fn load_presets(path: &Path) -> Result<BTreeMap<String, StylePreset>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read style presets: {}", path.display()))?;
+    parse_presets(&content)
+        .with_context(|| format!("Invalid style presets file: {}", path.display()))
+}
+
+fn parse_presets(content: &str) -> Result<BTreeMap<String, StylePreset>> {
+    Ok(toml::from_str(content)?)
+}
+
+/// Parse an `RRGGBB` hex color, with or without a leading `#`
+pub fn parse_hex_color(input: &str) -> Result<(u8, u8, u8)> {
+    let hex = input.trim().strip_prefix('#').unwrap_or(input.trim());
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!(
+            "Invalid color '{}': expected 6 hex digits like FFFFFF or #FFFF00",
+            input
+        );
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+    Ok((channel(0), channel(2), channel(4)))
+}
+
+/// The `&HAABBGGRR` form ASS styles use for a color (fully opaque)
+pub fn ass_color((r, g, b): (u8, u8, u8)) -> String {
+    format!("&H00{:02X}{:02X}{:02X}", b, g, r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_presets_render() {
+        let presets = parse_presets(BUILTIN_PRESETS).unwrap();
+        assert_eq!(
+            presets["bold-yellow"].to_force_style().unwrap(),
+            "FontName=Arial,PrimaryColour=&H0000FFFF,OutlineColour=&H00000000,Bold=-1,Outline=3,Shadow=0"
+        );
+        for preset in presets.values() {
+            preset.to_force_style().unwrap();
+        }
+    }
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_hex_color("#FFFFFF").unwrap(), (255, 255, 255));
+        assert_eq!(parse_hex_color("ffffff").unwrap(), (255, 255, 255));
+        assert_eq!(ass_color(parse_hex_color("#112233").unwrap()), "&H00332211");
+    }
+
+    #[test]
+    fn rejects_bad_colors() {
+        assert!(parse_hex_color("xyz").is_err());
+        assert!(parse_hex_color("FFFFF").is_err());
+        assert!(parse_hex_color("GG0000").is_err());
+        assert!(parse_hex_color("##FFFFFF").is_err());
+    }
+}
@@ -0,0 +1,604 @@
+use crate::subtitle::fix;
+use crate::subtitle::srt::Subtitle;
+use anyhow::{Context, Result};
+
+/// Default minimum gap between adjacent cues, about one frame at 25fps
+pub const DEFAULT_MIN_GAP_MS: u64 = 40;
+
+/// Shorten cues that touch or overlap the next one so at least `min_gap_ms`
+/// separates them. A cue is never shortened to nothing; such overlaps are left
+/// for the user to fix. Returns the number of cues adjusted.
+pub fn enforce_min_gap(subtitles: &mut [Subtitle], min_gap_ms: u64) -> usize {
+    let mut adjusted = 0;
+    for i in 1..subtitles.len() {
+        let next_start = subtitles[i].start_time;
+        let prev = &mut subtitles[i - 1];
+        if prev.end_time + min_gap_ms > next_start {
+            let end = next_start.saturating_sub(min_gap_ms);
+            if end > prev.start_time {
+                prev.end_time = end;
+                adjusted += 1;
+            }
+        }
+    }
+    adjusted
+}
+
+/// Extend cues across short silences so the text doesn't flicker off and on,
+/// stopping `min_gap_ms` before the next cue. Silences longer than
+/// `max_fill_ms` are left alone. Returns the number of cues extended.
+pub fn fill_gaps(subtitles: &mut [Subtitle], max_fill_ms: u64, min_gap_ms: u64) -> usize {
+    let mut extended = 0;
+    for i in 1..subtitles.len() {
+        let next_start = subtitles[i].start_time;
+        let prev = &mut subtitles[i - 1];
+        let silence = next_start.saturating_sub(prev.end_time);
+        if silence > min_gap_ms && silence <= max_fill_ms {
+            prev.end_time = next_start - min_gap_ms;
+            extended += 1;
+        }
+    }
+    extended
+}
+
+/// Continuous captions: every cue stays up until the next one starts, and the
+/// last until `media_end_ms`, so the text never blinks off between cues.
+/// Overlapping cues are cut back to the same boundary. Returns the number of
+/// cues whose end changed.
+pub fn make_continuous(subtitles: &mut [Subtitle], media_end_ms: Option<u64>) -> usize {
+    let mut changed = 0;
+    for i in 0..subtitles.len() {
+        let end = match subtitles.get(i + 1) {
+            Some(next) => next.start_time,
+            None => match media_end_ms {
+                Some(media_end) => media_end.max(subtitles[i].end_time),
+                None => continue,
+            },
+        };
+        let sub = &mut subtitles[i];
+        // Never collapse a cue; out-of-order starts are left for the user
+        if end > sub.start_time && end != sub.end_time {
+            sub.end_time = end;
+            changed += 1;
+        }
+    }
+    changed
+}
+
+/// Extend cues shorter than `min_duration_ms`, borrowing from the silence
+/// after them but stopping `min_gap_ms` before the next cue. Returns the
+/// number of cues extended.
+pub fn pad_short_cues(subtitles: &mut [Subtitle], min_duration_ms: u64, min_gap_ms: u64) -> usize {
+    let mut padded = 0;
+    for i in 0..subtitles.len() {
+        let limit = subtitles
+            .get(i + 1)
+            .map(|next| next.start_time.saturating_sub(min_gap_ms))
+            .unwrap_or(u64::MAX);
+        let sub = &mut subtitles[i];
+        if sub.end_time.saturating_sub(sub.start_time) < min_duration_ms {
+            let end = (sub.start_time + min_duration_ms).min(limit);
+            if end > sub.end_time {
+                sub.end_time = end;
+                padded += 1;
+            }
+        }
+    }
+    padded
+}
+
+/// Split cues longer than `max_duration_ms` at sentence boundaries. The
+/// cue's time is shared out by text length, or cut where the next piece's
+/// first word starts when the cue has word timings, and the pieces stay back
+/// to back, so the cue still covers exactly the same span. Each piece keeps
+/// the cue's position, lock and the timings of its own words. A single long
+/// sentence is left alone. Returns the number of cues split.
+pub fn split_long_cues(subtitles: &mut Vec<Subtitle>, max_duration_ms: u64) -> usize {
+    let mut split = 0;
+    let mut result = Vec::with_capacity(subtitles.len());
+
+    for sub in subtitles.drain(..) {
+        let duration = sub.end_time.saturating_sub(sub.start_time);
+        let sentences = fix::split_sentences(&sub.text);
+        if duration <= max_duration_ms || sentences.len() < 2 {
+            result.push(sub);
+            continue;
+        }
+
+        let total_chars: usize = sentences.iter().map(|s| s.chars().count()).sum();
+        let share = |s: &str| duration * s.chars().count() as u64 / total_chars as u64;
+
+        // Group consecutive sentences while the group still fits
+        let mut groups: Vec<(Vec<&str>, u64)> = Vec::new();
+        for sentence in sentences {
+            let length = share(sentence);
+            match groups.last_mut() {
+                Some((group, group_length)) if *group_length + length <= max_duration_ms => {
+                    group.push(sentence);
+                    *group_length += length;
+                }
+                _ => groups.push((vec![sentence], length)),
+            }
+        }
+
+        // Whisper's word timings, while they still line up with the text
+        let mut words = sub
+            .confidence
+            .clone()
+            .filter(|words| words.len() == sub.text.split_whitespace().count())
+            .map(Vec::into_iter);
+
+        let count = groups.len();
+        let mut start = sub.start_time;
+        for (i, (group, length)) in groups.into_iter().enumerate() {
+            let text = group.join(" ");
+            let confidence = words.as_mut().map(|words| {
+                words
+                    .by_ref()
+                    .take(text.split_whitespace().count())
+                    .collect::<Vec<_>>()
+            });
+            let end = if i + 1 == count {
+                sub.end_time
+            } else {
+                match words.as_ref().and_then(|words| words.as_slice().first()) {
+                    Some(next) => next.start_time.clamp(start, sub.end_time),
+                    None => start + length,
+                }
+            };
+            result.push(Subtitle {
+                start_time: start,
+                end_time: end,
+                text,
+                confidence,
+                ..sub.clone()
+            });
+            start = end;
+        }
+        split += 1;
+    }
+
+    *subtitles = result;
+    fix::reindex(subtitles);
+    split
+}
+
+/// Apply whichever duration limits are set: split long cues, then pad short ones
+pub fn clamp_durations(
+    subtitles: &mut Vec<Subtitle>,
+    min_duration_ms: Option<u64>,
+    max_duration_ms: Option<u64>,
+    min_gap_ms: u64,
+) {
+    if let Some(max) = max_duration_ms {
+        split_long_cues(subtitles, max);
+    }
+    if let Some(min) = min_duration_ms {
+        pad_short_cues(subtitles, min, min_gap_ms);
+    }
+}
+
+/// Cues visible between `start_ms` and `start_ms + duration_ms`, shifted so the
+/// window starts at zero and trimmed to it. Used to burn a short excerpt.
+pub fn clip_window(subtitles: &[Subtitle], start_ms: u64, duration_ms: u64) -> Vec<Subtitle> {
+    let end_ms = start_ms + duration_ms;
+    subtitles
+        .iter()
+        .filter(|sub| sub.end_time > start_ms && sub.start_time < end_ms)
+        .enumerate()
+        .map(|(i, sub)| Subtitle {
+            index: i + 1,
+            start_time: sub.start_time.max(start_ms) - start_ms,
+            end_time: sub.end_time.min(end_ms) - start_ms,
+            ..sub.clone()
+        })
+        .collect()
+}
+
+/// Number of cues still on screen after `media_end_ms`
+pub fn count_past_end(subtitles: &[Subtitle], media_end_ms: u64) -> usize {
+    subtitles
+        .iter()
+        .filter(|sub| sub.end_time > media_end_ms)
+        .count()
+}
+
+/// Trim cues that run past `media_end_ms` and drop the ones that start at or
+/// after it. Returns the number of cues trimmed or dropped.
+pub fn clamp_to_media_end(subtitles: &mut Vec<Subtitle>, media_end_ms: u64) -> usize {
+    let before = subtitles.len();
+    subtitles.retain(|sub| sub.start_time < media_end_ms);
+    let mut changed = before - subtitles.len();
+    for sub in subtitles.iter_mut() {
+        if sub.end_time > media_end_ms {
+            sub.end_time = media_end_ms;
+            changed += 1;
+        }
+    }
+    fix::reindex(subtitles);
+    changed
+}
+
+/// Ripple a timing change: move the cues from `index` on by `delta_ms`, up
+/// to the first locked cue, which stays put as an anchor. The shift is cut
+/// short rather than pushing cues into the anchor or back over the cue before
+/// `index`. Returns the number of cues moved.
+pub fn ripple_from(
+    subtitles: &mut [Subtitle],
+    index: usize,
+    delta_ms: i64,
+    min_gap_ms: u64,
+) -> usize {
+    let end = subtitles
+        .iter()
+        .skip(index)
+        .position(|sub| sub.locked)
+        .map_or(subtitles.len(), |offset| index + offset);
+    if index >= end {
+        return 0;
+    }
+
+    let delta_ms = if delta_ms > 0 {
+        match subtitles.get(end) {
+            Some(anchor) => {
+                let room = anchor
+                    .start_time
+                    .saturating_sub(min_gap_ms)
+                    .saturating_sub(subtitles[end - 1].end_time);
+                delta_ms.min(room as i64)
+            }
+            None => delta_ms,
+        }
+    } else {
+        let floor = match index.checked_sub(1) {
+            Some(prev) => subtitles[prev].end_time + min_gap_ms,
+            None => 0,
+        };
+        let room = subtitles[index].start_time.saturating_sub(floor);
+        delta_ms.max(-(room as i64))
+    };
+    if delta_ms == 0 {
+        return 0;
+    }
+
+    shift_all(&mut subtitles[index..end], delta_ms);
+    end - index
+}
+
+/// Move cue `index` by `delta_ms`, keeping its duration. The move is cut
+/// short at zero and `min_gap_ms` away from the cues either side, without
+/// pulling back a cue that already sits closer. Returns how far it moved.
+pub fn move_cue(subtitles: &mut [Subtitle], index: usize, delta_ms: i64, min_gap_ms: u64) -> i64 {
+    let Some(sub) = subtitles.get(index) else {
+        return 0;
+    };
+    let delta_ms = if delta_ms > 0 {
+        match subtitles.get(index + 1) {
+            Some(next) => {
+                let room = next
+                    .start_time
+                    .saturating_sub(min_gap_ms)
+                    .saturating_sub(sub.end_time);
+                delta_ms.min(room as i64)
+            }
+            None => delta_ms,
+        }
+    } else {
+        let floor = match index.checked_sub(1) {
+            Some(prev) => subtitles[prev].end_time + min_gap_ms,
+            None => 0,
+        };
+        let room = sub.start_time.saturating_sub(floor);
+        delta_ms.max(-(room as i64))
+    };
+
+    shift_all(&mut subtitles[index..=index], delta_ms);
+    delta_ms
+}
+
+/// The cue to land on for `time_ms`: the one showing at that time (`true`),
+/// else the next one to start, else the last. `None` when there are no cues.
+pub fn cue_at(subtitles: &[Subtitle], time_ms: u64) -> Option<(usize, bool)> {
+    if let Some(index) = subtitles
+        .iter()
+        .position(|s| s.start_time <= time_ms && time_ms < s.end_time)
+    {
+        return Some((index, true));
+    }
+    let nearest = subtitles
+        .iter()
+        .position(|s| s.start_time > time_ms)
+        .or(subtitles.len().checked_sub(1))?;
+    Some((nearest, false))
+}
+
+/// Append `new` to `existing`, shifting the new cues by `offset_ms` (default:
+/// the end of the last existing cue), then sort by start time and renumber
+pub fn append_shifted(
+    mut existing: Vec<Subtitle>,
+    new: Vec<Subtitle>,
+    offset_ms: Option<u64>,
+) -> Vec<Subtitle> {
+    let offset_ms =
+        offset_ms.unwrap_or_else(|| existing.iter().map(|sub| sub.end_time).max().unwrap_or(0));
+    existing.extend(new.into_iter().map(|sub| Subtitle {
+        start_time: sub.start_time + offset_ms,
+        end_time: sub.end_time + offset_ms,
+        ..sub
+    }));
+    existing.sort_by_key(|sub| sub.start_time);
+    for (i, sub) in existing.iter_mut().enumerate() {
+        sub.index = i + 1;
+    }
+    existing
+}
+
+/// Move every cue by `offset_ms`, clamping times that would fall before
+/// zero. Returns the number of cues that were clamped.
+pub fn shift_all(subtitles: &mut [Subtitle], offset_ms: i64) -> usize {
+    let mut clamped = 0;
+    for sub in subtitles.iter_mut() {
+        if offset_ms < 0 && sub.start_time < offset_ms.unsigned_abs() {
+            clamped += 1;
+        }
+        sub.start_time = sub.start_time.saturating_add_signed(offset_ms);
+        sub.end_time = sub.end_time.saturating_add_signed(offset_ms);
+    }
+    clamped
+}
+
+/// Parse a signed time offset: `500ms`, `+1.5s`, `-2s`, a bare number of
+/// milliseconds, or an SRT timestamp such as `-00:00:02,000`
+pub fn parse_offset(input: &str) -> Result<i64> {
+    let trimmed = input.trim();
+    let (sign, rest) = match trimmed.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+
+    let ms = if rest.contains(':') {
+        Subtitle::parse_time(&rest.replace('.', ","))
+            .with_context(|| format!("Invalid offset: {}", input))? as i64
+    } else if let Some(ms) = rest.strip_suffix("ms") {
+        ms.trim()
+            .parse::<i64>()
+            .with_context(|| format!("Invalid offset: {}", input))?
+    } else if let Some(secs) = rest.strip_suffix('s') {
+        let secs: f64 = secs
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid offset: {}", input))?;
+        if !secs.is_finite() || secs < 0.0 {
+            anyhow::bail!("Invalid offset: {}", input);
+        }
+        (secs * 1000.0).round() as i64
+    } else {
+        rest.parse::<i64>()
+            .with_context(|| format!("Invalid offset: {}", input))?
+    };
+
+    if ms < 0 {
+        anyhow::bail!("Invalid offset: {}", input);
+    }
+    Ok(sign * ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subtitle::srt::WordConfidence;
+
+    fn cue(start: u64, end: u64) -> Subtitle {
+        Subtitle::new(0, start, end, "text".to_string())
+    }
+
+    #[test]
+    fn test_cue_at_lands_on_nearest_following() {
+        let subs = vec![cue(1000, 2000), cue(3000, 4000)];
+        assert_eq!(cue_at(&subs, 1500), Some((0, true)));
+        assert_eq!(cue_at(&subs, 2000), Some((1, false)));
+        assert_eq!(cue_at(&subs, 500), Some((0, false)));
+        assert_eq!(cue_at(&subs, 9000), Some((1, false)));
+        assert_eq!(cue_at(&[], 0), None);
+    }
+
+    #[test]
+    fn test_gap_operations_leave_min_gap() {
+        let mut subs = vec![
+            cue(0, 1000),
+            cue(1000, 2000),
+            cue(2300, 3000),
+            cue(9000, 9500),
+        ];
+        assert_eq!(enforce_min_gap(&mut subs, 40), 1);
+        assert_eq!(subs[0].end_time, 960);
+
+        assert_eq!(fill_gaps(&mut subs, 500, 40), 1);
+        assert_eq!(subs[1].end_time, 2260);
+        assert_eq!(subs[2].end_time, 3000);
+    }
+
+    #[test]
+    fn test_split_long_cue_at_sentences() {
+        let mut subs = vec![
+            Subtitle::new(
+                1,
+                1000,
+                13000,
+                "First sentence here. Second one is here. Third and last one.".to_string(),
+            ),
+            Subtitle::new(2, 14000, 14100, "Hi.".to_string()),
+        ];
+        assert_eq!(split_long_cues(&mut subs, 5000), 1);
+        assert_eq!(subs.len(), 4);
+        assert_eq!(subs[0].text, "First sentence here.");
+        assert_eq!(subs[0].start_time, 1000);
+        assert_eq!(subs[2].end_time, 13000);
+        for pair in subs[..3].windows(2) {
+            assert_eq!(pair[0].end_time, pair[1].start_time);
+        }
+        for sub in &subs[..3] {
+            assert!(sub.end_time - sub.start_time <= 5000);
+        }
+        assert_eq!(subs[3].index, 4);
+
+        assert_eq!(pad_short_cues(&mut subs, 700, 40), 1);
+        assert_eq!(subs[3].end_time, 14700);
+
+        // Abbreviations don't end a sentence
+        let mut subs = vec![Subtitle::new(
+            1,
+            0,
+            12000,
+            "Mr. Smith is here, e.g. at home. He waits.".to_string(),
+        )];
+        assert_eq!(split_long_cues(&mut subs, 5000), 1);
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0].text, "Mr. Smith is here, e.g. at home.");
+        assert_eq!(subs[1].text, "He waits.");
+    }
+
+    #[test]
+    fn test_split_long_cue_keeps_lock_and_word_timings() {
+        let word = |text: &str, start_time: u64| WordConfidence {
+            text: text.to_string(),
+            probability: 0.9,
+            start_time,
+            end_time: start_time + 400,
+        };
+        let mut subs = vec![Subtitle {
+            locked: true,
+            confidence: Some(vec![
+                word("Hello", 1000),
+                word("there.", 1500),
+                word("General", 8000),
+                word("Kenobi.", 8600),
+            ]),
+            ..Subtitle::new(1, 1000, 10000, "Hello there. General Kenobi.".to_string())
+        }];
+        assert_eq!(split_long_cues(&mut subs, 5000), 1);
+        assert_eq!(subs.len(), 2);
+        // Cut where the second sentence's first word starts
+        assert_eq!((subs[0].start_time, subs[0].end_time), (1000, 8000));
+        assert_eq!((subs[1].start_time, subs[1].end_time), (8000, 10000));
+        for sub in &subs {
+            assert!(sub.locked);
+            let words = sub.confidence.as_ref().unwrap();
+            assert_eq!(words.len(), 2);
+            assert!(words
+                .iter()
+                .all(|w| w.start_time >= sub.start_time && w.end_time <= sub.end_time));
+        }
+        assert_eq!(subs[1].confidence.as_ref().unwrap()[0].text, "General");
+    }
+
+    #[test]
+    fn test_clip_window_shifts_and_trims() {
+        let subs = vec![
+            cue(0, 1000),
+            cue(4000, 6000),
+            cue(9000, 12000),
+            cue(20000, 21000),
+        ];
+        let clipped = clip_window(&subs, 5000, 5000);
+        assert_eq!(clipped.len(), 2);
+        assert_eq!((clipped[0].start_time, clipped[0].end_time), (0, 1000));
+        assert_eq!((clipped[1].start_time, clipped[1].end_time), (4000, 5000));
+        assert_eq!(clipped[1].index, 2);
+    }
+
+    #[test]
+    fn test_append_shifted() {
+        let existing = vec![cue(0, 1000), cue(2000, 3500)];
+        let merged = append_shifted(existing.clone(), vec![cue(100, 900)], None);
+        assert_eq!(merged.len(), 3);
+        assert_eq!((merged[2].start_time, merged[2].end_time), (3600, 4400));
+        assert_eq!(merged[2].index, 3);
+
+        let merged = append_shifted(existing, vec![cue(0, 500)], Some(1500));
+        assert_eq!(merged[1].start_time, 1500);
+        assert_eq!(
+            merged.iter().map(|sub| sub.index).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_clamp_to_media_end() {
+        let mut subs = vec![cue(0, 1000), cue(4000, 6000), cue(7000, 8000)];
+        assert_eq!(count_past_end(&subs, 5000), 2);
+        assert_eq!(clamp_to_media_end(&mut subs, 5000), 2);
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[1].end_time, 5000);
+        assert_eq!(count_past_end(&subs, 5000), 0);
+    }
+
+    #[test]
+    fn test_parse_offset_and_shift() {
+        assert_eq!(parse_offset("+1.5s").unwrap(), 1500);
+        assert_eq!(parse_offset("-00:00:02,000").unwrap(), -2000);
+        assert_eq!(parse_offset("+500ms").unwrap(), 500);
+        assert_eq!(parse_offset("-1s").unwrap(), -1000);
+        assert_eq!(parse_offset("250").unwrap(), 250);
+        assert!(parse_offset("1.5x").is_err());
+        assert!(parse_offset("--1s").is_err());
+
+        let mut subs = vec![cue(500, 1500), cue(3000, 4000)];
+        assert_eq!(shift_all(&mut subs, -1000), 1);
+        assert_eq!((subs[0].start_time, subs[0].end_time), (0, 500));
+        assert_eq!((subs[1].start_time, subs[1].end_time), (2000, 3000));
+    }
+
+    #[test]
+    fn test_ripple_stops_at_locked_cue() {
+        let mut subs = vec![
+            cue(0, 1000),
+            cue(1500, 2500),
+            cue(3000, 4000),
+            cue(6000, 7000),
+        ];
+        subs[3].locked = true;
+        // Cue 1 grew by 500ms: cues 2 and 3 follow, the locked cue 4 doesn't
+        subs[0].end_time += 500;
+        assert_eq!(ripple_from(&mut subs, 1, 500, 40), 2);
+        assert_eq!(subs[1].start_time, 2000);
+        assert_eq!(subs[2].end_time, 4500);
+        assert_eq!(subs[3].start_time, 6000);
+
+        // Only as far as the anchor allows
+        assert_eq!(ripple_from(&mut subs, 1, 5000, 40), 2);
+        assert_eq!(subs[2].end_time, 5960);
+
+        // And never back over the cue before
+        assert_eq!(ripple_from(&mut subs, 1, -10_000, 40), 2);
+        assert_eq!(subs[1].start_time, 1540);
+    }
+
+    #[test]
+    fn test_move_cue_keeps_duration() {
+        let mut subs = vec![cue(50, 1000), cue(2000, 3000), cue(3500, 4000)];
+        assert_eq!(move_cue(&mut subs, 1, 300, 40), 300);
+        assert_eq!((subs[1].start_time, subs[1].end_time), (2300, 3300));
+        // Stops short of the next cue, the previous one and zero
+        assert_eq!(move_cue(&mut subs, 1, 500, 40), 160);
+        assert_eq!(subs[1].end_time, 3460);
+        assert_eq!(move_cue(&mut subs, 1, -5000, 40), -1420);
+        assert_eq!((subs[1].start_time, subs[1].end_time), (1040, 2040));
+        assert_eq!(move_cue(&mut subs, 0, -100, 40), -50);
+        assert_eq!((subs[0].start_time, subs[0].end_time), (0, 950));
+    }
+
+    #[test]
+    fn test_make_continuous_leaves_no_gaps() {
+        let mut subs = vec![
+            Subtitle::new(1, 0, 900, "a".to_string()),
+            Subtitle::new(2, 1500, 2600, "b".to_string()),
+            Subtitle::new(3, 2500, 3000, "c".to_string()),
+        ];
+        assert_eq!(make_continuous(&mut subs, Some(10_000)), 3);
+        for pair in subs.windows(2) {
+            assert_eq!(pair[0].end_time, pair[1].start_time);
+        }
+        assert_eq!(subs[2].end_time, 10_000);
+    }
+}
@@ -0,0 +1,240 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::cli::args::{SubtitlePosition, TextAlign};
+use crate::subtitle::ass::AssStyle;
+use crate::subtitle::srt::Subtitle;
+
+/// Distance of left- or right-aligned cues from the frame edge, about the
+/// burn's 10-unit side margin in its 384-unit wide canvas
+const SIDE_MARGIN_PERCENT: u32 = 3;
+
+/// Parse subtitles from a WebVTT file
+pub fn parse_vtt(path: &Path) -> Result<Vec<Subtitle>> {
+    let content = fs::read_to_string(path).context("Failed to read WebVTT file")?;
+    parse_vtt_string(&content)
+}
+
+/// Parse WebVTT content from a string. Cue identifiers, `NOTE`, `STYLE` and
+/// `REGION` blocks are skipped; of the cue settings only a `line` at the top
+/// or middle is kept, as the cue's position.
+pub fn parse_vtt_string(content: &str) -> Result<Vec<Subtitle>> {
+    let content = content.trim_start_matches('\u{feff}');
+    if !content.starts_with("WEBVTT") {
+        anyhow::bail!("Not a WebVTT file: it doesn't start with WEBVTT");
+    }
+
+    let mut subtitles = Vec::new();
+    let mut lines = content.lines().peekable();
+
+    // The header runs up to the first blank line
+    while lines.next().is_some_and(|l| !l.trim().is_empty()) {}
+
+    while lines.peek().is_some() {
+        // Collect one block, up to the next blank line
+        let mut block = Vec::new();
+        for line in lines.by_ref() {
+            if line.trim().is_empty() {
+                break;
+            }
+            block.push(line);
+        }
+        let Some(first_line) = block.first() else {
+            continue;
+        };
+        if ["NOTE", "STYLE", "REGION"]
+            .iter()
+            .any(|keyword| first_line.split_whitespace().next() == Some(keyword))
+        {
+            continue;
+        }
+
+        // The time range, after an optional cue identifier
+        let Some(timing_at) = block.iter().take(2).position(|l| l.contains("-->")) else {
+            anyhow::bail!("Expected a time range in cue: {}", first_line);
+        };
+        let (start, rest) = block[timing_at]
+            .split_once("-->")
+            .context("Expected time range")?;
+        let mut rest = rest.split_whitespace();
+        let end = rest
+            .next()
+            .with_context(|| format!("Invalid time range: {}", block[timing_at]))?;
+        let start_time = parse_time(start.trim())?;
+        let end_time = parse_time(end)?;
+        let position = rest.find_map(|setting| match setting {
+            "line:0" | "line:0%" => Some(SubtitlePosition::Top),
+            "line:50%" => Some(SubtitlePosition::Middle),
+            _ => None,
+        });
+
+        let text = block[timing_at + 1..].join("\n");
+        subtitles.push(Subtitle {
+            position,
+            ..Subtitle::new(subtitles.len() + 1, start_time, end_time, text)
+        });
+    }
+
+    Ok(subtitles)
+}
+
+/// Parse a WebVTT timestamp: `HH:MM:SS.mmm`, or `MM:SS.mmm` under an hour
+fn parse_time(s: &str) -> Result<u64> {
+    let (clock, millis) = s
+        .split_once('.')
+        .with_context(|| format!("Invalid time format: {}", s))?;
+    let parts: Vec<&str> = clock.split(':').collect();
+    let (hours, minutes, seconds) = match parts[..] {
+        [hours, minutes, seconds] => (hours, minutes, seconds),
+        [minutes, seconds] => ("0", minutes, seconds),
+        _ => anyhow::bail!("Invalid time format: {}", s),
+    };
+
+    let hours: u64 = hours.parse().context("Invalid hours")?;
+    let minutes: u64 = minutes.parse().context("Invalid minutes")?;
+    let seconds: u64 = seconds.parse().context("Invalid seconds")?;
+    let millis: u64 = millis.parse().context("Invalid milliseconds")?;
+
+    Ok(hours * 3_600_000 + minutes * 60_000 + seconds * 1_000 + millis)
+}
+
+/// SRT's timestamp with a `.` before the milliseconds
+fn format_time(ms: u64) -> String {
+    Subtitle::format_time(ms).replace(',', ".")
+}
+
+/// Cue settings placing a cue at `position`, aligned as `style` says: a
+/// `line` for the top or middle, and `align` with a `position` near the edge
+/// for left or right text. Players' defaults (bottom, centered) are left out.
+fn cue_settings(position: SubtitlePosition, style: &AssStyle) -> String {
+    let mut settings = Vec::new();
+    match position {
+        SubtitlePosition::Top => settings.push("line:0".to_string()),
+        SubtitlePosition::Middle => settings.push("line:50%".to_string()),
+        SubtitlePosition::Bottom => {}
+    }
+    match style.align {
+        TextAlign::Left => {
+            settings.push(format!("position:{}%", SIDE_MARGIN_PERCENT));
+            settings.push("align:left".to_string());
+        }
+        TextAlign::Right => {
+            settings.push(format!("position:{}%", 100 - SIDE_MARGIN_PERCENT));
+            settings.push("align:right".to_string());
+        }
+        TextAlign::Center => {}
+    }
+    settings
+        .iter()
+        .map(|setting| format!(" {}", setting))
+        .collect()
+}
+
+/// `WEBVTT` header, then each cue's `HH:MM:SS.mmm --> HH:MM:SS.mmm` line and
+/// text, blank line between cues. Cue settings place each cue where the burn
+/// would: at its position override or the style's, aligned as the style
+/// says. SRT's `<i>`, `<b>` and `<u>` tags are WebVTT's too.
+pub fn to_vtt(subtitles: &[Subtitle], style: &AssStyle) -> String {
+    let mut content = "WEBVTT\n".to_string();
+    for sub in subtitles {
+        let settings = cue_settings(sub.position.unwrap_or(style.position), style);
+        content.push_str(&format!(
+            "\n{} --> {}{}\n{}\n",
+            format_time(sub.start_time),
+            format_time(sub.end_time),
+            settings,
+            sub.text
+        ));
+    }
+    content
+}
+
+/// Save subtitles as WebVTT, placed as `style` says
+pub fn save_vtt(path: &Path, subtitles: &[Subtitle], style: &AssStyle) -> Result<()> {
+    fs::write(path, to_vtt(subtitles, style)).context("Failed to write WebVTT file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subtitle::srt;
+
+    fn cues() -> Vec<Subtitle> {
+        srt::parse_srt_string(
+            "1\n00:00:01,000 --> 00:00:03,500\nHello\nthere\n\n\
+             2\n00:00:03,500 --> 00:00:05,000\n{\\an8}General <i>Kenobi</i>\n\n\
+             3\n01:02:05,120 --> 01:02:06,000\nLater\n",
+        )
+        .unwrap()
+    }
+
+    fn style(position: SubtitlePosition, align: TextAlign) -> AssStyle {
+        AssStyle {
+            position,
+            align,
+            ..AssStyle::default()
+        }
+    }
+
+    #[test]
+    fn test_to_vtt() {
+        assert_eq!(
+            to_vtt(&cues(), &style(SubtitlePosition::Bottom, TextAlign::Center)),
+            "WEBVTT\n\n\
+             00:00:01.000 --> 00:00:03.500\nHello\nthere\n\n\
+             00:00:03.500 --> 00:00:05.000 line:0\nGeneral <i>Kenobi</i>\n\n\
+             01:02:05.120 --> 01:02:06.000\nLater\n"
+        );
+
+        // The burn's alignment and default position drive every cue's settings
+        let vtt = to_vtt(&cues(), &style(SubtitlePosition::Middle, TextAlign::Right));
+        assert!(vtt.contains("00:00:03.500 line:50% position:97% align:right\nHello"));
+        assert!(vtt.contains("00:00:05.000 line:0 position:97% align:right\nGeneral"));
+        let vtt = to_vtt(&cues(), &style(SubtitlePosition::Bottom, TextAlign::Left));
+        assert!(vtt.contains("00:00:03.500 position:3% align:left\nHello"));
+        assert!(vtt.lines().all(|line| !line.ends_with(' ')));
+    }
+
+    #[test]
+    fn test_parse_vtt() {
+        let content = "\u{feff}WEBVTT - Kenobi\nKind: captions\n\n\
+                       NOTE written by hand,\nover two lines\n\n\
+                       STYLE\n::cue { color: yellow }\n\n\
+                       intro\n00:01.000 --> 00:03.500 align:start position:10%\nHello\nthere\n\n\
+                       00:00:03.500 --> 00:00:05.000 line:0\nGeneral <i>Kenobi</i>\n\n\n\
+                       01:02:05.120 --> 01:02:06.000\nLater\n";
+        let parsed = parse_vtt_string(content).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].text, "Hello\nthere");
+        assert_eq!((parsed[0].start_time, parsed[0].end_time), (1000, 3500));
+        assert_eq!(parsed[1].position, Some(SubtitlePosition::Top));
+        assert_eq!(parsed[2].index, 3);
+        assert!(parse_vtt_string("1\n00:00:01,000 --> 00:00:02,000\nHi\n").is_err());
+    }
+
+    #[test]
+    fn test_srt_vtt_round_trip() {
+        // SRT -> WebVTT -> SRT keeps every cue, its timing within a millisecond
+        let vtt = parse_vtt_string(&to_vtt(
+            &cues(),
+            &style(SubtitlePosition::Bottom, TextAlign::Center),
+        ))
+        .unwrap();
+        let srt = vtt
+            .iter()
+            .map(|sub| sub.to_srt(&srt::SrtWriteOptions::default()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let round_trip = srt::parse_srt_string(&srt).unwrap();
+        for (before, after) in cues().iter().zip(&round_trip) {
+            assert!(before.start_time.abs_diff(after.start_time) <= 1);
+            assert!(before.end_time.abs_diff(after.end_time) <= 1);
+            assert_eq!(
+                (&before.text, before.position),
+                (&after.text, after.position)
+            );
+        }
+        assert_eq!(round_trip.len(), 3);
+    }
+}
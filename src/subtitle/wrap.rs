@@ -0,0 +1,214 @@
+use crate::subtitle::srt::Subtitle;
+use anyhow::{Context, Result};
+use std::path::Path;
+use ttf_parser::{Face, GlyphId};
+
+/// A TrueType/OpenType font, read with `ttf-parser` to measure how wide a
+/// line of text renders without rasterizing anything. Kerning is ignored,
+/// which only ever makes a line measure slightly wider than it is.
+#[derive(Debug, Clone)]
+pub struct FontMetrics {
+    data: Vec<u8>,
+    /// Ascent plus descent in font units; libass scales a font so this spans
+    /// the style's font size, so widths are measured against it too
+    line_height: f64,
+}
+
+impl FontMetrics {
+    /// Read the metrics of a `.ttf`/`.otf` file (the first font of a `.ttc`)
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read font {}", path.display()))?;
+        Self::from_bytes(data).with_context(|| format!("Unsupported font {}", path.display()))
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        let face = Face::parse(&data, 0)?;
+        // The OS/2 Windows metrics are what libass sizes by; hhea is the fallback
+        let line_height = match face.tables().os2 {
+            Some(os2) => os2.windows_ascender() as f64 - os2.windows_descender() as f64,
+            None => face.tables().hhea.ascender as f64 - face.tables().hhea.descender as f64,
+        };
+        let line_height = if line_height > 0.0 {
+            line_height
+        } else {
+            face.units_per_em() as f64
+        };
+        Ok(Self { data, line_height })
+    }
+
+    /// Rendered width of `text` in pixels at font size `size`
+    pub fn text_width(&self, text: &str, size: f64) -> f64 {
+        // Parsed once already in `from_bytes`, so this can't fail
+        let Ok(face) = Face::parse(&self.data, 0) else {
+            return 0.0;
+        };
+        let units: f64 = text
+            .chars()
+            .map(|c| {
+                let glyph = face.glyph_index(c).unwrap_or(GlyphId(0));
+                face.glyph_hor_advance(glyph).unwrap_or_default() as f64
+            })
+            .sum();
+        units * size / self.line_height
+    }
+}
+
+/// Widths to wrap by: a font's, or estimated from the character count
+#[derive(Debug, Clone)]
+pub struct WrapFont {
+    pub metrics: Option<FontMetrics>,
+    /// Glyph width as a fraction of the font size, for estimates
+    pub char_width_factor: f64,
+}
+
+impl WrapFont {
+    /// Measure with the font at `font_file` when given, else estimate
+    pub fn load(font_file: Option<&Path>, char_width_factor: f64) -> Result<Self> {
+        Ok(Self {
+            metrics: font_file.map(FontMetrics::load).transpose()?,
+            char_width_factor,
+        })
+    }
+
+    /// Wrap every cue to `max_px` at font size `size`. Returns how many
+    /// cues changed.
+    pub fn wrap_all(&self, subtitles: &mut [Subtitle], size: f64, max_px: f64) -> usize {
+        let max_chars = (max_px / (size * self.char_width_factor)).floor().max(1.0) as usize;
+        let mut changed = 0;
+        for sub in subtitles.iter_mut() {
+            let wrapped = match &self.metrics {
+                Some(font) => wrap_to_pixel_width(&sub.text, font, size, max_px),
+                None => wrap_to_char_count(&sub.text, max_chars),
+            };
+            if wrapped != sub.text {
+                sub.text = wrapped;
+                changed += 1;
+            }
+        }
+        changed
+    }
+}
+
+/// Break each line of `text` at spaces so it renders no wider than `max_px`
+/// in `font` at `size`. Existing line breaks are kept; a single word wider
+/// than the limit gets a line of its own.
+pub fn wrap_to_pixel_width(text: &str, font: &FontMetrics, size: f64, max_px: f64) -> String {
+    wrap_with(text, |line| font.text_width(line, size) <= max_px)
+}
+
+/// Break each line of `text` at spaces so it has at most `max_chars`
+/// characters, for when no font is at hand to measure with
+pub fn wrap_to_char_count(text: &str, max_chars: usize) -> String {
+    wrap_with(text, |line| line.chars().count() <= max_chars)
+}
+
+/// Greedy word wrap: each line takes as many words as still `fits`
+fn wrap_with(text: &str, fits: impl Fn(&str) -> bool) -> String {
+    let mut lines = Vec::new();
+    for source in text.lines() {
+        if fits(source.trim()) {
+            lines.push(source.to_string());
+            continue;
+        }
+        let mut line = String::new();
+        for word in source.split_whitespace() {
+            if line.is_empty() {
+                line.push_str(word);
+                continue;
+            }
+            let candidate = format!("{} {}", line, word);
+            if fits(&candidate) {
+                line = candidate;
+            } else {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            }
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A font with three glyphs: missing (500 units), 'i' (250) and
+    /// 'm'/'w' (1000), 1000 units per em and as tall, with a format 4 cmap
+    /// and none of the outline tables only rendering needs
+    fn test_font() -> FontMetrics {
+        let be16 =
+            |values: &[u16]| -> Vec<u8> { values.iter().flat_map(|v| v.to_be_bytes()).collect() };
+
+        let mut head = vec![0; 54];
+        head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+        let mut hhea = vec![0; 36];
+        hhea[4..6].copy_from_slice(&800u16.to_be_bytes());
+        hhea[6..8].copy_from_slice(&(-200i16).to_be_bytes());
+        hhea[34..36].copy_from_slice(&3u16.to_be_bytes());
+        let hmtx = be16(&[500, 0, 250, 0, 1000, 0]);
+        let maxp = be16(&[0, 0x5000, 3]);
+        // Segments: 'i' alone, 'm'..'w' through the glyph array, then the 0xFFFF end
+        let mut cmap: Vec<u8> = be16(&[0, 1, 3, 1, 0, 12]);
+        cmap.extend(be16(&[4, 0, 0, 6, 0, 0, 0]));
+        cmap.extend(be16(&[0x69, 0x77, 0xFFFF, 0]));
+        cmap.extend(be16(&[0x69, 0x6D, 0xFFFF]));
+        cmap.extend(be16(&[(1u16).wrapping_sub(0x69), 0, 1]));
+        cmap.extend(be16(&[0, 4, 0]));
+        let mut glyphs = vec![0u16; 11];
+        glyphs[0] = 2;
+        glyphs[10] = 2;
+        cmap.extend(be16(&glyphs));
+
+        let tables = [
+            (b"cmap", cmap),
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", hmtx),
+            (b"maxp", maxp),
+        ];
+        let mut data: Vec<u8> = vec![0, 1, 0, 0];
+        data.extend(be16(&[tables.len() as u16, 0, 0, 0]));
+        let mut offset = 12 + tables.len() * 16;
+        for (tag, body) in &tables {
+            data.extend(tag.iter());
+            data.extend([0; 4]);
+            data.extend((offset as u32).to_be_bytes());
+            data.extend((body.len() as u32).to_be_bytes());
+            offset += body.len();
+        }
+        for (_, body) in tables {
+            data.extend(body);
+        }
+        FontMetrics::from_bytes(data).unwrap()
+    }
+
+    #[test]
+    fn test_wraps_by_measured_width() {
+        let font = test_font();
+        assert_eq!(font.text_width("i", 20.0), 5.0);
+        assert_eq!(font.text_width("mw", 20.0), 40.0);
+        assert_eq!(font.text_width("x", 20.0), 10.0);
+
+        // Same length, but narrow letters fit where wide ones don't
+        assert_eq!(wrap_to_pixel_width("iii iii", &font, 20.0, 40.0), "iii iii");
+        assert_eq!(
+            wrap_to_pixel_width("mmm mmm", &font, 20.0, 40.0),
+            "mmm\nmmm"
+        );
+        assert_eq!(
+            wrap_to_pixel_width("- i i i\n- mm mm", &font, 20.0, 70.0),
+            "- i i i\n- mm\nmm"
+        );
+
+        assert_eq!(wrap_to_char_count("one two three", 7), "one two\nthree");
+        let mut subs = vec![Subtitle::new(1, 0, 1000, "one two three".to_string())];
+        let estimate = WrapFont {
+            metrics: None,
+            char_width_factor: 0.6,
+        };
+        assert_eq!(estimate.wrap_all(&mut subs, 10.0, 42.0), 1);
+        assert_eq!(subs[0].text, "one two\nthree");
+        assert!(FontMetrics::from_bytes(vec![0, 1, 0, 0]).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::Result;
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use std::io::Stdout;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+
+use crate::app::App;
+
+/// Run `app` in the terminal until it quits. Owns raw mode, the alternate
+/// screen and mouse capture, and restores the terminal on every exit path:
+/// normal return, early error, or panic.
+pub async fn run_app(app: &mut App, use_altscreen: bool) -> Result<()> {
+    install_panic_hook();
+
+    let mut terminal = setup_terminal(use_altscreen)?;
+    let _guard = TerminalGuard;
+
+    let result = app.run(&mut terminal).await;
+
+    // Restore explicitly so teardown errors are reported; the guard then
+    // finds nothing left to undo
+    restore_terminal()?;
+
+    result
+}
+
+/// Restores the terminal when dropped, so an early `?` or unwinding can't
+/// leave the user's shell in raw mode
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = restore_terminal();
+    }
+}
+
+/// Restore the terminal before the panic message is printed, so it's readable
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let original_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            let _ = restore_terminal();
+            original_hook(panic_info);
+        }));
+    });
+}
+
+// What setup_terminal changed, so restore_terminal only undoes that and
+// is a no-op when called twice (e.g. from the panic hook and normal exit)
+static RAW_MODE: AtomicBool = AtomicBool::new(false);
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+static MOUSE_CAPTURE: AtomicBool = AtomicBool::new(false);
+
+/// Put the terminal into raw mode and, when possible, the alternate screen.
+/// If the alternate screen can't be entered (or `use_altscreen` is false)
+/// the TUI is drawn on the main screen instead.
+fn setup_terminal(use_altscreen: bool) -> Result<Terminal<CrosstermBackend<Stdout>>> {
+    use crossterm::{
+        event::EnableMouseCapture,
+        execute,
+        terminal::{enable_raw_mode, EnterAlternateScreen},
+    };
+
+    enable_raw_mode()?;
+    RAW_MODE.store(true, Ordering::SeqCst);
+
+    let mut stdout = std::io::stdout();
+    if use_altscreen {
+        match execute!(stdout, EnterAlternateScreen) {
+            Ok(()) => ALTERNATE_SCREEN.store(true, Ordering::SeqCst),
+            Err(e) => tracing::debug!("Alternate screen unavailable, drawing inline: {}", e),
+        }
+    }
+    if execute!(stdout, EnableMouseCapture).is_ok() {
+        MOUSE_CAPTURE.store(true, Ordering::SeqCst);
+    }
+
+    let terminal = Terminal::new(CrosstermBackend::new(stdout)).and_then(|mut terminal| {
+        if !ALTERNATE_SCREEN.load(Ordering::SeqCst) {
+            terminal.clear()?;
+        }
+        Ok(terminal)
+    });
+    match terminal {
+        Ok(terminal) => Ok(terminal),
+        Err(e) => {
+            let _ = restore_terminal();
+            Err(e.into())
+        }
+    }
+}
+
+/// Undo whatever `setup_terminal` did. Safe to call more than once, or
+/// without setup having happened.
+fn restore_terminal() -> Result<()> {
+    use crossterm::{
+        cursor::{MoveTo, Show},
+        event::DisableMouseCapture,
+        execute,
+        terminal::{disable_raw_mode, Clear, ClearType, LeaveAlternateScreen},
+    };
+
+    let mut stdout = std::io::stdout();
+    if MOUSE_CAPTURE.swap(false, Ordering::SeqCst) {
+        execute!(stdout, DisableMouseCapture)?;
+    }
+    let was_raw = RAW_MODE.swap(false, Ordering::SeqCst);
+    if ALTERNATE_SCREEN.swap(false, Ordering::SeqCst) {
+        execute!(stdout, LeaveAlternateScreen)?;
+    } else if was_raw {
+        // Drawn inline: don't leave the last frame behind the shell prompt
+        execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+    }
+    if was_raw {
+        execute!(stdout, Show)?;
+        disable_raw_mode()?;
+    }
+    Ok(())
+}
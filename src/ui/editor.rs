@@ -3,17 +3,23 @@ use ratatui::{
     style::Modifier,
     text::{Line, Span},
     widgets::{
-        Block, Borders, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Wrap,
     },
     Frame,
 };
 
-use super::style;
-use crate::app::App;
-use crate::subtitle::srt::Subtitle;
+use super::keymap::Action;
+use super::progress::centered_rect;
+use super::{spinner, style};
+use crate::app::{App, FindMode, LeaveAction, Prompt};
+use crate::cli::args::SubtitlePosition;
+use crate::subtitle::lint::{self, Severity};
+use crate::subtitle::srt::{Subtitle, LOW_CONFIDENCE};
 
 pub fn draw(frame: &mut Frame, app: &App) {
     let area = frame.area();
+    let issue_count = app.lint_issues().len();
 
     let chunks = Layout::vertical([
         Constraint::Length(3), // Title
@@ -22,17 +28,38 @@ pub fn draw(frame: &mut Frame, app: &App) {
     ])
     .split(area);
 
-    // Title
+    // Title, followed by the open tabs when there is more than one
+    let heading = if app.dirty {
+        " SUBTITLE EDITOR * "
+    } else {
+        " SUBTITLE EDITOR "
+    };
+    let mut title_line = vec![
+        Span::styled("┌─", style::border_style()),
+        Span::styled(heading, style::title_style()),
+    ];
+    let mut used = heading.chars().count() + 5;
+    let active = app.active_document();
+    for (i, label) in app.document_labels().into_iter().enumerate() {
+        let tab = format!(" {}:{} ", i + 1, label);
+        used += tab.chars().count();
+        title_line.push(Span::styled(
+            tab,
+            if i == active {
+                style::highlight_style()
+            } else {
+                style::muted_style()
+            },
+        ));
+    }
+    title_line.push(Span::styled(
+        "─".repeat((area.width as usize).saturating_sub(used)),
+        style::border_style(),
+    ));
+    title_line.push(Span::styled("┐", style::border_style()));
+
     let title = Paragraph::new(vec![
-        Line::from(vec![
-            Span::styled("┌─", style::border_style()),
-            Span::styled(" SUBTITLE EDITOR ", style::title_style()),
-            Span::styled(
-                "─".repeat((area.width as usize).saturating_sub(22)),
-                style::border_style(),
-            ),
-            Span::styled("┐", style::border_style()),
-        ]),
+        Line::from(title_line),
         Line::from(vec![
             Span::styled("│ ", style::border_style()),
             Span::styled(
@@ -52,34 +79,563 @@ pub fn draw(frame: &mut Frame, app: &App) {
                     .unwrap_or_default(),
                 style::muted_style(),
             ),
+            Span::styled(
+                if app.is_busy() {
+                    format!(
+                        " │ {} {}",
+                        spinner::frame(),
+                        if app.transcribing {
+                            "transcribing..."
+                        } else {
+                            "working..."
+                        }
+                    )
+                } else {
+                    String::new()
+                },
+                style::success_style(),
+            ),
+            Span::styled(
+                if issue_count > 0 {
+                    format!(" │ {} issues (Tab to jump)", issue_count)
+                } else {
+                    String::new()
+                },
+                style::warning_style(),
+            ),
         ]),
+        stats_line(app),
     ]);
     frame.render_widget(title, chunks[0]);
 
-    // Content area - split into list and edit panel
-    let content_chunks = Layout::horizontal([
-        Constraint::Percentage(60), // Subtitle list
-        Constraint::Percentage(40), // Edit panel
-    ])
-    .split(chunks[1]);
+    if app.inline_edit {
+        // Rows are edited in place, so the list gets the whole width
+        draw_subtitle_list(frame, app, chunks[1]);
+    } else {
+        // Content area - split into list and edit panel
+        let content_chunks = Layout::horizontal([
+            Constraint::Percentage(60), // Subtitle list
+            Constraint::Percentage(40), // Edit panel
+        ])
+        .split(chunks[1]);
 
-    // Subtitle list
-    draw_subtitle_list(frame, app, content_chunks[0]);
+        // Subtitle list
+        draw_subtitle_list(frame, app, content_chunks[0]);
 
-    // Edit panel
-    draw_edit_panel(frame, app, content_chunks[1]);
+        // Edit panel
+        draw_edit_panel(frame, app, content_chunks[1]);
+    }
 
     // Help bar
     draw_help(frame, app, chunks[2]);
+
+    // Modal prompt on top of everything else
+    if let Some(prompt) = &app.prompt {
+        draw_prompt(frame, app, prompt);
+    }
+}
+
+/// At-a-glance figures for the whole file: time on screen, how much of the
+/// video that covers, reading speed and the longest cue
+fn stats_line(app: &App) -> Line<'static> {
+    if app.subtitles.is_empty() {
+        return Line::from("");
+    }
+    let stats = lint::stats(&app.subtitles, app.video_duration_ms);
+
+    let mut spans = vec![
+        Span::styled("│ ", style::border_style()),
+        Span::styled(
+            format!("{} shown", format_span(stats.shown_ms)),
+            style::normal_style(),
+        ),
+    ];
+    if let Some(coverage) = stats.coverage {
+        spans.push(Span::styled(
+            format!(" ({:.0}% of video)", coverage * 100.0),
+            style::muted_style(),
+        ));
+    }
+    spans.push(Span::styled(" │ ", style::muted_style()));
+    spans.push(Span::styled(
+        format!("avg {:.1} cps, max ", stats.average_cps),
+        style::normal_style(),
+    ));
+    spans.push(Span::styled(
+        format!("{:.1}", stats.max_cps),
+        if stats.max_cps > app.lint_rules.max_cps {
+            style::warning_style()
+        } else {
+            style::normal_style()
+        },
+    ));
+    if let Some((position, duration)) = stats.longest {
+        spans.push(Span::styled(" │ ", style::muted_style()));
+        spans.push(Span::styled(
+            format!("longest #{} {}", position + 1, format_span(duration)),
+            if duration > app.lint_rules.max_duration_ms {
+                style::warning_style()
+            } else {
+                style::normal_style()
+            },
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Compact length for the stats line: "8.4s", "4m05s", "1h02m"
+fn format_span(ms: u64) -> String {
+    let seconds = ms / 1000;
+    if seconds < 60 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else if seconds < 3600 {
+        format!("{}m{:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}h{:02}m", seconds / 3600, seconds % 3600 / 60)
+    }
+}
+
+fn draw_prompt(frame: &mut Frame, app: &App, prompt: &Prompt) {
+    let area = centered_rect(60, 30, frame.area());
+
+    let (title, content) = match prompt {
+        Prompt::OpenDocument => (
+            " Open in New Tab ",
+            vec![
+                Line::from(vec![Span::styled(
+                    "Subtitle file to open:",
+                    style::muted_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled(&app.prompt_buffer, style::normal_style()),
+                    Span::styled("█", style::key_style()), // Cursor
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Enter", style::key_style()),
+                    Span::styled(" open  ", style::muted_style()),
+                    Span::styled("Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::FindText { mode, error } => (
+            " Replace in All Cues ",
+            vec![
+                Line::from(vec![
+                    Span::styled("Find (", style::muted_style()),
+                    Span::styled(mode.as_str(), style::key_style()),
+                    Span::styled("):", style::muted_style()),
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled(&app.prompt_buffer, style::normal_style()),
+                    Span::styled("█", style::key_style()), // Cursor
+                ]),
+                Line::from(vec![Span::styled(
+                    error.as_deref().unwrap_or(""),
+                    style::error_style(),
+                )]),
+                Line::from(vec![
+                    Span::styled("Enter", style::key_style()),
+                    Span::styled(" continue  ", style::muted_style()),
+                    Span::styled("Tab", style::key_style()),
+                    Span::styled(" case/regex  ", style::muted_style()),
+                    Span::styled("Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::ReplaceWith { find, mode } => (
+            " Replace in All Cues ",
+            vec![
+                Line::from(vec![
+                    Span::styled("Replace ", style::muted_style()),
+                    Span::styled(find.as_str(), style::key_style()),
+                    Span::styled(
+                        if *mode == FindMode::Regex {
+                            " with ($1 for groups):"
+                        } else {
+                            " with:"
+                        },
+                        style::muted_style(),
+                    ),
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled(&app.prompt_buffer, style::normal_style()),
+                    Span::styled("█", style::key_style()), // Cursor
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Enter", style::key_style()),
+                    Span::styled(" continue  ", style::muted_style()),
+                    Span::styled("Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::ConfirmReplace {
+            find,
+            to,
+            occurrences,
+            cues,
+            ..
+        } => (
+            " Confirm Replace ",
+            vec![
+                Line::from(vec![
+                    Span::styled(find.as_str(), style::key_style()),
+                    Span::styled(" → ", style::muted_style()),
+                    Span::styled(to.as_str(), style::key_style()),
+                ]),
+                Line::from(""),
+                Line::from(vec![Span::styled(
+                    format!(
+                        "Will replace {} occurrence{} in {} cue{}.",
+                        occurrences,
+                        if *occurrences == 1 { "" } else { "s" },
+                        cues,
+                        if *cues == 1 { "" } else { "s" }
+                    ),
+                    style::normal_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("y", style::key_style()),
+                    Span::styled(" replace  ", style::muted_style()),
+                    Span::styled("n/Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::ConfirmResegment { first, last } => (
+            " Confirm Re-segment ",
+            vec![
+                Line::from(vec![Span::styled(
+                    if *first == 0 && *last + 1 == app.subtitles.len() {
+                        format!(
+                            "Re-cut all {} cues at pauses in the audio?",
+                            app.subtitles.len()
+                        )
+                    } else {
+                        format!("Re-cut cues {}-{} at pauses in the audio?", first + 1, last + 1)
+                    },
+                    style::normal_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![Span::styled(
+                    format!(
+                        "⚠ Their timing, and which words each cue holds, are rewritten\n  (locked cues are kept; {} undoes).",
+                        app.keymap.label(Action::Undo)
+                    ),
+                    style::warning_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("y", style::key_style()),
+                    Span::styled(" re-segment  ", style::muted_style()),
+                    Span::styled("n/Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::RegenerateSettings => (
+            " Regenerate Subtitles ",
+            vec![
+                Line::from(vec![Span::styled(
+                    "Model and language (e.g. \"small fr\", \"base auto\"):",
+                    style::muted_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled(&app.prompt_buffer, style::normal_style()),
+                    Span::styled("█", style::key_style()), // Cursor
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Enter", style::key_style()),
+                    Span::styled(" continue  ", style::muted_style()),
+                    Span::styled("Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::ConfirmRegenerate { model, language } => (
+            " Confirm Regenerate ",
+            vec![
+                Line::from(vec![
+                    Span::styled("Re-transcribe with ", style::normal_style()),
+                    Span::styled(
+                        format!("{} / {}", model.as_str(), language),
+                        style::key_style(),
+                    ),
+                    Span::styled("?", style::normal_style()),
+                ]),
+                Line::from(""),
+                Line::from(vec![Span::styled(
+                    "⚠ The SRT file is overwritten. Your current subtitles are kept until you\n  compare them with the new ones.",
+                    style::warning_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("y", style::key_style()),
+                    Span::styled(" regenerate  ", style::muted_style()),
+                    Span::styled("n/Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::SubtitlesPastEnd {
+            count,
+            media_end_ms,
+        } => (
+            " Subtitles Past End ",
+            vec![
+                Line::from(vec![
+                    Span::styled(format!("{} cues", count), style::key_style()),
+                    Span::styled(
+                        format!(
+                            " run past the end of the video ({}).",
+                            Subtitle::format_time(*media_end_ms)
+                        ),
+                        style::normal_style(),
+                    ),
+                ]),
+                Line::from(""),
+                Line::from(vec![Span::styled(
+                    "Their tails would be cut off. Usually a sign of a bad shift or rescale.",
+                    style::muted_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("t", style::key_style()),
+                    Span::styled(" trim and burn  ", style::muted_style()),
+                    Span::styled("b", style::key_style()),
+                    Span::styled(" burn anyway  ", style::muted_style()),
+                    Span::styled("c/Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::OverwriteOutput { path } => (
+            " Output Exists ",
+            vec![
+                Line::from(vec![
+                    Span::styled(path.display().to_string(), style::key_style()),
+                    Span::styled(" already exists.", style::normal_style()),
+                ]),
+                Line::from(""),
+                Line::from(vec![Span::styled(
+                    "Replace it, or write the new render under a numbered name?",
+                    style::muted_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("o", style::key_style()),
+                    Span::styled(" overwrite  ", style::muted_style()),
+                    Span::styled("r", style::key_style()),
+                    Span::styled(" rename  ", style::muted_style()),
+                    Span::styled("c/Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::CueTiming { error } => (
+            " Cue Timing ",
+            vec![
+                Line::from(vec![Span::styled(
+                    "Start and end (HH:MM:SS,mmm --> HH:MM:SS,mmm):",
+                    style::muted_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled(&app.prompt_buffer, style::normal_style()),
+                    Span::styled("█", style::key_style()), // Cursor
+                ]),
+                Line::from(vec![Span::styled(
+                    error.as_deref().unwrap_or(""),
+                    style::error_style(),
+                )]),
+                Line::from(vec![
+                    Span::styled("Enter", style::key_style()),
+                    Span::styled(" apply  ", style::muted_style()),
+                    Span::styled("Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::GoToTime { error } => (
+            " Go to Time ",
+            vec![
+                Line::from(vec![Span::styled(
+                    "Timecode (HH:MM:SS,mmm):",
+                    style::muted_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled(&app.prompt_buffer, style::normal_style()),
+                    Span::styled("█", style::key_style()), // Cursor
+                ]),
+                Line::from(vec![Span::styled(
+                    error.as_deref().unwrap_or(""),
+                    style::error_style(),
+                )]),
+                Line::from(vec![
+                    Span::styled("Enter", style::key_style()),
+                    Span::styled(" go  ", style::muted_style()),
+                    Span::styled("Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::ReviewRegenerate { summary } => (
+            " Regenerate Complete ",
+            vec![
+                Line::from(vec![Span::styled(
+                    "Compared with your previous subtitles:",
+                    style::normal_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![Span::styled(summary.to_string(), style::key_style())]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("k/Enter", style::key_style()),
+                    Span::styled(" keep new  ", style::muted_style()),
+                    Span::styled("r/Esc", style::key_style()),
+                    Span::styled(" restore previous", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::EmbeddedSubtitles { tracks } => (
+            " Embedded Subtitles ",
+            std::iter::once(Line::from(vec![Span::styled(
+                "This video already has subtitle tracks. Edit one instead of transcribing?",
+                style::normal_style(),
+            )]))
+            .chain(std::iter::once(Line::from("")))
+            .chain(tracks.iter().map(|track| {
+                Line::from(vec![
+                    Span::styled(format!("  {} ", track.index), style::key_style()),
+                    Span::styled(
+                        track.label(),
+                        if track.is_text() {
+                            style::normal_style()
+                        } else {
+                            style::muted_style()
+                        },
+                    ),
+                ])
+            }))
+            .chain([
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("0-9", style::key_style()),
+                    Span::styled(" edit track  ", style::muted_style()),
+                    Span::styled("w/Enter", style::key_style()),
+                    Span::styled(" transcribe with Whisper  ", style::muted_style()),
+                    Span::styled("Esc", style::key_style()),
+                    Span::styled(" back", style::muted_style()),
+                ]),
+            ])
+            .collect(),
+        ),
+        Prompt::RestoreAutosave { path } => (
+            " Restore Unsaved Edits ",
+            vec![
+                Line::from(vec![Span::styled(
+                    "This file has newer edits that were never saved:",
+                    style::normal_style(),
+                )]),
+                Line::from(vec![Span::styled(
+                    path.display().to_string(),
+                    style::muted_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("y", style::key_style()),
+                    Span::styled(" restore  ", style::muted_style()),
+                    Span::styled("n/Esc", style::key_style()),
+                    Span::styled(" discard", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::UnsavedChanges { then } => (
+            " Unsaved Changes ",
+            vec![
+                Line::from(vec![Span::styled(
+                    match then {
+                        LeaveAction::Quit => "Save your changes before quitting?",
+                        LeaveAction::Home => "Save your changes before leaving the editor?",
+                    },
+                    style::normal_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("s", style::key_style()),
+                    Span::styled(" save  ", style::muted_style()),
+                    Span::styled("d", style::key_style()),
+                    Span::styled(" discard  ", style::muted_style()),
+                    Span::styled("c/Esc", style::key_style()),
+                    Span::styled(" cancel", style::muted_style()),
+                ]),
+            ],
+        ),
+        Prompt::NoAudioStream => (
+            " No Audio ",
+            vec![
+                Line::from(vec![Span::styled(
+                    "This video has no audio stream to transcribe.",
+                    style::normal_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![Span::styled(
+                    "Open the editor with an empty subtitle list to caption it manually?",
+                    style::muted_style(),
+                )]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("y", style::key_style()),
+                    Span::styled(" open editor  ", style::muted_style()),
+                    Span::styled("n/Esc", style::key_style()),
+                    Span::styled(" back", style::muted_style()),
+                ]),
+            ],
+        ),
+    };
+
+    let block = Block::default()
+        .title(title)
+        .title_style(style::title_style())
+        .borders(Borders::ALL)
+        .border_style(style::border_style());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(content)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
 }
 
 fn draw_subtitle_list(frame: &mut Frame, app: &App, area: Rect) {
+    // Worst lint severity per cue, for the marker column
+    let mut severities: Vec<Option<Severity>> = vec![None; app.subtitles.len()];
+    for issue in app.lint_issues() {
+        let worst = &mut severities[issue.position];
+        *worst = (*worst).max(Some(issue.severity));
+    }
+
+    let range = app.selected_range();
     let items: Vec<ListItem> = app
         .subtitles
         .iter()
         .enumerate()
         .map(|(i, sub)| {
             let is_selected = i == app.selected_index;
+            let in_range = range.is_some_and(|(first, last)| (first..=last).contains(&i));
             let time_str = format!(
                 "{} → {}",
                 Subtitle::format_time(sub.start_time),
@@ -88,27 +644,53 @@ fn draw_subtitle_list(frame: &mut Frame, app: &App, area: Rect) {
 
             // Truncate text if too long
             let max_text_len = (area.width as usize).saturating_sub(35);
-            let text_preview: String = sub.text.chars().take(max_text_len).collect();
-            let text_preview = if sub.text.len() > max_text_len {
-                format!("{}...", text_preview)
+            let editing_here = app.inline_edit && app.editing_subtitle && is_selected;
+            let text_preview = if editing_here {
+                // Keep the end of the buffer, where the cursor is, in view
+                let chars: Vec<char> = app.edit_buffer.replace('\n', "↵").chars().collect();
+                let start = chars.len().saturating_sub(max_text_len.saturating_sub(1));
+                format!("{}█", chars[start..].iter().collect::<String>())
             } else {
-                text_preview
+                let text_preview: String = sub.text.chars().take(max_text_len).collect();
+                if sub.text.len() > max_text_len {
+                    format!("{}...", text_preview)
+                } else {
+                    text_preview
+                }
             };
 
             let content = format!(
-                " {:3} │ {} │ {}",
+                "{:3} │ {} │ {}{}{}",
                 sub.index,
                 time_str,
+                // Anchors for ripple edits
+                if sub.locked { "🔒 " } else { "" },
+                // Cues moved off the default band
+                match sub.position {
+                    Some(SubtitlePosition::Top) => "▲ ",
+                    Some(SubtitlePosition::Middle) => "◆ ",
+                    _ => "",
+                },
                 text_preview.replace('\n', " ")
             );
 
-            let style = if is_selected {
+            let style = if editing_here {
+                style::editing_style()
+            } else if is_selected {
                 style::highlight_style()
+            } else if in_range {
+                style::selection_style()
             } else {
                 style::normal_style()
             };
 
-            ListItem::new(Line::from(Span::styled(content, style)))
+            let marker = match severities[i] {
+                Some(Severity::Error) => Span::styled("✖", style::error_style()),
+                Some(Severity::Warning) => Span::styled("⚠", style::warning_style()),
+                None => Span::raw(" "),
+            };
+
+            ListItem::new(Line::from(vec![marker, Span::styled(content, style)]))
         })
         .collect();
 
@@ -120,7 +702,11 @@ fn draw_subtitle_list(frame: &mut Frame, app: &App, area: Rect) {
                 .borders(Borders::ALL)
                 .border_style(style::border_style()),
         )
-        .highlight_style(style::highlight_style());
+        .highlight_style(if app.inline_edit && app.editing_subtitle {
+            style::editing_style()
+        } else {
+            style::highlight_style()
+        });
 
     // Create a stateful list to enable scrolling
     let mut list_state = ratatui::widgets::ListState::default();
@@ -146,6 +732,52 @@ fn draw_subtitle_list(frame: &mut Frame, app: &App, area: Rect) {
     }
 }
 
+/// Cue text as one `Line` per line break, with a cursor after the last one
+fn text_lines(text: &str, cursor: bool) -> Vec<Line<'_>> {
+    let mut lines: Vec<Line> = text
+        .split('\n')
+        .map(|line| Line::from(Span::styled(line, style::normal_style())))
+        .collect();
+    if cursor {
+        if let Some(last) = lines.last_mut() {
+            last.push_span(Span::styled("█", style::key_style()));
+        }
+    }
+    lines
+}
+
+/// Cue text with words Whisper was unsure of picked out, when the
+/// confidence still lines up with the text
+fn confidence_lines(sub: &Subtitle) -> Option<Vec<Line<'_>>> {
+    let words = sub.confidence.as_ref()?;
+    if words.len() != sub.text.split_whitespace().count() {
+        return None;
+    }
+
+    let mut words = words.iter();
+    let lines = sub
+        .text
+        .split('\n')
+        .map(|line| {
+            let spans: Vec<Span> = line
+                .split_inclusive(' ')
+                .map(|piece| {
+                    let unsure = !piece.trim().is_empty()
+                        && words.next().is_some_and(|w| w.probability < LOW_CONFIDENCE);
+                    let style = if unsure {
+                        style::warning_style().add_modifier(Modifier::UNDERLINED)
+                    } else {
+                        style::normal_style()
+                    };
+                    Span::styled(piece, style)
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+    Some(lines)
+}
+
 fn draw_edit_panel(frame: &mut Frame, app: &App, area: Rect) {
     let block = Block::default()
         .title(if app.editing_subtitle {
@@ -169,58 +801,119 @@ fn draw_edit_panel(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(block, area);
 
     if let Some(sub) = app.subtitles.get(app.selected_index) {
-        let content = if app.editing_subtitle {
-            vec![
-                Line::from(vec![Span::styled("Text:", style::key_style())]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::styled(&app.edit_buffer, style::normal_style()),
-                    Span::styled("█", style::key_style()), // Cursor
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::styled("Press ", style::muted_style()),
-                    Span::styled("Enter", style::key_style()),
-                    Span::styled(" to save, ", style::muted_style()),
-                    Span::styled("Esc", style::key_style()),
-                    Span::styled(" to cancel", style::muted_style()),
-                ]),
-            ]
+        let (header, text, footer) = if app.editing_subtitle {
+            (
+                vec![
+                    Line::from(vec![Span::styled("Text:", style::key_style())]),
+                    Line::from(""),
+                ],
+                text_lines(&app.edit_buffer, true),
+                vec![
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("Press ", style::muted_style()),
+                        Span::styled("Enter", style::key_style()),
+                        Span::styled(" to save, ", style::muted_style()),
+                        Span::styled("Esc", style::key_style()),
+                        Span::styled(" to cancel", style::muted_style()),
+                    ]),
+                ],
+            )
         } else {
-            vec![
-                Line::from(vec![
-                    Span::styled("Index: ", style::muted_style()),
-                    Span::styled(sub.index.to_string(), style::normal_style()),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::styled("Start: ", style::muted_style()),
-                    Span::styled(Subtitle::format_time(sub.start_time), style::key_style()),
-                ]),
-                Line::from(vec![
-                    Span::styled("End:   ", style::muted_style()),
-                    Span::styled(Subtitle::format_time(sub.end_time), style::key_style()),
-                ]),
-                Line::from(""),
-                Line::from(vec![Span::styled("Text:", style::muted_style())]),
-                Line::from(vec![Span::styled(&sub.text, style::normal_style())]),
-            ]
+            let highlighted = app.show_confidence.then(|| confidence_lines(sub)).flatten();
+            (
+                vec![
+                    Line::from(vec![
+                        Span::styled("Index: ", style::muted_style()),
+                        Span::styled(sub.index.to_string(), style::normal_style()),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("Start: ", style::muted_style()),
+                        Span::styled(Subtitle::format_time(sub.start_time), style::key_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("End:   ", style::muted_style()),
+                        Span::styled(Subtitle::format_time(sub.end_time), style::key_style()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("Pos:   ", style::muted_style()),
+                        Span::styled(
+                            sub.position.map_or("default", |p| p.as_str()),
+                            style::normal_style(),
+                        ),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![Span::styled(
+                        if highlighted.is_some() {
+                            "Text (low-confidence words highlighted):"
+                        } else {
+                            "Text:"
+                        },
+                        style::muted_style(),
+                    )]),
+                ],
+                highlighted.unwrap_or_else(|| text_lines(&sub.text, false)),
+                Vec::new(),
+            )
         };
 
-        let paragraph = Paragraph::new(content);
-        frame.render_widget(paragraph, inner);
+        let chunks = Layout::vertical([
+            Constraint::Length(header.len() as u16),
+            Constraint::Min(1),
+            Constraint::Length(footer.len() as u16),
+        ])
+        .split(inner);
+
+        // Long cues wrap to the panel width and scroll (PgUp/PgDn) when taller
+        // than it. While editing, the view follows the cursor at the end.
+        let text = Paragraph::new(text).wrap(Wrap { trim: false });
+        let overflow = text
+            .line_count(chunks[1].width)
+            .saturating_sub(chunks[1].height as usize) as u16;
+        app.text_overflow.set(overflow);
+        let scroll = if app.editing_subtitle {
+            overflow
+        } else {
+            app.text_scroll.min(overflow)
+        };
+
+        frame.render_widget(Paragraph::new(header), chunks[0]);
+        frame.render_widget(text.scroll((scroll, 0)), chunks[1]);
+        frame.render_widget(Paragraph::new(footer), chunks[2]);
+
+        if !app.editing_subtitle && overflow > 0 {
+            let mut scrollbar_state =
+                ScrollbarState::new(overflow as usize + 1).position(scroll as usize);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight),
+                chunks[1],
+                &mut scrollbar_state,
+            );
+        }
     } else {
         let empty = Paragraph::new(vec![
             Line::from(""),
             Line::from(vec![Span::styled(
-                "No subtitles yet.",
+                if app.no_speech {
+                    "No speech was detected in the audio."
+                } else {
+                    "No subtitles yet."
+                },
                 style::muted_style(),
             )]),
             Line::from(""),
             Line::from(vec![
                 Span::styled("Press ", style::muted_style()),
                 Span::styled("a", style::key_style()),
-                Span::styled(" to add one.", style::muted_style()),
+                Span::styled(
+                    if app.no_speech {
+                        " to caption it by hand."
+                    } else {
+                        " to add one."
+                    },
+                    style::muted_style(),
+                ),
             ]),
         ]);
         frame.render_widget(empty, inner);
@@ -229,16 +922,44 @@ fn draw_edit_panel(frame: &mut Frame, app: &App, area: Rect) {
 
 fn draw_help(frame: &mut Frame, app: &App, area: Rect) {
     // Pre-format strings to avoid borrow issues
-    let overlay_height_str = format!("H:{}px", app.overlay_height);
+    // Limits and the resulting band are only known once the video is probed
+    let limits = app.video_size.zip(app.overlay_offset_limits());
+    let overlay_height_str = match limits {
+        Some(((_, height), _)) => format!("H:{}px (max {})", app.overlay_height, height),
+        None => format!("H:{}px", app.overlay_height),
+    };
     let overlay_width_str = format!(
-        "W:{}",
+        "W:{}{}",
         app.overlay_width
             .map(|w| format!("{}px", w))
-            .unwrap_or_else(|| "auto".to_string())
+            .unwrap_or_else(|| "auto".to_string()),
+        limits
+            .map(|((width, _), _)| format!(" (max {})", width))
+            .unwrap_or_default()
     );
-    let overlay_x_str = format!("X:{}px", app.overlay_x_offset);
-    let overlay_y_str = format!("Y:{}px", app.overlay_y_offset);
+    let overlay_x_str = match limits {
+        Some((_, ((min, max), _))) => {
+            format!("X:{}px ({}..{})", app.overlay_x_offset, min, max)
+        }
+        None => format!("X:{}px", app.overlay_x_offset),
+    };
+    let overlay_y_str = match limits {
+        Some((_, (_, (min, max)))) => {
+            format!("Y:{}px ({}..{})", app.overlay_y_offset, min, max)
+        }
+        None => format!("Y:{}px", app.overlay_y_offset),
+    };
+    let overlay_band_str = app
+        .overlay_band()
+        .map(|band| {
+            format!(
+                " │ band {}x{} at {},{}",
+                band.width, band.height, band.x, band.y
+            )
+        })
+        .unwrap_or_default();
 
+    let keys = &app.keymap;
     let help_text = if app.editing_subtitle {
         vec![
             Line::from(vec![Span::styled(
@@ -249,6 +970,18 @@ fn draw_help(frame: &mut Frame, app: &App, area: Rect) {
                 Span::styled("  Type to edit  │  ", style::muted_style()),
                 Span::styled("Enter ", style::key_style()),
                 Span::styled("save  │  ", style::muted_style()),
+                Span::styled(
+                    if app.inline_edit { "↑/↓ " } else { "" },
+                    style::key_style(),
+                ),
+                Span::styled(
+                    if app.inline_edit {
+                        "save and edit prev/next  │  "
+                    } else {
+                        ""
+                    },
+                    style::muted_style(),
+                ),
                 Span::styled("Esc ", style::key_style()),
                 Span::styled("cancel", style::muted_style()),
             ]),
@@ -260,27 +993,165 @@ fn draw_help(frame: &mut Frame, app: &App, area: Rect) {
                 style::muted_style(),
             )]),
             Line::from(vec![
-                Span::styled("  ↑/k ↓/j ", style::key_style()),
+                Span::styled(
+                    format!("  {} {} ", keys.label(Action::Up), keys.label(Action::Down)),
+                    style::key_style(),
+                ),
                 Span::styled("navigate  ", style::muted_style()),
-                Span::styled("e/Enter ", style::key_style()),
+                Span::styled(format!("{} ", keys.label(Action::Edit)), style::key_style()),
                 Span::styled("edit  ", style::muted_style()),
-                Span::styled("a ", style::key_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::InlineEdit)),
+                    style::key_style(),
+                ),
+                Span::styled(
+                    "inline  ",
+                    if app.inline_edit {
+                        style::success_style()
+                    } else {
+                        style::muted_style()
+                    },
+                ),
+                Span::styled(
+                    format!("{} ", keys.label(Action::AddCue)),
+                    style::key_style(),
+                ),
                 Span::styled("add  ", style::muted_style()),
-                Span::styled("d ", style::key_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::Delete)),
+                    style::key_style(),
+                ),
                 Span::styled("delete  ", style::muted_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::Regenerate)),
+                    style::key_style(),
+                ),
+                Span::styled("regenerate  ", style::muted_style()),
+                Span::styled(
+                    format!(
+                        "{}/{} ",
+                        keys.label(Action::NextIssue),
+                        keys.label(Action::PrevIssue)
+                    ),
+                    style::key_style(),
+                ),
+                Span::styled("next/prev issue  ", style::muted_style()),
             ]),
             Line::from(vec![
-                Span::styled("  [ ] ", style::key_style()),
+                Span::styled(
+                    format!(
+                        "  {} {} ",
+                        keys.label(Action::StartEarlier),
+                        keys.label(Action::StartLater)
+                    ),
+                    style::key_style(),
+                ),
                 Span::styled("start time  ", style::muted_style()),
-                Span::styled("{ } ", style::key_style()),
+                Span::styled(
+                    format!(
+                        "{} {} ",
+                        keys.label(Action::EndEarlier),
+                        keys.label(Action::EndLater)
+                    ),
+                    style::key_style(),
+                ),
                 Span::styled("end time  ", style::muted_style()),
-                Span::styled("s ", style::key_style()),
+                Span::styled(
+                    format!(
+                        "{} {} ",
+                        keys.label(Action::MoveEarlier),
+                        keys.label(Action::MoveLater)
+                    ),
+                    style::key_style(),
+                ),
+                Span::styled("move  ", style::muted_style()),
+                Span::styled(
+                    format!("({})  ", app.config.nudge_steps().label()),
+                    style::muted_style(),
+                ),
+                Span::styled(
+                    format!("{} ", keys.label(Action::SetTimes)),
+                    style::key_style(),
+                ),
+                Span::styled("set times  ", style::muted_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::GoToTime)),
+                    style::key_style(),
+                ),
+                Span::styled("go to time  ", style::muted_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::Ripple)),
+                    style::key_style(),
+                ),
+                Span::styled(
+                    "ripple  ",
+                    if app.ripple {
+                        style::success_style()
+                    } else {
+                        style::muted_style()
+                    },
+                ),
+                Span::styled(format!("{} ", keys.label(Action::Lock)), style::key_style()),
+                Span::styled("lock  ", style::muted_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::TogglePosition)),
+                    style::key_style(),
+                ),
+                Span::styled("top/bottom  ", style::muted_style()),
+                Span::styled(format!("{} ", keys.label(Action::Case)), style::key_style()),
+                Span::styled("case  ", style::muted_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::FixSpacing)),
+                    style::key_style(),
+                ),
+                Span::styled("spacing  ", style::muted_style()),
+                Span::styled(
+                    format!(
+                        "{}/{} ",
+                        keys.label(Action::Replace),
+                        keys.label(Action::Undo)
+                    ),
+                    style::key_style(),
+                ),
+                Span::styled("replace/undo  ", style::muted_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::Resegment)),
+                    style::key_style(),
+                ),
+                Span::styled("re-segment  ", style::muted_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::Confidence)),
+                    style::key_style(),
+                ),
+                Span::styled("confidence  ", style::muted_style()),
+                Span::styled(format!("{} ", keys.label(Action::Save)), style::key_style()),
                 Span::styled("save  ", style::muted_style()),
-                Span::styled("b ", style::key_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::OpenTab)),
+                    style::key_style(),
+                ),
+                Span::styled("open tab  ", style::muted_style()),
+                Span::styled(format!("{} ", keys.label(Action::Burn)), style::key_style()),
                 Span::styled("burn  ", style::muted_style()),
-                Span::styled("o ", style::key_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::TestBurn)),
+                    style::key_style(),
+                ),
+                Span::styled("test burn  ", style::muted_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::SelectRange)),
+                    style::key_style(),
+                ),
+                Span::styled("select range  ", style::muted_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::ExportOverlay)),
+                    style::key_style(),
+                ),
                 Span::styled("overlay  ", style::muted_style()),
-                Span::styled("p ", style::key_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::Preview)),
+                    style::key_style(),
+                ),
                 Span::styled(
                     if app.preview_active {
                         "stop preview  "
@@ -293,20 +1164,51 @@ fn draw_help(frame: &mut Frame, app: &App, area: Rect) {
                         style::muted_style()
                     },
                 ),
-                Span::styled("q ", style::key_style()),
+                Span::styled(format!("{} ", keys.label(Action::Quit)), style::key_style()),
                 Span::styled("quit", style::muted_style()),
             ]),
             Line::from(vec![
                 Span::styled("  Overlay: ", style::muted_style()),
-                Span::styled("h/H ", style::key_style()),
+                Span::styled(
+                    format!(
+                        "{}/{} ",
+                        keys.label(Action::OverlayShorter),
+                        keys.label(Action::OverlayTaller)
+                    ),
+                    style::key_style(),
+                ),
                 Span::styled("height  ", style::muted_style()),
-                Span::styled("w/W ", style::key_style()),
+                Span::styled(
+                    format!(
+                        "{}/{} ",
+                        keys.label(Action::OverlayNarrower),
+                        keys.label(Action::OverlayWider)
+                    ),
+                    style::key_style(),
+                ),
                 Span::styled("width  ", style::muted_style()),
-                Span::styled("x/X ", style::key_style()),
+                Span::styled(
+                    format!(
+                        "{}/{} ",
+                        keys.label(Action::OverlayLeft),
+                        keys.label(Action::OverlayRight)
+                    ),
+                    style::key_style(),
+                ),
                 Span::styled("X pos  ", style::muted_style()),
-                Span::styled("y/Y ", style::key_style()),
+                Span::styled(
+                    format!(
+                        "{}/{} ",
+                        keys.label(Action::OverlayUp),
+                        keys.label(Action::OverlayDown)
+                    ),
+                    style::key_style(),
+                ),
                 Span::styled("Y pos  ", style::muted_style()),
-                Span::styled("0 ", style::key_style()),
+                Span::styled(
+                    format!("{} ", keys.label(Action::ResetOverlay)),
+                    style::key_style(),
+                ),
                 Span::styled("reset", style::muted_style()),
             ]),
             Line::from(vec![
@@ -318,6 +1220,7 @@ fn draw_help(frame: &mut Frame, app: &App, area: Rect) {
                 Span::styled(&overlay_x_str, style::normal_style()),
                 Span::styled(" │ ", style::muted_style()),
                 Span::styled(&overlay_y_str, style::normal_style()),
+                Span::styled(&overlay_band_str, style::muted_style()),
             ]),
         ];
 
@@ -326,9 +1229,32 @@ fn draw_help(frame: &mut Frame, app: &App, area: Rect) {
             lines.push(Line::from(vec![
                 Span::styled("  ▶ ", style::success_style()),
                 Span::styled(
-                    "LIVE PREVIEW ACTIVE - Changes update in real-time",
+                    if app.video_path.is_some() {
+                        "LIVE PREVIEW ACTIVE - Changes update in real-time  "
+                    } else {
+                        "PLAYING AUDIO  "
+                    },
                     style::success_style(),
                 ),
+                Span::styled(
+                    format!(
+                        "{} {} {} ",
+                        keys.label(Action::PlayPause),
+                        keys.label(Action::SeekToCue),
+                        keys.label(Action::PlayCue)
+                    ),
+                    style::key_style(),
+                ),
+                Span::styled("pause, play from cue, play cue  ", style::muted_style()),
+                Span::styled(
+                    format!(
+                        "{}/{} ",
+                        keys.label(Action::MarkStart),
+                        keys.label(Action::MarkEnd)
+                    ),
+                    style::key_style(),
+                ),
+                Span::styled("set start/end from playback", style::muted_style()),
             ]));
         }
 
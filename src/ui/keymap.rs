@@ -0,0 +1,460 @@
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// A command in the subtitle editor's cue list. The `[keys]` section of the
+/// config file binds these by their snake_case name (`add_cue = "i"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Back,
+    Up,
+    Down,
+    Edit,
+    InlineEdit,
+    AddCue,
+    Delete,
+    StartEarlier,
+    StartLater,
+    EndEarlier,
+    EndLater,
+    MoveEarlier,
+    MoveLater,
+    SetTimes,
+    MarkStart,
+    MarkEnd,
+    Ripple,
+    Lock,
+    Case,
+    FixSpacing,
+    TogglePosition,
+    Confidence,
+    SelectRange,
+    ScrollUp,
+    ScrollDown,
+    NextIssue,
+    PrevIssue,
+    Save,
+    Burn,
+    TestBurn,
+    ExportOverlay,
+    Preview,
+    PlayPause,
+    SeekToCue,
+    PlayCue,
+    GoToTime,
+    Regenerate,
+    OpenTab,
+    Replace,
+    Undo,
+    Resegment,
+    OverlayShorter,
+    OverlayTaller,
+    OverlayNarrower,
+    OverlayWider,
+    OverlayLeft,
+    OverlayRight,
+    OverlayUp,
+    OverlayDown,
+    ResetOverlay,
+}
+
+/// Keys given for an action in the config: `delete = "x"` or `delete = ["x", "Delete"]`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeyBinding {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// The built-in bindings, with each action's config name
+const DEFAULT_BINDINGS: &[(Action, &str, &[KeyCode])] = &[
+    (Action::Quit, "quit", &[KeyCode::Char('q')]),
+    (Action::Back, "back", &[KeyCode::Esc]),
+    (Action::Up, "up", &[KeyCode::Up, KeyCode::Char('k')]),
+    (Action::Down, "down", &[KeyCode::Down, KeyCode::Char('j')]),
+    (Action::Edit, "edit", &[KeyCode::Char('e'), KeyCode::Enter]),
+    (Action::InlineEdit, "inline_edit", &[KeyCode::Char('i')]),
+    (Action::AddCue, "add_cue", &[KeyCode::Char('a')]),
+    (Action::Delete, "delete", &[KeyCode::Char('d')]),
+    (Action::StartEarlier, "start_earlier", &[KeyCode::Char('[')]),
+    (Action::StartLater, "start_later", &[KeyCode::Char(']')]),
+    (Action::EndEarlier, "end_earlier", &[KeyCode::Char('-')]),
+    (Action::EndLater, "end_later", &[KeyCode::Char('=')]),
+    (Action::MoveEarlier, "move_earlier", &[KeyCode::Char(',')]),
+    (Action::MoveLater, "move_later", &[KeyCode::Char('.')]),
+    (Action::SetTimes, "set_times", &[KeyCode::Char('T')]),
+    (Action::MarkStart, "mark_start", &[KeyCode::Char('m')]),
+    (Action::MarkEnd, "mark_end", &[KeyCode::Char('M')]),
+    (Action::Ripple, "ripple", &[KeyCode::Char('r')]),
+    (Action::Lock, "lock", &[KeyCode::Char('L')]),
+    (Action::Case, "case", &[KeyCode::Char('c')]),
+    (Action::FixSpacing, "fix_spacing", &[KeyCode::Char('n')]),
+    (
+        Action::TogglePosition,
+        "toggle_position",
+        &[KeyCode::Char('v')],
+    ),
+    (Action::Confidence, "confidence", &[KeyCode::Char('C')]),
+    (Action::SelectRange, "select_range", &[KeyCode::Char('V')]),
+    (Action::ScrollUp, "scroll_up", &[KeyCode::PageUp]),
+    (Action::ScrollDown, "scroll_down", &[KeyCode::PageDown]),
+    (Action::NextIssue, "next_issue", &[KeyCode::Tab]),
+    (Action::PrevIssue, "prev_issue", &[KeyCode::BackTab]),
+    (Action::Save, "save", &[KeyCode::Char('s')]),
+    (Action::Burn, "burn", &[KeyCode::Char('b')]),
+    (Action::TestBurn, "test_burn", &[KeyCode::Char('t')]),
+    (
+        Action::ExportOverlay,
+        "export_overlay",
+        &[KeyCode::Char('o')],
+    ),
+    (Action::Preview, "preview", &[KeyCode::Char('p')]),
+    (Action::PlayPause, "play_pause", &[KeyCode::Char(' ')]),
+    (Action::SeekToCue, "seek_to_cue", &[KeyCode::Char('g')]),
+    (Action::PlayCue, "play_cue", &[KeyCode::Char('P')]),
+    (Action::GoToTime, "go_to_time", &[KeyCode::Char('G')]),
+    (Action::Regenerate, "regenerate", &[KeyCode::Char('R')]),
+    (Action::OpenTab, "open_tab", &[KeyCode::Char('O')]),
+    (Action::Replace, "replace", &[KeyCode::Char('f')]),
+    (Action::Undo, "undo", &[KeyCode::Char('u')]),
+    (Action::Resegment, "resegment", &[KeyCode::Char('S')]),
+    (
+        Action::OverlayShorter,
+        "overlay_shorter",
+        &[KeyCode::Char('h')],
+    ),
+    (
+        Action::OverlayTaller,
+        "overlay_taller",
+        &[KeyCode::Char('H')],
+    ),
+    (
+        Action::OverlayNarrower,
+        "overlay_narrower",
+        &[KeyCode::Char('w')],
+    ),
+    (Action::OverlayWider, "overlay_wider", &[KeyCode::Char('W')]),
+    (Action::OverlayLeft, "overlay_left", &[KeyCode::Char('x')]),
+    (Action::OverlayRight, "overlay_right", &[KeyCode::Char('X')]),
+    (Action::OverlayUp, "overlay_up", &[KeyCode::Char('y')]),
+    (Action::OverlayDown, "overlay_down", &[KeyCode::Char('Y')]),
+    (Action::ResetOverlay, "reset_overlay", &[KeyCode::Char('0')]),
+];
+
+impl Action {
+    /// The action's name in the `[keys]` config section
+    pub fn name(self) -> &'static str {
+        DEFAULT_BINDINGS
+            .iter()
+            .find(|(action, _, _)| *action == self)
+            .map_or("", |(_, name, _)| name)
+    }
+
+    /// Whether the action shifts a cue's times, by a larger step with Shift
+    fn nudges(self) -> bool {
+        matches!(
+            self,
+            Action::StartEarlier
+                | Action::StartLater
+                | Action::EndEarlier
+                | Action::EndLater
+                | Action::MoveEarlier
+                | Action::MoveLater
+        )
+    }
+}
+
+/// The key Shift was held on to type `c`, on a US layout: `[` for `{`
+fn unshifted(c: char) -> Option<char> {
+    const PAIRS: &[(char, char)] = &[
+        ('{', '['),
+        ('}', ']'),
+        ('_', '-'),
+        ('+', '='),
+        ('<', ','),
+        ('>', '.'),
+        (':', ';'),
+        ('"', '\''),
+        ('?', '/'),
+        ('|', '\\'),
+        ('~', '`'),
+    ];
+    if c.is_ascii_uppercase() {
+        return Some(c.to_ascii_lowercase());
+    }
+    PAIRS
+        .iter()
+        .find(|(shifted, _)| *shifted == c)
+        .map(|(_, key)| *key)
+}
+
+/// How far the nudge and move actions shift a cue, in milliseconds: `small`
+/// normally, `large` with Shift held
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NudgeSteps {
+    pub small: u64,
+    pub large: u64,
+}
+
+impl NudgeSteps {
+    /// The step for a key press, `large` or not as `KeyMap::action_for` says
+    pub fn step(&self, large: bool) -> u64 {
+        if large {
+            self.large
+        } else {
+            self.small
+        }
+    }
+
+    /// Both steps as shown in the help bar, e.g. "100ms, ⇧ 1000ms"
+    pub fn label(&self) -> String {
+        format!("{}ms, ⇧ {}ms", self.small, self.large)
+    }
+}
+
+/// Which keys trigger which editor actions
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    keys: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            keys: DEFAULT_BINDINGS
+                .iter()
+                .map(|(action, _, keys)| (*action, keys.to_vec()))
+                .collect(),
+        }
+    }
+}
+
+impl KeyMap {
+    /// The default bindings with `overrides` applied. A rebound action loses
+    /// its default keys, and a key given to one action is taken away from
+    /// whichever action had it by default.
+    pub fn with_overrides(overrides: &HashMap<Action, KeyBinding>) -> Result<Self> {
+        let mut map = Self::default();
+        let mut taken: HashMap<KeyCode, Action> = HashMap::new();
+        for (&action, binding) in overrides {
+            let names = match binding {
+                KeyBinding::One(name) => std::slice::from_ref(name),
+                KeyBinding::Many(names) => names.as_slice(),
+            };
+            let mut keys = Vec::new();
+            for name in names {
+                let key = parse_key(name)
+                    .with_context(|| format!("Invalid key for {}", action.name()))?;
+                if let Some(other) = taken.insert(key, action) {
+                    if other != action {
+                        anyhow::bail!(
+                            "Key {} is bound to both {} and {}",
+                            key_name(key),
+                            other.name(),
+                            action.name()
+                        );
+                    }
+                }
+                keys.push(key);
+            }
+            map.keys.insert(action, keys);
+        }
+
+        for (action, keys) in map.keys.iter_mut() {
+            if !overrides.contains_key(action) {
+                keys.retain(|key| !taken.contains_key(key));
+            }
+        }
+        Ok(map)
+    }
+
+    /// The action bound to `key`, if any
+    pub fn action(&self, key: KeyCode) -> Option<Action> {
+        self.keys
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| *action)
+    }
+
+    /// The action for a key press, and whether it takes the large nudge step.
+    /// Terminals type Shift+`[` as `{`, flagging Shift on Windows but not
+    /// elsewhere, so Shift on a character key never counts by itself: an
+    /// unbound character typed with Shift (`{`) runs the nudge action bound
+    /// to the key under it (`[`) with the large step. Named keys report
+    /// Shift everywhere, so Shift+`Left` is the large step of `Left`.
+    pub fn action_for(&self, event: KeyEvent) -> Option<(Action, bool)> {
+        if let Some(action) = self.action(event.code) {
+            let large = action.nudges()
+                && event.modifiers.contains(KeyModifiers::SHIFT)
+                && !matches!(event.code, KeyCode::Char(_));
+            return Some((action, large));
+        }
+        let KeyCode::Char(c) = event.code else {
+            return None;
+        };
+        unshifted(c)
+            .and_then(|key| self.action(KeyCode::Char(key)))
+            .filter(|action| action.nudges())
+            .map(|action| (action, true))
+    }
+
+    /// Keys bound to `action` as shown in the help bar, e.g. "e/Enter"
+    pub fn label(&self, action: Action) -> String {
+        match self.keys.get(&action) {
+            Some(keys) if !keys.is_empty() => keys
+                .iter()
+                .map(|&key| key_name(key))
+                .collect::<Vec<_>>()
+                .join("/"),
+            _ => "-".to_string(),
+        }
+    }
+}
+
+/// Parse a key as written in the config: a single character (case matters),
+/// or a name such as `Enter`, `Esc`, `Tab`, `Shift-Tab`, `Space`, `Delete`,
+/// `PageDown`, `Up` or `F5` (case doesn't)
+pub fn parse_key(name: &str) -> Result<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c));
+    }
+
+    let key = match name.to_ascii_lowercase().as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" | "shift-tab" => KeyCode::BackTab,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        other => match other.strip_prefix('f').map(str::parse::<u8>) {
+            Some(Ok(n @ 1..=12)) => KeyCode::F(n),
+            _ => anyhow::bail!("Unknown key '{}'", name),
+        },
+    };
+    Ok(key)
+}
+
+/// How a key is written in the help bar
+pub fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::BackTab => "⇧Tab".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::Delete => "Del".to_string(),
+        KeyCode::Insert => "Ins".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_replace_and_steal_keys() {
+        let defaults = KeyMap::default();
+        assert_eq!(defaults.action(KeyCode::Char('d')), Some(Action::Delete));
+        assert_eq!(defaults.label(Action::Up), "↑/k");
+
+        let overrides = HashMap::from([
+            (Action::Delete, KeyBinding::One("x".to_string())),
+            (
+                Action::OverlayLeft,
+                KeyBinding::Many(vec!["Delete".to_string(), "F2".to_string()]),
+            ),
+        ]);
+        let map = KeyMap::with_overrides(&overrides).unwrap();
+        assert_eq!(map.action(KeyCode::Char('x')), Some(Action::Delete));
+        assert_eq!(map.action(KeyCode::Char('d')), None);
+        assert_eq!(map.action(KeyCode::F(2)), Some(Action::OverlayLeft));
+        assert_eq!(map.label(Action::OverlayLeft), "Del/F2");
+
+        let clash = HashMap::from([
+            (Action::Save, KeyBinding::One("w".to_string())),
+            (Action::Burn, KeyBinding::One("w".to_string())),
+        ]);
+        assert!(KeyMap::with_overrides(&clash).is_err());
+        assert!(parse_key("Hyper").is_err());
+        assert_eq!(parse_key("shift-tab").unwrap(), KeyCode::BackTab);
+    }
+
+    #[test]
+    fn test_shifted_keys_take_the_large_step() {
+        let map = KeyMap::default();
+        let press = |code, modifiers| map.action_for(KeyEvent::new(code, modifiers));
+        let (none, shift) = (KeyModifiers::NONE, KeyModifiers::SHIFT);
+
+        // Shift+[ arrives as `{`: without the flag on Unix, with it on Windows
+        assert_eq!(
+            press(KeyCode::Char('['), none),
+            Some((Action::StartEarlier, false))
+        );
+        assert_eq!(
+            press(KeyCode::Char('{'), none),
+            Some((Action::StartEarlier, true))
+        );
+        assert_eq!(
+            press(KeyCode::Char('{'), shift),
+            Some((Action::StartEarlier, true))
+        );
+        assert_eq!(
+            press(KeyCode::Char('='), none),
+            Some((Action::EndLater, false))
+        );
+        assert_eq!(
+            press(KeyCode::Char('+'), shift),
+            Some((Action::EndLater, true))
+        );
+        assert_eq!(
+            press(KeyCode::Char('<'), shift),
+            Some((Action::MoveEarlier, true))
+        );
+
+        // Shifted keys bound to their own action, or not to a nudge, stay as they are
+        assert_eq!(
+            press(KeyCode::Char('H'), shift),
+            Some((Action::OverlayTaller, false))
+        );
+        assert_eq!(press(KeyCode::Char('Q'), shift), None);
+
+        // Named keys report Shift reliably
+        let overrides =
+            HashMap::from([(Action::StartEarlier, KeyBinding::One("Left".to_string()))]);
+        let map = KeyMap::with_overrides(&overrides).unwrap();
+        assert_eq!(
+            map.action_for(KeyEvent::new(KeyCode::Left, shift)),
+            Some((Action::StartEarlier, true))
+        );
+        assert_eq!(
+            map.action_for(KeyEvent::new(KeyCode::Char('{'), shift)),
+            None
+        );
+
+        let steps = NudgeSteps {
+            small: 100,
+            large: 1000,
+        };
+        assert_eq!((steps.step(false), steps.step(true)), (100, 1000));
+    }
+}
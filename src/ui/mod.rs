@@ -2,5 +2,7 @@ pub mod done;
 pub mod editor;
 pub mod file_picker;
 pub mod home;
+pub mod keymap;
 pub mod progress;
+pub mod spinner;
 pub mod style;
@@ -5,8 +5,9 @@ use ratatui::{
     Frame,
 };
 
-use super::style;
+use super::{spinner, style};
 use crate::app::App;
+use crate::utils::eta::format_duration;
 
 pub fn draw(frame: &mut Frame, app: &App, title: &str) {
     let area = frame.area();
@@ -16,7 +17,7 @@ pub fn draw(frame: &mut Frame, app: &App, title: &str) {
 
     let chunks = Layout::vertical([
         Constraint::Length(3), // Title
-        Constraint::Length(2), // Spacer
+        Constraint::Length(2), // Step and ETA
         Constraint::Length(3), // Progress bar
         Constraint::Length(2), // Spacer
         Constraint::Length(2), // Message
@@ -34,6 +35,30 @@ pub fn draw(frame: &mut Frame, app: &App, title: &str) {
     .alignment(Alignment::Center);
     frame.render_widget(title_widget, chunks[0]);
 
+    // Step count, elapsed time and estimated time remaining
+    if let Some(stage) = &app.stage {
+        let eta = match stage.eta() {
+            Some(eta) => format_duration(eta),
+            None => "--:--".to_string(),
+        };
+        let stage_widget = Paragraph::new(vec![Line::from(vec![
+            Span::styled(
+                format!("Step {}/{}", stage.step, stage.total),
+                style::key_style(),
+            ),
+            Span::styled(
+                format!(
+                    " — elapsed {} — ETA {}",
+                    format_duration(stage.elapsed()),
+                    eta
+                ),
+                style::muted_style(),
+            ),
+        ])])
+        .alignment(Alignment::Center);
+        frame.render_widget(stage_widget, chunks[1]);
+    }
+
     // Progress bar
     let progress_percent = (app.progress * 100.0) as u16;
     let gauge = Gauge::default()
@@ -55,16 +80,6 @@ pub fn draw(frame: &mut Frame, app: &App, title: &str) {
     .alignment(Alignment::Center);
     frame.render_widget(message, chunks[4]);
 
-    // Spinner animation based on progress
-    let spinner_chars = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
-    let spinner_idx = ((std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_millis()
-        / 100) as usize)
-        % spinner_chars.len();
-    let spinner = spinner_chars[spinner_idx];
-
     // If we have an error, show it
     if let Some(error) = &app.error_message {
         let error_widget = Paragraph::new(vec![Line::from(vec![
@@ -75,7 +90,7 @@ pub fn draw(frame: &mut Frame, app: &App, title: &str) {
         frame.render_widget(error_widget, chunks[5]);
     } else {
         let spinner_widget = Paragraph::new(vec![Line::from(vec![
-            Span::styled(spinner, style::key_style()),
+            Span::styled(spinner::frame(), style::key_style()),
             Span::styled(" Processing... ", style::muted_style()),
         ])])
         .alignment(Alignment::Center);
@@ -86,14 +101,16 @@ pub fn draw(frame: &mut Frame, app: &App, title: &str) {
     let help = Paragraph::new(vec![Line::from(vec![
         Span::styled("Press ", style::muted_style()),
         Span::styled("Esc", style::key_style()),
-        Span::styled(" to cancel", style::muted_style()),
+        Span::styled(" to cancel, ", style::muted_style()),
+        Span::styled("q", style::key_style()),
+        Span::styled(" to cancel and quit", style::muted_style()),
     ])])
     .alignment(Alignment::Center);
     frame.render_widget(help, chunks[6]);
 }
 
 /// Create a centered rect
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::vertical([
         Constraint::Percentage((100 - percent_y) / 2),
         Constraint::Percentage(percent_y),
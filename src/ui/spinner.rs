@@ -0,0 +1,14 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Braille frames, one step per 100ms
+const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Spinner frame for the current time. The UI redraws at least every 100ms
+/// while polling for input, so this animates without extra state.
+pub fn frame() -> &'static str {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    FRAMES[(millis / 100) as usize % FRAMES.len()]
+}
@@ -30,6 +30,19 @@ pub fn highlight_style() -> Style {
         .add_modifier(Modifier::BOLD)
 }
 
+/// The list row being edited inline
+pub fn editing_style() -> Style {
+    Style::default()
+        .fg(BG_PRIMARY)
+        .bg(SUCCESS)
+        .add_modifier(Modifier::BOLD)
+}
+
+/// Rows inside a range selection
+pub fn selection_style() -> Style {
+    Style::default().fg(TEXT_PRIMARY).bg(BG_SECONDARY)
+}
+
 pub fn normal_style() -> Style {
     Style::default().fg(TEXT_PRIMARY)
 }
@@ -48,6 +61,10 @@ pub fn success_style() -> Style {
     Style::default().fg(SUCCESS)
 }
 
+pub fn warning_style() -> Style {
+    Style::default().fg(WARNING)
+}
+
 pub fn error_style() -> Style {
     Style::default().fg(ERROR)
 }
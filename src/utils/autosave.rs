@@ -0,0 +1,70 @@
+use std::path::{Path, PathBuf};
+
+/// Directory under the temp base that holds autosaves. Unlike a
+/// `TempWorkspace` it outlives the run, so a crashed session can be recovered.
+const AUTOSAVE_DIR: &str = "auto-subs-tui-autosave";
+
+/// Where unsaved edits of `srt_path` are autosaved. The name carries a hash of
+/// the full path so same-named files in different folders don't collide.
+pub fn autosave_path(srt_path: &Path, temp_dir: Option<&Path>) -> PathBuf {
+    let base = temp_dir
+        .map(Path::to_path_buf)
+        .unwrap_or_else(std::env::temp_dir);
+    let absolute = std::path::absolute(srt_path).unwrap_or_else(|_| srt_path.to_path_buf());
+    let stem = srt_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "subtitles".to_string());
+
+    base.join(AUTOSAVE_DIR).join(format!(
+        "{}-{:016x}.autosave.srt",
+        stem,
+        fnv1a(absolute.to_string_lossy().as_bytes())
+    ))
+}
+
+/// An autosave for `srt_path` written after the file itself was last saved
+pub fn newer_autosave(srt_path: &Path, temp_dir: Option<&Path>) -> Option<PathBuf> {
+    let autosave = autosave_path(srt_path, temp_dir);
+    let autosaved = std::fs::metadata(&autosave)
+        .and_then(|m| m.modified())
+        .ok()?;
+    match std::fs::metadata(srt_path).and_then(|m| m.modified()) {
+        Ok(saved) if saved >= autosaved => None,
+        _ => Some(autosave),
+    }
+}
+
+/// Delete the autosave for `srt_path`, if any
+pub fn remove(srt_path: &Path, temp_dir: Option<&Path>) {
+    let _ = std::fs::remove_file(autosave_path(srt_path, temp_dir));
+}
+
+/// FNV-1a, used instead of `DefaultHasher` because the name must stay the
+/// same across builds for an autosave to be found again
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn autosave_path_is_per_file() {
+        let base = Path::new("/tmp/base");
+        let a = autosave_path(Path::new("/videos/a/movie.srt"), Some(base));
+        let b = autosave_path(Path::new("/videos/b/movie.srt"), Some(base));
+
+        assert_eq!(
+            a,
+            autosave_path(Path::new("/videos/a/movie.srt"), Some(base))
+        );
+        assert_ne!(a, b);
+        assert!(a.starts_with(base.join(AUTOSAVE_DIR)));
+        let name = a.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("movie-") && name.ends_with(".autosave.srt"));
+    }
+}
@@ -0,0 +1,128 @@
+use std::io::{self, BufRead, BufReader, Read};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How often a running process is checked for exit or cancellation
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Lets the UI stop a background job. The worker runs its external processes
+/// through `output`, which keeps the `Child` here so `cancel` can kill it;
+/// long in-process work (Whisper) polls `is_cancelled` instead.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle {
+    cancelled: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+}
+
+impl CancelHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop the job: kill and reap the process it is running, if any
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        if let Some(child) = self.child.lock().unwrap().as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Like `Command::output`, but the process can be killed through `cancel`.
+    /// Fails with `ErrorKind::Interrupted` once the job is cancelled.
+    pub fn output(&self, command: &mut Command) -> io::Result<Output> {
+        self.output_with_lines(command, |_| {})
+    }
+
+    /// Like `output`, also passing each line of stdout to `on_line` as the
+    /// process prints it (e.g. FFmpeg's `-progress pipe:1` reports)
+    pub fn output_with_lines(
+        &self,
+        command: &mut Command,
+        mut on_line: impl FnMut(&str),
+    ) -> io::Result<Output> {
+        if self.is_cancelled() {
+            return Err(cancelled());
+        }
+
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        // Drain both pipes so a chatty process never blocks on a full one
+        let (line_tx, lines) = mpsc::channel();
+        let stdout = drain_lines(child.stdout.take(), line_tx);
+        let stderr = drain(child.stderr.take());
+        *self.child.lock().unwrap() = Some(child);
+
+        let status = loop {
+            {
+                let mut slot = self.child.lock().unwrap();
+                let child = slot.as_mut().unwrap();
+                // Cancelled between spawning and storing the child
+                if self.is_cancelled() {
+                    let _ = child.kill();
+                }
+                if let Some(status) = child.try_wait()? {
+                    *slot = None;
+                    break status;
+                }
+            }
+            lines.try_iter().for_each(|line| on_line(&line));
+            std::thread::sleep(POLL_INTERVAL);
+        };
+
+        let stdout = stdout.join().unwrap_or_default();
+        lines.try_iter().for_each(|line| on_line(&line));
+        let stderr = stderr.join().unwrap_or_default();
+        if self.is_cancelled() {
+            return Err(cancelled());
+        }
+        Ok(Output {
+            status,
+            stdout,
+            stderr,
+        })
+    }
+}
+
+fn cancelled() -> io::Error {
+    io::Error::new(io::ErrorKind::Interrupted, "cancelled")
+}
+
+/// Like `drain`, also sending each line on as soon as it is read
+fn drain_lines(
+    pipe: Option<impl Read + Send + 'static>,
+    lines: Sender<String>,
+) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(pipe) = pipe {
+            let mut reader = BufReader::new(pipe);
+            let mut line = Vec::new();
+            while reader.read_until(b'\n', &mut line).unwrap_or(0) > 0 {
+                let _ = lines.send(String::from_utf8_lossy(&line).trim_end().to_string());
+                buffer.append(&mut line);
+            }
+        }
+        buffer
+    })
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
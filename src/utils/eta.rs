@@ -0,0 +1,88 @@
+use std::time::{Duration, Instant};
+
+/// Weight given to the newest rate sample in the moving average
+const SMOOTHING: f64 = 0.3;
+
+/// Tracks one stage of a multi-step operation and estimates its time remaining
+#[derive(Debug, Clone)]
+pub struct StageTimer {
+    /// 1-based index of the current stage
+    pub step: usize,
+    /// Number of stages in the operation
+    pub total: usize,
+    started: Instant,
+    last: Option<(Instant, f32)>,
+    /// Exponential moving average of progress per second
+    rate: Option<f64>,
+}
+
+impl StageTimer {
+    pub fn new(step: usize, total: usize) -> Self {
+        Self {
+            step,
+            total,
+            started: Instant::now(),
+            last: None,
+            rate: None,
+        }
+    }
+
+    /// Time since the stage started
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Record a progress value (0.0 - 1.0) reported for this stage
+    pub fn update(&mut self, progress: f32) {
+        self.update_at(progress, Instant::now());
+    }
+
+    fn update_at(&mut self, progress: f32, now: Instant) {
+        let (since, from) = self.last.unwrap_or((self.started, 0.0));
+        let dt = now.saturating_duration_since(since).as_secs_f64();
+        // Ignore bursts of messages and progress that jumps backwards
+        if dt > 0.0 && progress >= from {
+            let sample = (progress - from) as f64 / dt;
+            self.rate = Some(match self.rate {
+                Some(rate) => SMOOTHING * sample + (1.0 - SMOOTHING) * rate,
+                None => sample,
+            });
+            self.last = Some((now, progress));
+        }
+    }
+
+    /// Estimated time until the stage finishes, if progress is moving
+    pub fn eta(&self) -> Option<Duration> {
+        let rate = self.rate.filter(|r| *r > 0.0)?;
+        let (_, progress) = self.last?;
+        let remaining = (1.0 - progress as f64).max(0.0) / rate;
+        Some(Duration::from_secs_f64(remaining.min(359_999.0)))
+    }
+}
+
+/// Format a duration as MM:SS, or H:MM:SS when it exceeds an hour
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eta_from_steady_rate() {
+        let mut timer = StageTimer::new(2, 3);
+        let start = timer.started;
+        timer.update_at(0.25, start + Duration::from_secs(10));
+        timer.update_at(0.5, start + Duration::from_secs(20));
+
+        // 2.5% per second with half remaining leaves 20 seconds
+        assert_eq!(timer.eta().map(|d| d.as_secs()), Some(20));
+        assert_eq!(format_duration(Duration::from_secs(83)), "01:23");
+    }
+}
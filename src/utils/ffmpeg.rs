@@ -0,0 +1,116 @@
+use anyhow::Result;
+use std::path::Path;
+
+/// Anything smaller is a header at best: even a fraction of a second of
+/// 16kHz WAV or encoded video is larger
+const MIN_OUTPUT_BYTES: u64 = 1024;
+
+/// Lines of ffmpeg's stderr quoted when its output turns out to be empty
+const STDERR_TAIL_LINES: usize = 10;
+
+/// Log level for ffmpeg steps whose warnings are reported: only warnings and
+/// errors, each tagged with its level (`[warning] ...`)
+pub const LOG_ARGS: [&str; 2] = ["-loglevel", "level+warning"];
+
+/// Makes ffmpeg print `key=value` progress reports to stdout, and only those
+pub const PROGRESS_ARGS: [&str; 3] = ["-progress", "pipe:1", "-nostats"];
+
+/// Position reached, in milliseconds, from a line of a `PROGRESS_ARGS` report.
+/// Despite its name, `out_time_ms` counts microseconds, like `out_time_us`.
+pub fn progress_time_ms(line: &str) -> Option<u64> {
+    let (key, value) = line.trim().split_once('=')?;
+    match key {
+        "out_time_us" | "out_time_ms" => value.parse::<u64>().ok().map(|us| us / 1000),
+        _ => None,
+    }
+}
+
+/// Warnings in the stderr of an ffmpeg step run with `LOG_ARGS`, plus the
+/// messages fontconfig prints on its own (e.g. a font that wasn't found).
+/// Repeats, such as a warning logged for every frame, are listed once.
+pub fn warnings(stderr: &[u8]) -> Vec<String> {
+    let mut warnings: Vec<String> = Vec::new();
+    for line in String::from_utf8_lossy(stderr).lines() {
+        let line = line.trim();
+        if (line.contains("[warning]") || line.starts_with("Fontconfig"))
+            && !warnings.iter().any(|seen| seen == line)
+        {
+            warnings.push(line.to_string());
+        }
+    }
+    warnings
+}
+
+/// Fail if an ffmpeg step that exited successfully left `path` missing or
+/// next to empty, which happens when a filter drops every frame. The end of
+/// ffmpeg's `stderr` is included since it usually says why.
+pub fn check_output(path: &Path, step: &str, stderr: &[u8]) -> Result<()> {
+    let size = std::fs::metadata(path).map(|m| m.len()).ok();
+    if size.is_some_and(|size| size >= MIN_OUTPUT_BYTES) {
+        return Ok(());
+    }
+
+    let problem = match size {
+        Some(size) => format!("wrote only {} bytes to {}", size, path.display()),
+        None => format!("did not create {}", path.display()),
+    };
+    let stderr = String::from_utf8_lossy(stderr);
+    let lines: Vec<&str> = stderr.lines().filter(|l| !l.trim().is_empty()).collect();
+    let tail = lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n");
+    anyhow::bail!(
+        "{}: FFmpeg reported success but {}\n{}",
+        step,
+        problem,
+        tail
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp::TempWorkspace;
+
+    #[test]
+    fn rejects_missing_and_tiny_outputs() {
+        let workspace = TempWorkspace::new(None).unwrap();
+        let missing = workspace.file("missing.mp4");
+        let error = check_output(&missing, "Burn", b"banner\nNothing was written\n")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("did not create") && error.ends_with("Nothing was written"));
+
+        let tiny = workspace.file("tiny.mp4");
+        std::fs::write(&tiny, [0u8; 10]).unwrap();
+        assert!(check_output(&tiny, "Burn", b"")
+            .unwrap_err()
+            .to_string()
+            .contains("only 10 bytes"));
+
+        let fine = workspace.file("fine.mp4");
+        std::fs::write(&fine, vec![0u8; 4096]).unwrap();
+        assert!(check_output(&fine, "Burn", b"").is_ok());
+    }
+
+    #[test]
+    fn parses_progress_times() {
+        assert_eq!(progress_time_ms("out_time_us=12345678"), Some(12_345));
+        assert_eq!(progress_time_ms("out_time_ms=2000000\n"), Some(2_000));
+        assert_eq!(progress_time_ms("out_time=00:00:02.000000"), None);
+        assert_eq!(progress_time_ms("out_time_us=N/A"), None);
+    }
+
+    #[test]
+    fn collects_warnings_once() {
+        let stderr = b"[Parsed_subtitles_1 @ 0x1] [warning] fontselect: failed to find Foo\n\
+            [info] frame=1\n\
+            Fontconfig error: Cannot load default config file\n\
+            [Parsed_subtitles_1 @ 0x1] [warning] fontselect: failed to find Foo\n";
+        assert_eq!(
+            warnings(stderr),
+            [
+                "[Parsed_subtitles_1 @ 0x1] [warning] fontselect: failed to find Foo",
+                "Fontconfig error: Cannot load default config file",
+            ]
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Run a user command on a file, e.g. a filter that rewrites the generated
+/// SRT in place. The command goes through the shell with the file path
+/// appended as its last argument (also available as `$1`). When not
+/// `interactive` it gets no stdin, so a command that prompts reads
+/// end-of-file instead of waiting for an answer.
+pub fn run_post_hook(command: &str, path: &Path, interactive: bool) -> Result<()> {
+    let mut command_line = shell_command(command, path);
+    if !interactive {
+        command_line.stdin(Stdio::null());
+    }
+    let status = command_line
+        .status()
+        .with_context(|| format!("Failed to run post-hook: {}", command))?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "Post-hook '{}' failed ({})",
+            command,
+            status
+                .code()
+                .map(|c| format!("exit code {}", c))
+                .unwrap_or_else(|| "killed by signal".to_string())
+        );
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str, path: &Path) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(format!("{} \"$1\"", command))
+        .arg("auto-subs-tui")
+        .arg(path);
+    cmd
+}
+
+#[cfg(not(unix))]
+fn shell_command(command: &str, path: &Path) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command).arg(path);
+    cmd
+}
@@ -1 +1,11 @@
-// Utility module - currently empty, reserved for future helpers
+pub mod autosave;
+pub mod cancel;
+pub mod eta;
+pub mod ffmpeg;
+pub mod hook;
+pub mod mpv;
+pub mod overwrite;
+pub mod remote;
+pub mod tail;
+pub mod temp;
+pub mod template;
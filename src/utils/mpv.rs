@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+
+/// Minimal client for mpv's JSON IPC socket (`--input-ipc-server`)
+pub struct MpvIpc {
+    #[cfg(unix)]
+    stream: std::os::unix::net::UnixStream,
+    /// Replies and events from mpv; kept so lines read ahead aren't lost
+    #[cfg(unix)]
+    reader: std::io::BufReader<std::os::unix::net::UnixStream>,
+    next_request_id: u64,
+}
+
+impl MpvIpc {
+    #[cfg(unix)]
+    pub fn connect(socket_path: &Path) -> Result<Self> {
+        let stream = std::os::unix::net::UnixStream::connect(socket_path)
+            .with_context(|| format!("Failed to connect to mpv at {}", socket_path.display()))?;
+        stream.set_read_timeout(Some(std::time::Duration::from_millis(500)))?;
+        let reader = std::io::BufReader::new(
+            stream
+                .try_clone()
+                .context("Failed to open the mpv IPC socket for reading")?,
+        );
+        Ok(Self {
+            stream,
+            reader,
+            next_request_id: 1,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn connect(_socket_path: &Path) -> Result<Self> {
+        anyhow::bail!("mpv IPC is only supported on Unix-like systems")
+    }
+
+    /// Read a property as mpv reports it (e.g. `12.5`, `"text"`)
+    pub fn get_property(&mut self, name: &str) -> Result<Value> {
+        self.request(&["get_property", name])?
+            .get_mut("data")
+            .map(Value::take)
+            .context("mpv reply had no data")
+    }
+
+    /// Current playback position in milliseconds
+    pub fn time_pos_ms(&mut self) -> Result<u64> {
+        let value = self.get_property("time-pos")?;
+        let seconds = value
+            .as_f64()
+            .with_context(|| format!("Unexpected time-pos from mpv: {}", value))?;
+        Ok((seconds.max(0.0) * 1000.0).round() as u64)
+    }
+
+    /// Jump to `position_ms` from the start of the file
+    pub fn seek_ms(&mut self, position_ms: u64) -> Result<()> {
+        let seconds = format!("{:.3}", position_ms as f64 / 1000.0);
+        self.request(&["seek", &seconds, "absolute"]).map(|_| ())
+    }
+
+    /// Set a property or option from its string form, e.g. `sub-margin-y` to `40`
+    pub fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        self.request(&["set", name, value]).map(|_| ())
+    }
+
+    /// Replace the video filter chain, e.g. with a `drawbox=...` filter
+    pub fn set_video_filters(&mut self, filters: &str) -> Result<()> {
+        self.request(&["vf", "set", filters]).map(|_| ())
+    }
+
+    /// Pause or resume playback
+    pub fn set_pause(&mut self, paused: bool) -> Result<()> {
+        self.set("pause", if paused { "yes" } else { "no" })
+    }
+
+    /// Pause playback, or resume it if paused
+    pub fn toggle_pause(&mut self) -> Result<()> {
+        self.request(&["cycle", "pause"]).map(|_| ())
+    }
+
+    /// Send a command and wait for its successful reply
+    #[cfg(unix)]
+    fn request(&mut self, args: &[&str]) -> Result<Value> {
+        use std::io::{BufRead, Write};
+
+        let request_id = self.next_request_id;
+        self.next_request_id += 1;
+
+        let mut request = json!({ "command": args, "request_id": request_id }).to_string();
+        request.push('\n');
+        self.stream
+            .write_all(request.as_bytes())
+            .context("Failed to send command to mpv")?;
+
+        // mpv interleaves event notifications with replies; skip until ours arrives
+        loop {
+            let mut line = String::new();
+            if self
+                .reader
+                .read_line(&mut line)
+                .context("No reply from mpv")?
+                == 0
+            {
+                anyhow::bail!("mpv closed the IPC connection");
+            }
+            if let Some(reply) = reply_to(&line, request_id)? {
+                return Ok(reply);
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn request(&mut self, _args: &[&str]) -> Result<Value> {
+        anyhow::bail!("mpv IPC is only supported on Unix-like systems")
+    }
+}
+
+/// IPC socket for this process's preview, in `dir` or the system temp
+/// directory. The PID keeps concurrent instances from sharing a socket.
+pub fn socket_path(dir: Option<&Path>) -> PathBuf {
+    dir.map(Path::to_path_buf)
+        .unwrap_or_else(std::env::temp_dir)
+        .join(format!("auto-subs-preview-{}.sock", std::process::id()))
+}
+
+/// Play `audio_path` from `start_ms` in mpv with no window, controlled only
+/// through the IPC socket. Terminal input and status output are off so mpv
+/// leaves the TUI alone; errors still go to the piped stderr.
+pub fn launch_audio(audio_path: &Path, socket_path: &Path, start_ms: u64) -> Result<Child> {
+    let mut ipc_arg = OsString::from("--input-ipc-server=");
+    ipc_arg.push(socket_path);
+    Command::new("mpv")
+        .arg(ipc_arg)
+        .arg("--no-video")
+        .arg("--input-terminal=no")
+        .arg("--really-quiet")
+        .arg("--keep-open=yes")
+        .arg(format!("--start={:.3}", start_ms as f64 / 1000.0))
+        .arg(audio_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to launch MPV. Please install mpv.")
+}
+
+/// Clear a leftover socket at `path` so mpv can bind it. A socket something
+/// still answers on is left alone rather than cut out from under its owner.
+pub fn claim_socket(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    #[cfg(unix)]
+    if std::os::unix::net::UnixStream::connect(path).is_ok() {
+        anyhow::bail!("IPC socket {} is in use by another process", path.display());
+    }
+    std::fs::remove_file(path)
+        .with_context(|| format!("Failed to remove stale IPC socket {}", path.display()))
+}
+
+/// The reply to request `request_id` in a line from mpv, `None` for events
+/// and other requests' replies, or an error when mpv rejected the command
+fn reply_to(line: &str, request_id: u64) -> Result<Option<Value>> {
+    let Ok(message) = serde_json::from_str::<Value>(line) else {
+        return Ok(None);
+    };
+    if message.get("request_id").and_then(Value::as_u64) != Some(request_id) {
+        return Ok(None);
+    }
+    match message.get("error").and_then(Value::as_str) {
+        Some("success") => Ok(Some(message)),
+        _ => anyhow::bail!("mpv rejected the command: {}", line.trim()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reply_to_matches_request_id() {
+        let reply = r#"{"data":"a \"quoted\", title","request_id":12,"error":"success"}"#;
+        assert_eq!(
+            reply_to(reply, 12).unwrap().unwrap()["data"],
+            "a \"quoted\", title"
+        );
+        assert!(reply_to(reply, 1).unwrap().is_none());
+        assert!(reply_to(r#"{"event":"pause"}"#, 12).unwrap().is_none());
+        assert!(reply_to(r#"{"request_id":3,"error":"property unavailable"}"#, 3).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::Result;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+/// What to do when an output file already exists
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverwritePolicy {
+    /// Ask on the terminal (refuse when there is no terminal to ask on)
+    Ask,
+    /// Overwrite without asking (`--yes`)
+    Always,
+    /// Never overwrite (`--no-overwrite`)
+    Never,
+}
+
+impl OverwritePolicy {
+    pub fn from_flags(yes: bool, no_overwrite: bool) -> Self {
+        if no_overwrite {
+            OverwritePolicy::Never
+        } else if yes {
+            OverwritePolicy::Always
+        } else {
+            OverwritePolicy::Ask
+        }
+    }
+
+    /// Make sure `path` may be written, asking the user if needed.
+    /// Fails with an explanation when the file must not be replaced.
+    pub fn check(&self, path: &Path) -> Result<()> {
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let allowed = match self {
+            OverwritePolicy::Always => true,
+            OverwritePolicy::Never => false,
+            OverwritePolicy::Ask => {
+                if !std::io::stdin().is_terminal() {
+                    false
+                } else {
+                    print!("⚠️  {} already exists. Overwrite? [y/N] ", path.display());
+                    std::io::stdout().flush()?;
+                    let mut answer = String::new();
+                    std::io::stdin().read_line(&mut answer)?;
+                    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+                }
+            }
+        };
+
+        if !allowed {
+            anyhow::bail!(
+                "Refusing to overwrite existing file: {} (use --yes to overwrite)",
+                path.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// First path that doesn't exist yet, appending `_1`, `_2`, ... to the file stem
+pub fn unique_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+
+    (1..)
+        .map(|n| path.with_file_name(format!("{}_{}{}", stem, n, extension)))
+        .find(|candidate| !candidate.exists())
+        .unwrap()
+}
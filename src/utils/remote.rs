@@ -0,0 +1,144 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Name used when a URL gives nothing better to name the download after
+const FALLBACK_NAME: &str = "remote.mp4";
+
+/// Whether an input argument is an http(s) URL rather than a local path
+pub fn is_url(input: &Path) -> bool {
+    input.to_str().is_some_and(|s| {
+        let s = s.to_ascii_lowercase();
+        s.starts_with("http://") || s.starts_with("https://")
+    })
+}
+
+/// Whether yt-dlp is installed and runs
+pub fn has_yt_dlp() -> bool {
+    Command::new("yt-dlp")
+        .arg("--version")
+        .stdin(Stdio::null())
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// File name for a URL's media, from the last part of its path
+/// (`https://host/talks/intro.mp4?t=1` gives `intro.mp4`)
+pub fn name_from_url(url: &str) -> PathBuf {
+    let path = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default();
+    match path
+        .split('/')
+        .skip(1)
+        .filter(|part| !part.is_empty())
+        .last()
+    {
+        Some(name) if Path::new(name).extension().is_some() => PathBuf::from(name),
+        Some(name) => PathBuf::from(format!("{}.mp4", name)),
+        None => PathBuf::from(FALLBACK_NAME),
+    }
+}
+
+/// Download `url` into `dir` and return the local file. yt-dlp is used when
+/// installed, so pages on video sites work too; otherwise FFmpeg fetches it,
+/// which only works for direct links to media files. `audio_only` keeps just
+/// the best audio stream, which is all transcription needs.
+pub fn download(url: &str, dir: &Path, audio_only: bool) -> Result<PathBuf> {
+    if has_yt_dlp() {
+        download_with_yt_dlp(url, dir, audio_only)
+    } else {
+        download_with_ffmpeg(url, dir)
+    }
+}
+
+fn download_with_yt_dlp(url: &str, dir: &Path, audio_only: bool) -> Result<PathBuf> {
+    let format = if audio_only {
+        "bestaudio/best"
+    } else {
+        // MP4 when the site offers it, so the burned copy stays MP4 as well
+        "bv*[ext=mp4]+ba[ext=m4a]/b[ext=mp4]/bv*+ba/b"
+    };
+    let output = Command::new("yt-dlp")
+        .args(["--no-playlist", "--no-progress", "--no-simulate"])
+        .args(["--print", "after_move:filepath"])
+        .args(["-f", format])
+        .arg("-P")
+        .arg(dir)
+        .args(["-o", "%(title).100B [%(id)s].%(ext)s"])
+        .arg(url)
+        .stdin(Stdio::null())
+        .output()
+        .context("Failed to run yt-dlp")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("yt-dlp could not download {}: {}", url, stderr.trim());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let path = stdout
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .filter(|path| path.exists())
+        .with_context(|| format!("yt-dlp did not report where it saved {}", url))?;
+    Ok(path)
+}
+
+fn download_with_ffmpeg(url: &str, dir: &Path) -> Result<PathBuf> {
+    let path = dir.join(name_from_url(url));
+    let output = Command::new("ffmpeg")
+        .args([
+            "-loglevel",
+            "error",
+            "-i",
+            url,
+            "-map",
+            "0",
+            "-c",
+            "copy",
+            "-y",
+        ])
+        .arg(&path)
+        .output()
+        .context("Failed to run FFmpeg")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "FFmpeg could not download {} (install yt-dlp for video site pages): {}",
+            url,
+            stderr.trim()
+        );
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_urls_and_names_them() {
+        assert!(is_url(Path::new("https://example.com/a.mp4")));
+        assert!(is_url(Path::new("HTTP://example.com")));
+        assert!(!is_url(Path::new("videos/https.mp4")));
+        assert!(!is_url(Path::new("-")));
+
+        assert_eq!(
+            name_from_url("https://host/talks/intro.mp4?t=1#x"),
+            PathBuf::from("intro.mp4")
+        );
+        assert_eq!(
+            name_from_url("https://host/watch/"),
+            PathBuf::from("watch.mp4")
+        );
+        assert_eq!(name_from_url("https://host"), PathBuf::from(FALLBACK_NAME));
+    }
+}
@@ -0,0 +1,38 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::sync::{Arc, Mutex};
+
+/// Keeps the last few lines written to a child process' output pipe. The pipe
+/// is drained on a background thread so the child never blocks on a full pipe.
+#[derive(Debug, Clone)]
+pub struct OutputTail {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl OutputTail {
+    /// Start draining `reader`, remembering at most `max_lines` lines
+    pub fn capture(reader: impl Read + Send + 'static, max_lines: usize) -> Self {
+        let lines = Arc::new(Mutex::new(VecDeque::with_capacity(max_lines)));
+        let sink = Arc::clone(&lines);
+        std::thread::spawn(move || {
+            for line in BufReader::new(reader).lines().map_while(Result::ok) {
+                let line = line.trim().to_string();
+                if line.is_empty() {
+                    continue;
+                }
+                let mut lines = sink.lock().unwrap();
+                if lines.len() == max_lines {
+                    lines.pop_front();
+                }
+                lines.push_back(line);
+            }
+        });
+        Self { lines }
+    }
+
+    /// Lines captured so far, joined with " | " for a one-line status message
+    pub fn summary(&self) -> String {
+        let lines = self.lines.lock().unwrap();
+        lines.iter().cloned().collect::<Vec<_>>().join(" | ")
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::{Context, Result};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Private directory for intermediate files (audio, SRT, overlays) that is
+/// removed when dropped, including when an operation bails out with an error
+#[derive(Debug)]
+pub struct TempWorkspace {
+    dir: PathBuf,
+}
+
+impl TempWorkspace {
+    /// Create a fresh workspace under `base`, or the system temp directory
+    pub fn new(base: Option<&Path>) -> Result<Self> {
+        let base = base
+            .map(Path::to_path_buf)
+            .unwrap_or_else(std::env::temp_dir);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let dir = base.join(format!("auto-subs-tui-{}-{}", std::process::id(), nanos));
+
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create temp directory {}", dir.display()))?;
+
+        Ok(Self { dir })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path for a file inside the workspace
+    pub fn file(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Copy all of stdin into a file inside the workspace. FFmpeg and Whisper
+    /// need a seekable file, so piped input is buffered to disk first.
+    pub fn buffer_stdin(&self, name: &str) -> Result<PathBuf> {
+        let mut stdin = std::io::stdin();
+        if stdin.is_terminal() {
+            anyhow::bail!("Input is '-' but nothing is piped on stdin");
+        }
+
+        let path = self.file(name);
+        let mut file = std::fs::File::create(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        let bytes = std::io::copy(&mut stdin, &mut file).context("Failed to read stdin")?;
+        if bytes == 0 {
+            anyhow::bail!("No data received on stdin");
+        }
+
+        Ok(path)
+    }
+}
+
+/// Whether a path argument is `-`, meaning read from stdin
+pub fn is_stdin(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+impl Drop for TempWorkspace {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
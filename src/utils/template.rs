@@ -0,0 +1,157 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Values of the run-specific output template tokens. Commands that don't
+/// transcribe leave them unset, and a template using them fails there.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateValues {
+    pub model: Option<String>,
+    pub lang: Option<String>,
+}
+
+/// Expand an output template for `input`. Tokens: `{dir}` (the input's
+/// directory), `{stem}` (its name without extension), `{ext}` (the extension
+/// the output gets by default: the video's for a burn, `srt` for a
+/// transcription, ...), `{model}`, `{lang}` and `{date}` (today, as
+/// YYYY-MM-DD). `{{` and `}}` stand for literal braces.
+pub fn expand(template: &str, input: &Path, ext: &str, values: &TemplateValues) -> Result<PathBuf> {
+    let mut expanded = String::new();
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                expanded.push('{');
+            }
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                expanded.push('}');
+            }
+            '}' => anyhow::bail!("Unmatched '}}' in output template '{}'", template),
+            '{' => {
+                let rest = chars.as_str();
+                let end = rest
+                    .find('}')
+                    .with_context(|| format!("Unclosed '{{' in output template '{}'", template))?;
+                expanded.push_str(&token_value(&rest[..end], input, ext, values)?);
+                chars = rest[end + 1..].chars();
+            }
+            c => expanded.push(c),
+        }
+    }
+    if expanded.trim().is_empty() {
+        anyhow::bail!("Output template '{}' expands to an empty path", template);
+    }
+    Ok(PathBuf::from(expanded))
+}
+
+fn token_value(token: &str, input: &Path, ext: &str, values: &TemplateValues) -> Result<String> {
+    let value = match token {
+        "dir" => match input.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_string_lossy().to_string(),
+            _ => ".".to_string(),
+        },
+        "stem" => input
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string(),
+        "ext" => ext.to_string(),
+        "date" => today(),
+        "model" | "lang" => {
+            let value = if token == "model" {
+                &values.model
+            } else {
+                &values.lang
+            };
+            value
+                .clone()
+                .with_context(|| format!("{{{}}} isn't available for this command", token))?
+        }
+        other => anyhow::bail!(
+            "Unknown output template token {{{}}} (expected dir, stem, ext, model, lang or date)",
+            other
+        ),
+    };
+    Ok(value)
+}
+
+/// Where an output goes: `explicit` when given, otherwise `template` expanded
+/// for `input` (creating the directories it names), otherwise `default()`
+pub fn resolve(
+    explicit: Option<PathBuf>,
+    template: Option<&str>,
+    input: &Path,
+    ext: &str,
+    values: &TemplateValues,
+    default: impl FnOnce() -> PathBuf,
+) -> Result<PathBuf> {
+    if let Some(path) = explicit {
+        return Ok(path);
+    }
+    let Some(template) = template else {
+        return Ok(default());
+    };
+    let path = expand(template, input, ext, values)?;
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    Ok(path)
+}
+
+/// Today's date (UTC) as YYYY-MM-DD
+fn today() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let (year, month, day) = civil_date(secs / 86_400);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Calendar date of a day counted from 1970-01-01
+fn civil_date(days: u64) -> (u64, u64, u64) {
+    // Howard Hinnant's days-to-civil algorithm, with years starting in March
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expands_tokens() {
+        let values = TemplateValues {
+            model: Some("small".to_string()),
+            lang: None,
+        };
+        let input = Path::new("videos/talk.mkv");
+        assert_eq!(
+            expand("{dir}/subbed/{stem}.{model}.{ext}", input, "mkv", &values).unwrap(),
+            PathBuf::from("videos/subbed/talk.small.mkv")
+        );
+        assert_eq!(
+            expand("{dir}/{{{stem}}}", Path::new("talk.wav"), "srt", &values).unwrap(),
+            PathBuf::from("./{talk}")
+        );
+        assert!(expand("{stem}.{lang}.srt", input, "srt", &values).is_err());
+        assert!(expand("{title}.mp4", input, "mp4", &values).is_err());
+        assert!(expand("{stem", input, "mp4", &values).is_err());
+
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(19_782), (2024, 2, 29));
+        assert_eq!(today().len(), 10);
+    }
+}